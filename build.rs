@@ -0,0 +1,123 @@
+use std::fmt::Write;
+use std::path::Path;
+use std::{env, fs, process};
+
+#[path = "src/syntatic_analyzer/lalr.rs"]
+mod lalr;
+
+const GRAMMAR: &str = "src/syntatic_analyzer/simplescript.grammar";
+
+/*
+ * Generates the parser tables and everything that has to agree with them from the grammar, each
+ * file is included by the module of the same name in src/syntatic_analyzer.
+ */
+fn main() {
+    println!("cargo:rerun-if-changed={}", GRAMMAR);
+    println!("cargo:rerun-if-changed=src/syntatic_analyzer/lalr.rs");
+
+    let text = fs::read_to_string(GRAMMAR).unwrap_or_else(|error| fail(&format!("{}: {}", GRAMMAR, error)));
+    let grammar = lalr::Grammar::parse(&text).unwrap_or_else(|error| fail(&format!("{}: {}", GRAMMAR, error)));
+    let tables = lalr::build(&grammar);
+    if let Err(report) = tables.check(&grammar) {
+        fail(&format!("{}: {}", GRAMMAR, report));
+    }
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let out_dir = Path::new(&out_dir);
+    for (name, code) in [("constants.rs", constants(&grammar, &tables)),
+                         ("rules.rs", rules(&grammar)),
+                         ("nonterminals.rs", nonterminals(&grammar)),
+                         ("terminals.rs", terminals(&grammar))] {
+        fs::write(out_dir.join(name), code).unwrap();
+    }
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("{}", message);
+    process::exit(1);
+}
+
+fn constants(grammar: &lalr::Grammar, tables: &lalr::Tables) -> String {
+    let columns = grammar.nonterminals.len() + grammar.terminals.len();
+    let compressed = tables.compress();
+    for (state, row) in tables.action.iter().enumerate() {
+        for (column, entry) in row.iter().enumerate() {
+            assert_eq!(compressed.get(state, column), *entry, "compressing changed the action of state {} on column {}", state, column);
+        }
+    }
+    let narrow = |number: usize| u16::try_from(number).ok().filter(|number| *number != u16::MAX)
+        .unwrap_or_else(|| fail("the parser table is too big for u16 states and offsets"));
+
+    let mut code = format!("#[cfg(test)]\npub const STATES: usize = {};\npub const COLUMNS: usize = {};\n\n", tables.action.len(), columns);
+    let base: Vec<String> = compressed.base.iter().map(|base| narrow(*base).to_string()).collect();
+    writeln!(code, "pub const BASE: &[u16] = &[{}];\n", base.join(", ")).unwrap();
+    // every state's row fits whole after its base, so lookups don't run past the end
+    let length = compressed.base.iter().max().unwrap() + columns;
+    let entries: Vec<String> = (0..length)
+        .map(|slot| match compressed.check.get(slot) {
+            Some(Some(owner)) => format!("({}, {})", narrow(*owner), i16::try_from(compressed.value[slot]).unwrap()),
+            _ => format!("({}, 0)", u16::MAX),
+        })
+        .collect();
+    writeln!(code, "pub const ENTRIES: &[(u16, i16)] = &[{}];\n", entries.join(", ")).unwrap();
+
+    // the uncompressed table is only kept for the tests and the benchmark
+    writeln!(code, "#[cfg(test)]\npub const ACTIONTABLE: &[&[i32; {}]; {}] = &[", columns, tables.action.len()).unwrap();
+    for row in tables.action.iter() {
+        let row: Vec<String> = row.iter().map(|entry| entry.to_string()).collect();
+        writeln!(code, "    &[{}],", row.join(", ")).unwrap();
+    }
+    code.push_str("];\n\n");
+
+    let lengths: Vec<String> = grammar.rules.iter().map(|rule| rule.right.len().to_string()).collect();
+    writeln!(code, "pub const RULELEN: &[usize] = &[{}];\n", lengths.join(", ")).unwrap();
+    let left: Vec<String> = grammar.rules.iter().map(|rule| format!("Token::{}", grammar.nonterminals[rule.left])).collect();
+    writeln!(code, "pub const RULELEFT: &[Token] = &[{}];\n", left.join(", ")).unwrap();
+    writeln!(code, "pub const ACCEPT_STATE: usize = {};", tables.accept_state).unwrap();
+    code
+}
+
+fn rules(grammar: &lalr::Grammar) -> String {
+    let mut code = String::from("#[allow(non_camel_case_types, clippy::upper_case_acronyms)]\n#[derive(Debug, Clone, Copy, PartialEq, Eq)]\npub enum Rules {\n");
+    code.push_str("    // what `From<usize>` gives for a number that is not a rule, the tables never reduce by it\n    ERROR,\n");
+    for (index, rule) in grammar.rules.iter().enumerate() {
+        writeln!(code, "    // {}\n    {},", grammar.rule(index), rule.name).unwrap();
+    }
+    code.push_str("}\n\nimpl From<usize> for Rules {\n    fn from(x: usize) -> Rules {\n        match x {\n");
+    for (index, rule) in grammar.rules.iter().enumerate() {
+        writeln!(code, "            {} => Rules::{},", index, rule.name).unwrap();
+    }
+    code.push_str("            _ => Rules::ERROR\n        }\n    }\n}\n");
+    code
+}
+
+fn nonterminals(grammar: &lalr::Grammar) -> String {
+    let mut code = format!("#[allow(clippy::upper_case_acronyms)]\n#[derive(Debug, Clone, Copy, PartialEq, Eq)]\npub enum Token {{\n    {},\n}}\n\n",
+                           grammar.nonterminals.join(", "));
+    code.push_str("impl Token {\n    pub fn into_usize(self) -> usize {\n        match self {\n");
+    for (column, name) in grammar.nonterminals.iter().enumerate() {
+        writeln!(code, "            Token::{} => {},", name, column).unwrap();
+    }
+    code.push_str("        }\n    }\n}\n");
+    code
+}
+
+fn terminals(grammar: &lalr::Grammar) -> String {
+    let first = grammar.nonterminals.len();
+    let mut code = String::from("impl<'a> From<Token<'a>> for usize {\n    fn from(t: Token<'a>) -> Self {\n        match t {\n");
+    for (index, terminal) in grammar.terminals.iter().enumerate() {
+        writeln!(code, "            Token::{} => {},", terminal.pattern, first + index).unwrap();
+    }
+    code.push_str("            // the parser skips lexical errors, they never index ACTIONTABLE\n            Token::ERROR(_) => usize::MAX,\n");
+    code.push_str("        }\n    }\n}\n\n");
+
+    code.push_str("// ACTIONTABLE columns before this one belong to non terminals\n");
+    writeln!(code, "pub const FIRST_TERMINAL: usize = {};\n", first).unwrap();
+    code.push_str("// How a terminal is shown to the user in error messages, indexed by its ACTIONTABLE column\n");
+    code.push_str("pub fn terminal_name(column: usize) -> &'static str {\n    match column {\n");
+    for (index, terminal) in grammar.terminals.iter().enumerate() {
+        writeln!(code, "        {} => {:?},", first + index, terminal.display).unwrap();
+    }
+    code.push_str("        _ => \"unknown token\",\n    }\n}\n");
+    code
+}
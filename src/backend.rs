@@ -0,0 +1,205 @@
+/*
+ * Translations of checked programs to other languages. They share how they see the types and
+ * left values of the program.
+ */
+
+use std::collections::HashSet;
+
+use crate::syntatic_analyzer::ast;
+use crate::syntatic_analyzer::nonterminals::{Kind, Type};
+
+pub mod c;
+pub mod llvm;
+pub mod wat;
+
+// The signature the scope analyzer records for a function
+fn signature(function: &ast::FunctionDecl) -> Kind {
+    Kind::function(function.return_type.type_.clone(), function.params.iter().map(|param| param.type_.type_.clone()).collect())
+}
+
+fn resolve(type_: &Type) -> &Type {
+    match type_ {
+        Type::Alias_type_(type_) => resolve(type_),
+        type_ => type_,
+    }
+}
+
+fn fields(type_: &Type) -> Vec<(&str, &Type)> {
+    match resolve(type_) {
+        Type::Struct_type_(fields) => fields.iter()
+            .filter_map(|field| match &field.1 {
+                Kind::field(type_) => Some((field.0.as_str(), type_)),
+                _ => None
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+// Whether evaluating `expr` can fail or change a variable
+fn has_effects(expr: &ast::Expr) -> bool {
+    match &expr.kind {
+        ast::ExprKind::Call { .. } | ast::ExprKind::IncDec { .. } => true,
+        ast::ExprKind::Binary { op: ast::BinaryOp::Div, .. } if expr.type_ == Type::Int_ => true,
+        ast::ExprKind::Binary { left, right, .. } => has_effects(left) || has_effects(right),
+        ast::ExprKind::Unary { operand, .. } | ast::ExprKind::Convert(operand) => has_effects(operand),
+        ast::ExprKind::LValue(lvalue) => !indices(lvalue).is_empty(),
+        _ => false
+    }
+}
+
+fn changes_variables(expr: &ast::Expr) -> bool {
+    match &expr.kind {
+        ast::ExprKind::IncDec { .. } => true,
+        ast::ExprKind::Binary { left, right, .. } => changes_variables(left) || changes_variables(right),
+        ast::ExprKind::Unary { operand, .. } | ast::ExprKind::Convert(operand) => changes_variables(operand),
+        ast::ExprKind::Call { args, .. } => args.iter().any(changes_variables),
+        ast::ExprKind::LValue(lvalue) => indices(lvalue).into_iter().any(|(index, _)| changes_variables(index)),
+        _ => false
+    }
+}
+
+// The indices of a left value in the order they are written, with the type of the array each one indexes
+fn indices(lvalue: &ast::LValue) -> Vec<(&ast::Expr, &Type)> {
+    match &lvalue.kind {
+        ast::LValueKind::Variable(_) => vec![],
+        ast::LValueKind::Field { base, .. } => indices(base),
+        ast::LValueKind::Index { base, index } => {
+            let mut indices = indices(base);
+            indices.push((index, &base.type_));
+            indices
+        },
+    }
+}
+
+fn array_length(type_: &Type) -> u32 {
+    match resolve(type_) {
+        Type::Array_type_(length, _) => *length,
+        _ => unreachable!("the semantic analysis checks indexing"),
+    }
+}
+
+// Whether `expr` converts its operand to the type it already has, which changes nothing
+fn converts_to_same_type(expr: &ast::Expr) -> bool {
+    matches!(&expr.kind, ast::ExprKind::Convert(operand) if operand.type_ == expr.type_)
+}
+
+// A name like `name` that isn't taken yet, shadowed variables get `name.1`, `name.2`, ...
+fn unique_name(taken: &mut HashSet<String>, name: &str) -> String {
+    let mut unique = name.to_string();
+    let mut suffix = 0;
+    while taken.contains(&unique) {
+        suffix += 1;
+        unique = format!("{}.{}", name, suffix);
+    }
+    taken.insert(unique.clone());
+    unique
+}
+
+/*
+ * The parts of an array or struct value, in order. Aggregates are compared part by part, the
+ * first one that differs decides like it does for the interpreter's values, and printed the
+ * same way.
+ */
+enum Parts<'t> {
+    Elements(u32, &'t Type),
+    Fields(Vec<(&'t str, &'t Type)>),
+}
+
+fn parts(type_: &Type) -> Parts<'_> {
+    match resolve(type_) {
+        Type::Array_type_(length, element) => Parts::Elements(*length, element),
+        type_ => Parts::Fields(fields(type_)),
+    }
+}
+
+/*
+ * The arrays and structs of a program with the names a backend gives them, every one after
+ * the ones it contains, so their definitions can be written in order. Each is named after the
+ * first type declared with it, the others get a number.
+ */
+struct Aggregates {
+    types: Vec<(Type, String)>,
+    // which of them need a function comparing or printing their values
+    compared: Vec<bool>,
+    printed: Vec<bool>,
+    // the name of a declared type, and of the n-th one if it is an undeclared `array` or `struct`
+    declared: fn(&str) -> String,
+    numbered: fn(&str, usize) -> String,
+}
+
+impl Aggregates {
+    fn new(declared: fn(&str) -> String, numbered: fn(&str, usize) -> String) -> Self {
+        Aggregates { types: vec![], compared: vec![], printed: vec![], declared, numbered }
+    }
+
+    // The index of the aggregate `type_`, added after the aggregates in it if it's new
+    fn intern(&mut self, type_: &Type, name: Option<&str>) -> usize {
+        let type_ = resolve(type_);
+        if let Some(index) = self.index(type_) {
+            return index;
+        }
+        let contained = match type_ {
+            Type::Array_type_(_, element) => vec![element.as_ref()],
+            Type::Struct_type_(_) => fields(type_).into_iter().map(|(_, field)| field).collect(),
+            _ => unreachable!("only arrays and structs are aggregates"),
+        };
+        for contained in contained {
+            if let Type::Array_type_(..) | Type::Struct_type_(_) = resolve(contained) {
+                self.intern(contained, None);
+            }
+        }
+        let name = match name {
+            Some(name) => (self.declared)(name),
+            None if matches!(type_, Type::Array_type_(..)) => (self.numbered)("array", self.types.len()),
+            None => (self.numbered)("struct", self.types.len()),
+        };
+        self.types.push((type_.clone(), name));
+        self.compared.push(false);
+        self.printed.push(false);
+        self.types.len() - 1
+    }
+
+    fn index(&self, type_: &Type) -> Option<usize> {
+        self.types.iter().position(|(known, _)| known == resolve(type_))
+    }
+
+    // The name of an aggregate already interned
+    fn name(&self, type_: &Type) -> &str {
+        &self.types[self.index(type_).expect("the aggregate was interned")].1
+    }
+
+    // What the helpers for values of `type_` are named after: the scalar type, or the name of the aggregate
+    fn helper_name(&self, type_: &Type) -> &str {
+        match resolve(type_) {
+            Type::Int_ => "integer",
+            Type::Real_ => "real",
+            Type::Char_ => "char",
+            Type::Bool_ => "boolean",
+            Type::String_ => "string",
+            aggregate => self.name(aggregate),
+        }
+    }
+
+    /*
+     * helper_name, marking the aggregate and the ones in it as needing a `compare` or `print`
+     * function. The functions of the values it contains come before it.
+     */
+    fn helper(&mut self, type_: &Type, what: &str) -> String {
+        if let aggregate @ (Type::Array_type_(..) | Type::Struct_type_(_)) = resolve(type_) {
+            let index = self.intern(aggregate, None);
+            let needed = if what == "compare" { &mut self.compared[index] } else { &mut self.printed[index] };
+            if !*needed {
+                *needed = true;
+                let parts: Vec<Type> = match parts(aggregate) {
+                    Parts::Elements(_, element) => vec![element.clone()],
+                    Parts::Fields(fields) => fields.into_iter().map(|(_, field)| field.clone()).collect(),
+                };
+                for part in parts.iter() {
+                    self.helper(part, what);
+                }
+            }
+        }
+        self.helper_name(type_).to_string()
+    }
+}
@@ -0,0 +1,620 @@
+use std::fmt::Write;
+
+use super::{array_length, changes_variables, converts_to_same_type, fields, has_effects, indices, parts, resolve, signature, Aggregates, Parts};
+use crate::interpreter::Value;
+use crate::syntatic_analyzer::ast;
+use crate::syntatic_analyzer::nonterminals::{Kind, Type};
+
+/*
+ * Translates a program without errors into C99, preceded by the runtime in simplescript.h.
+ *
+ * integer, real, char and boolean are int32_t, double, uint32_t and bool, a char being the
+ * number of the Unicode character, strings are the length prefixed UTF-8 ss_string of the
+ * runtime. Arrays and structs are C structs, arrays wrapping
+ * a fixed C array so they are copied on assignment and passed by value like any Simplescript
+ * value. Each struct is named after the first type declared with it.
+ *
+ * C leaves the order operands are evaluated in unspecified, so operands that fail or change
+ * a variable are stored in temporaries when the order would show. Calls are not counted: a
+ * recursion too deep for the C stack crashes instead of stopping with a stack overflow error.
+ */
+
+pub const RUNTIME: &str = include_str!("simplescript.h");
+
+// Names the runtime or the headers it includes already use, a program's names are escaped if they are one of them
+const RESERVED: &[&str] = &[
+    "auto", "break", "case", "char", "const", "continue", "default", "do", "double", "else", "enum", "extern", "float",
+    "for", "goto", "if", "inline", "int", "long", "register", "restrict", "return", "short", "signed", "sizeof",
+    "static", "struct", "switch", "typedef", "union", "unsigned", "void", "volatile", "while", "_Bool", "_Complex",
+    "_Imaginary", "main", "bool", "true", "false", "int8_t", "int16_t", "int32_t", "int64_t", "uint8_t", "uint16_t",
+    "uint32_t", "uint64_t", "intptr_t", "uintptr_t", "intmax_t", "uintmax_t", "INT8_MIN", "INT8_MAX", "INT16_MIN",
+    "INT16_MAX", "INT32_MIN", "INT32_MAX", "INT64_MIN", "INT64_MAX", "UINT8_MAX", "UINT16_MAX", "UINT32_MAX",
+    "UINT64_MAX", "INTMAX_MIN", "INTMAX_MAX", "UINTMAX_MAX", "SIZE_MAX", "FILE", "fpos_t", "size_t", "NULL", "EOF",
+    "BUFSIZ", "FILENAME_MAX", "FOPEN_MAX", "L_tmpnam", "SEEK_CUR", "SEEK_END", "SEEK_SET", "TMP_MAX", "stderr",
+    "stdin", "stdout", "remove", "rename", "tmpfile", "tmpnam", "fclose", "fflush", "fopen", "freopen", "setbuf",
+    "setvbuf", "fprintf", "fscanf", "printf", "scanf", "snprintf", "sprintf", "sscanf", "vfprintf", "vfscanf",
+    "vprintf", "vscanf", "vsnprintf", "vsprintf", "vsscanf", "fgetc", "fgets", "fputc", "fputs", "getc", "getchar",
+    "gets", "putc", "putchar", "puts", "ungetc", "fread", "fwrite", "fgetpos", "fseek", "fsetpos", "ftell", "rewind",
+    "clearerr", "feof", "ferror", "perror", "div_t", "ldiv_t", "lldiv_t", "wchar_t", "EXIT_FAILURE", "EXIT_SUCCESS",
+    "RAND_MAX", "MB_CUR_MAX", "atof", "atoi", "atol", "atoll", "strtod", "strtof", "strtold", "strtol", "strtoll",
+    "strtoul", "strtoull", "rand", "srand", "calloc", "free", "malloc", "realloc", "abort", "atexit", "exit", "_Exit",
+    "getenv", "system", "bsearch", "qsort", "abs", "labs", "llabs", "div", "ldiv", "lldiv", "mblen", "mbtowc",
+    "wctomb", "mbstowcs", "wcstombs", "memcpy", "memmove", "strcpy", "strncpy", "strcat", "strncat", "memcmp",
+    "strcmp", "strcoll", "strncmp", "strxfrm", "memchr", "strchr", "strcspn", "strpbrk", "strrchr", "strspn",
+    "strstr", "strtok", "memset", "strerror", "strlen",
+];
+
+// The C name of a name of the program: reserved names, and the ones that could be mistaken for them, end in `_`
+fn escape(name: &str) -> String {
+    let reserved = RESERVED.contains(&name) || name.starts_with("ss_") || name.starts_with("SS_") || name.ends_with('_');
+    if reserved { format!("{}_", name) } else { name.to_string() }
+}
+
+fn is_literal(expr: &ast::Expr) -> bool {
+    matches!(expr.kind, ast::ExprKind::Integer(_) | ast::ExprKind::Real(_) | ast::ExprKind::Char(_) |
+                        ast::ExprKind::Bool(_) | ast::ExprKind::String(_))
+}
+
+// An operation applied to operands that may have to be evaluated first: `(t0 = a, t1 = b, op)`
+fn sequenced(prefix: Vec<String>, expr: String) -> String {
+    if prefix.is_empty() { expr } else { format!("({}, {})", prefix.join(", "), expr) }
+}
+
+// `expr` without the parentheses around it, where the statement already has its own
+fn unparenthesized(expr: &str) -> &str {
+    let inner = match expr.strip_prefix('(').and_then(|expr| expr.strip_suffix(')')) {
+        Some(inner) => inner,
+        None => return expr,
+    };
+    // `(a) + (b)` starts and ends with parentheses that don't match each other, and the
+    // parentheses of `(t0 = a, b)` keep the comma from separating arguments
+    let mut depth = 0;
+    let mut quote = None;
+    let mut escaped = false;
+    for ch in inner.chars() {
+        match (quote, ch) {
+            (Some(_), _) if escaped => escaped = false,
+            (Some(_), '\\') => escaped = true,
+            (Some(open), ch) if ch == open => quote = None,
+            (Some(_), _) => (),
+            (None, '"' | '\'') => quote = Some(ch),
+            (None, '(') => depth += 1,
+            (None, ')' | ',') if depth == 0 => return expr,
+            (None, ')') => depth -= 1,
+            _ => ()
+        }
+    }
+    inner
+}
+
+fn char_literal(ch: char) -> String {
+    match ch {
+        '\'' => String::from("'\\''"),
+        '\\' => String::from("'\\\\'"),
+        '\n' => String::from("'\\n'"),
+        '\t' => String::from("'\\t'"),
+        '\r' => String::from("'\\r'"),
+        '\0' => String::from("'\\0'"),
+        ' '..='~' => format!("'{}'", ch),
+        _ => format!("0x{:x}", u32::from(ch)),
+    }
+}
+
+fn string_literal(s: &str) -> String {
+    let mut literal = String::from("SS_STRING(\"");
+    let bytes = s.as_bytes();
+    for (i, byte) in bytes.iter().enumerate() {
+        match byte {
+            b'"' => literal.push_str("\\\""),
+            b'\\' => literal.push_str("\\\\"),
+            b'\n' => literal.push_str("\\n"),
+            b'\t' => literal.push_str("\\t"),
+            // `??` would start a trigraph
+            b'?' if bytes.get(i + 1) == Some(&b'?') => literal.push_str("\\?"),
+            b' '..=b'~' => literal.push(*byte as char),
+            _ => write!(literal, "\\{:03o}", byte).unwrap(),
+        }
+    }
+    literal.push_str("\")");
+    literal
+}
+
+fn integer_literal(n: i32) -> String {
+    match n {
+        i32::MIN => String::from("(-2147483647 - 1)"),
+        n if n < 0 => format!("({})", n),
+        n => n.to_string(),
+    }
+}
+
+fn real_literal(x: f64) -> String {
+    match x {
+        // too large for a double, C reads it as infinity too
+        x if x.is_infinite() => String::from(if x > 0.0 { "1e999" } else { "(-1e999)" }),
+        x if x < 0.0 => format!("({:?})", x),
+        x => format!("{:?}", x),
+    }
+}
+
+/*
+ * Translates `program`. With an entry call the C program gets a `main` that makes the call
+ * and prints the value it returns, as `--run` does.
+ */
+pub fn generate(program: &ast::Program, entry: Option<(&str, &[Value])>) -> String {
+    let mut generator = Generator { aggregates: Aggregates::new(escape, |kind, n| format!("ss_{}{}", kind, n)) };
+    for declaration in program.declarations.iter() {
+        if let ast::Declaration::Type(type_decl) = declaration {
+            if !matches!(type_decl.kind, ast::TypeDeclKind::Alias(_)) {
+                generator.aggregates.intern(&type_decl.type_, Some(&type_decl.name.name));
+            }
+        }
+    }
+
+    let mut prototypes = String::new();
+    let mut functions = String::new();
+    for declaration in program.declarations.iter() {
+        if let ast::Declaration::Function(function) = declaration {
+            let Kind::function(return_type, params) = signature(function) else { unreachable!() };
+            let params: Vec<String> = params.iter().map(|type_| generator.c_type(type_)).collect();
+            writeln!(prototypes, "{} {}({});", generator.c_type(&return_type), escape(&function.name.name), params.join(", ")).unwrap();
+            functions.push('\n');
+            functions.push_str(&FunctionGenerator::new(&mut generator).generate(function));
+        }
+    }
+
+    let main = entry.map(|(function, args)| {
+        let return_type = program.declarations.iter()
+            .find_map(|declaration| match declaration {
+                ast::Declaration::Function(declared) if declared.name.name == function => Some(&declared.return_type.type_),
+                _ => None
+            })
+            .expect("the entry call is checked against the program");
+        let args: Vec<String> = args.iter().map(value_literal).collect();
+        let print = generator.print(return_type);
+        format!("\nint main(void) {{\n    {}({}({}));\n    putchar('\\n');\n    return 0;\n}}\n",
+                print, escape(function), args.join(", "))
+    });
+
+    let mut c = String::from(RUNTIME);
+    let aggregates = &generator.aggregates;
+    for (type_, name) in aggregates.types.iter() {
+        c.push('\n');
+        c.push_str(&generator.definition(type_, name));
+    }
+    for (index, (type_, name)) in aggregates.types.iter().enumerate() {
+        if aggregates.compared[index] {
+            c.push('\n');
+            c.push_str(&generator.compare_function(type_, name));
+        }
+        if aggregates.printed[index] {
+            c.push('\n');
+            c.push_str(&generator.print_function(type_, name));
+        }
+    }
+    if !prototypes.is_empty() {
+        c.push('\n');
+        c.push_str(&prototypes);
+    }
+    c.push_str(&functions);
+    c.push_str(&main.unwrap_or_default());
+    c
+}
+
+fn value_literal(value: &Value) -> String {
+    match value {
+        Value::Integer(n) => integer_literal(*n),
+        Value::Real(x) => real_literal(*x),
+        Value::Char(ch) => char_literal(*ch),
+        Value::Boolean(b) => b.to_string(),
+        Value::String(s) => string_literal(s),
+        Value::Array(_) | Value::Struct(_) => unreachable!("calls only have scalar and string arguments"),
+    }
+}
+
+struct Generator {
+    aggregates: Aggregates,
+}
+
+impl Generator {
+    fn c_type(&mut self, type_: &Type) -> String {
+        match resolve(type_) {
+            Type::Int_ => String::from("int32_t"),
+            Type::Real_ => String::from("double"),
+            Type::Char_ => String::from("uint32_t"),
+            Type::Bool_ => String::from("bool"),
+            Type::String_ => String::from("ss_string"),
+            Type::Array_type_(..) | Type::Struct_type_(_) => {
+                self.aggregates.intern(type_, None);
+                format!("struct {}", self.aggregates.name(type_))
+            },
+            Type::Alias_type_(_) | Type::Universal_ => unreachable!("checked programs have no erroneous types"),
+        }
+    }
+
+    fn definition(&self, type_: &Type, name: &str) -> String {
+        let mut definition = format!("struct {} {{\n", name);
+        match type_ {
+            Type::Array_type_(length, element) => {
+                writeln!(definition, "    {} items[{}];", self.known_type(element), length).unwrap();
+            },
+            _ => {
+                for (field, type_) in fields(type_) {
+                    writeln!(definition, "    {} {};", self.known_type(type_), escape(field)).unwrap();
+                }
+            },
+        }
+        definition.push_str("};\n");
+        definition
+    }
+
+    // The C type of a type already interned
+    fn known_type(&self, type_: &Type) -> String {
+        match resolve(type_) {
+            Type::Array_type_(..) | Type::Struct_type_(_) => format!("struct {}", self.aggregates.name(type_)),
+            Type::Int_ => String::from("int32_t"),
+            Type::Real_ => String::from("double"),
+            Type::Char_ => String::from("uint32_t"),
+            Type::Bool_ => String::from("bool"),
+            Type::String_ => String::from("ss_string"),
+            Type::Alias_type_(_) | Type::Universal_ => unreachable!(),
+        }
+    }
+
+    // The runtime or generated function comparing values of `type_`
+    fn compare(&mut self, type_: &Type) -> String {
+        format!("ss_compare_{}", self.aggregates.helper(type_, "compare"))
+    }
+
+    fn print(&mut self, type_: &Type) -> String {
+        format!("ss_print_{}", self.aggregates.helper(type_, "print"))
+    }
+
+    // The name of the helper of a type whose helpers were already requested
+    fn known_helper(&self, type_: &Type, what: &str) -> String {
+        format!("ss_{}_{}", what, self.aggregates.helper_name(type_))
+    }
+
+    fn compare_function(&self, type_: &Type, name: &str) -> String {
+        let mut function = format!("static int ss_compare_{0}(struct {0} a, struct {0} b) {{\n", name);
+        match parts(type_) {
+            Parts::Elements(length, element) => {
+                function.push_str("    int order = 0;\n    uint32_t i;\n");
+                writeln!(function, "    for (i = 0; i < {} && order == 0; i++) {{", length).unwrap();
+                writeln!(function, "        order = {}(a.items[i], b.items[i]);", self.known_helper(element, "compare")).unwrap();
+                function.push_str("    }\n");
+            },
+            Parts::Fields(fields) => {
+                function.push_str("    int order = 0;\n");
+                for (field, type_) in fields {
+                    let field = escape(field);
+                    writeln!(function, "    if (order == 0) {{\n        order = {}(a.{1}, b.{1});\n    }}",
+                             self.known_helper(type_, "compare"), field).unwrap();
+                }
+            },
+        }
+        function.push_str("    return order;\n}\n");
+        function
+    }
+
+    fn print_function(&self, type_: &Type, name: &str) -> String {
+        let mut function = format!("static void ss_print_{0}(struct {0} value) {{\n", name);
+        match parts(type_) {
+            Parts::Elements(length, element) => {
+                function.push_str("    uint32_t i;\n    putchar('[');\n");
+                writeln!(function, "    for (i = 0; i < {}; i++) {{", length).unwrap();
+                function.push_str("        if (i > 0) {\n            fputs(\", \", stdout);\n        }\n");
+                writeln!(function, "        {}(value.items[i]);", self.known_helper(element, "print")).unwrap();
+                function.push_str("    }\n    putchar(']');\n");
+            },
+            Parts::Fields(fields) => {
+                for (i, (field, type_)) in fields.into_iter().enumerate() {
+                    writeln!(function, "    fputs(\"{}{} = \", stdout);", if i == 0 { "{ " } else { ", " }, field).unwrap();
+                    writeln!(function, "    {}(value.{});", self.known_helper(type_, "print"), escape(field)).unwrap();
+                }
+                function.push_str("    fputs(\" }\", stdout);\n");
+            },
+        }
+        function.push_str("}\n");
+        function
+    }
+}
+
+struct FunctionGenerator<'g> {
+    generator: &'g mut Generator,
+    body: String,
+    indent: usize,
+    // the temporaries that keep operands in order, declared at the start of the function
+    temps: Vec<String>,
+}
+
+impl<'g> FunctionGenerator<'g> {
+    fn new(generator: &'g mut Generator) -> Self {
+        FunctionGenerator { generator, body: String::new(), indent: 1, temps: vec![] }
+    }
+
+    fn generate(mut self, function: &ast::FunctionDecl) -> String {
+        let params: Vec<String> = function.params.iter()
+            .map(|param| format!("{} {}", self.generator.c_type(&param.type_.type_), escape(&param.name.name)))
+            .collect();
+        let header = format!("{} {}({}) {{\n", self.generator.c_type(&function.return_type.type_), escape(&function.name.name), params.join(", "));
+        self.block_contents(&function.body);
+
+        let mut c = header;
+        for temp in self.temps.iter() {
+            writeln!(c, "    {};", temp).unwrap();
+        }
+        c.push_str(&self.body);
+        c.push_str("}\n");
+        c
+    }
+
+    fn line(&mut self, line: &str) {
+        for _ in 0..self.indent {
+            self.body.push_str("    ");
+        }
+        self.body.push_str(line);
+        self.body.push('\n');
+    }
+
+    fn temp(&mut self, type_: &Type) -> String {
+        let name = format!("ss_t{}", self.temps.len());
+        let declaration = format!("{} {}", self.generator.c_type(type_), name);
+        self.temps.push(declaration);
+        name
+    }
+
+    fn block_contents(&mut self, block: &ast::Block) {
+        for var in block.vars.iter() {
+            let type_ = self.generator.c_type(&var.type_.type_);
+            // variables start as zero, like the interpreter's
+            let zero = match resolve(&var.type_.type_) {
+                Type::Int_ | Type::Real_ | Type::Char_ | Type::Bool_ => "0",
+                _ => "{0}",
+            };
+            for name in var.names.iter() {
+                self.line(&format!("{} {} = {};", type_, escape(&name.name), zero));
+            }
+        }
+        for stmt in block.stmts.iter() {
+            self.stmt(stmt);
+        }
+    }
+
+    // The statements of a branch or a loop body, between the braces the caller writes
+    fn body(&mut self, stmt: &ast::Stmt) {
+        self.indent += 1;
+        match &stmt.kind {
+            ast::StmtKind::Block(block) => self.block_contents(block),
+            _ => self.stmt(stmt),
+        }
+        self.indent -= 1;
+    }
+
+    fn stmt(&mut self, stmt: &ast::Stmt) {
+        match &stmt.kind {
+            ast::StmtKind::If { condition, then_branch, else_branch } => {
+                let condition = self.expr(condition);
+                self.line(&format!("if ({}) {{", unparenthesized(&condition)));
+                self.body(then_branch);
+                let mut else_branch = else_branch.as_deref();
+                // `else if` chains stay flat
+                while let Some(ast::Stmt { kind: ast::StmtKind::If { condition, then_branch, else_branch: next }, .. }) = else_branch {
+                    let condition = self.expr(condition);
+                    self.line(&format!("}} else if ({}) {{", unparenthesized(&condition)));
+                    self.body(then_branch);
+                    else_branch = next.as_deref();
+                }
+                if let Some(else_branch) = else_branch {
+                    self.line("} else {");
+                    self.body(else_branch);
+                }
+                self.line("}");
+            },
+            ast::StmtKind::While { condition, body, .. } => {
+                let condition = self.expr(condition);
+                self.line(&format!("while ({}) {{", unparenthesized(&condition)));
+                self.body(body);
+                self.line("}");
+            },
+            ast::StmtKind::DoWhile { body, condition, .. } => {
+                self.line("do {");
+                self.body(body);
+                let condition = self.expr(condition);
+                self.line(&format!("}} while ({});", unparenthesized(&condition)));
+            },
+            ast::StmtKind::Block(block) => {
+                self.line("{");
+                self.indent += 1;
+                self.block_contents(block);
+                self.indent -= 1;
+                self.line("}");
+            },
+            ast::StmtKind::Assign { target, value } => {
+                let (prefix, place, value) = self.place(target, Some(value));
+                for expr in prefix {
+                    self.line(&format!("{};", expr));
+                }
+                self.line(&format!("{} = {};", place, unparenthesized(&value.unwrap())));
+            },
+            // loops only leave or continue the innermost loop, as in C
+            ast::StmtKind::Break(_) => self.line("break;"),
+            ast::StmtKind::Continue(_) => self.line("continue;"),
+            ast::StmtKind::Return(value) => {
+                let value = self.expr(value);
+                self.line(&format!("return {};", unparenthesized(&value)));
+            },
+            ast::StmtKind::Error => unreachable!("checked programs have no erroneous statements"),
+        }
+    }
+
+    /*
+     * Evaluates `operands` left to right: an operand is stored in a temporary when a later one
+     * changes a variable, or when both can fail. With `reused` every operand that can fail is
+     * stored, its text is going to be written twice.
+     */
+    fn operands(&mut self, operands: &[&ast::Expr], reused: bool) -> (Vec<String>, Vec<String>) {
+        let mut prefix = vec![];
+        let mut texts = vec![];
+        for (i, operand) in operands.iter().enumerate() {
+            let later = &operands[i + 1..];
+            let text = self.expr(operand);
+            let store = !is_literal(operand) && (later.iter().any(|later| changes_variables(later)) ||
+                                                 changes_variables(operand) && !later.iter().all(|later| is_literal(later)) ||
+                                                 has_effects(operand) && (reused || later.iter().any(|later| has_effects(later))));
+            if store {
+                let temp = self.temp(&operand.type_);
+                prefix.push(format!("{} = {}", temp, unparenthesized(&text)));
+                texts.push(temp);
+            } else {
+                texts.push(text);
+            }
+        }
+        (prefix, texts)
+    }
+
+    /*
+     * A left value, and the value assigned to it if there is one. Like the interpreter the
+     * indices are checked once all of them and the value are known, so with several indices, or
+     * a value that can fail, the checks are done before the place is used.
+     */
+    fn place(&mut self, lvalue: &ast::LValue, value: Option<&ast::Expr>) -> (Vec<String>, String, Option<String>) {
+        let indices = indices(lvalue);
+        let deferred = indices.len() > 1 || (indices.len() == 1 && value.is_some_and(has_effects));
+        let mut operands: Vec<&ast::Expr> = indices.iter().map(|(index, _)| *index).collect();
+        operands.extend(value);
+        let (mut prefix, mut texts) = self.operands(&operands, deferred);
+        let value = value.map(|_| texts.pop().unwrap());
+
+        let mut checked = vec![];
+        for ((index, array), text) in indices.iter().zip(texts) {
+            // the runtime checks the indices of strings when it reads or replaces the character
+            if *resolve(array) == Type::String_ {
+                checked.push(text);
+                continue;
+            }
+            let start = index.span.start;
+            let check = format!("ss_index({}, {}, {}, {})", unparenthesized(&text), array_length(array), start.line, start.column);
+            if deferred {
+                prefix.push(check);
+                checked.push(text);
+            } else {
+                checked.push(check);
+            }
+        }
+        let mut checked = checked.into_iter();
+        match (&lvalue.kind, &value) {
+            // a string is a value, assigning one of its characters assigns a new string
+            (ast::LValueKind::Index { base, index }, Some(value)) if *resolve(&base.type_) == Type::String_ => {
+                let place = self.access(base, &mut checked);
+                let start = index.span.start;
+                let value = format!("ss_with_char({}, {}, {}, {}, {})", place, unparenthesized(&checked.next().unwrap()),
+                                    unparenthesized(value), start.line, start.column);
+                (prefix, place, Some(value))
+            },
+            _ => (prefix, self.access(lvalue, &mut checked), value),
+        }
+    }
+
+    fn access(&mut self, lvalue: &ast::LValue, indices: &mut impl Iterator<Item = String>) -> String {
+        match &lvalue.kind {
+            ast::LValueKind::Variable(name) => escape(&name.name),
+            ast::LValueKind::Field { base, field } => format!("{}.{}", self.access(base, indices), escape(&field.name)),
+            ast::LValueKind::Index { base: string, index } if *resolve(&string.type_) == Type::String_ => {
+                let string = self.access(string, indices);
+                let start = index.span.start;
+                format!("ss_char_at({}, {}, {}, {})", string, unparenthesized(&indices.next().unwrap()), start.line, start.column)
+            },
+            ast::LValueKind::Index { base, .. } => {
+                let base = self.access(base, indices);
+                format!("{}.items[{}]", base, indices.next().unwrap())
+            },
+        }
+    }
+
+    fn expr(&mut self, expr: &ast::Expr) -> String {
+        match &expr.kind {
+            ast::ExprKind::Integer(n) => integer_literal(*n),
+            ast::ExprKind::Real(x) => real_literal(*x),
+            ast::ExprKind::Char(ch) => char_literal(*ch),
+            ast::ExprKind::Bool(b) => b.to_string(),
+            ast::ExprKind::String(s) => string_literal(s),
+            ast::ExprKind::LValue(lvalue) => {
+                let (prefix, place, _) = self.place(lvalue, None);
+                sequenced(prefix, place)
+            },
+            // C's `&&` and `||` already skip their right operand
+            ast::ExprKind::Binary { op: op @ (ast::BinaryOp::And | ast::BinaryOp::Or), left, right } => {
+                let left = self.expr(left);
+                let right = self.expr(right);
+                format!("({} {} {})", left, if *op == ast::BinaryOp::And { "&&" } else { "||" }, right)
+            },
+            ast::ExprKind::Binary { op, left, right } => {
+                let (prefix, texts) = self.operands(&[left, right], false);
+                let (a, b) = (&texts[0], &texts[1]);
+                let start = expr.span.start;
+                let operation = match (op, resolve(&left.type_)) {
+                    (ast::BinaryOp::Add, Type::Int_) => format!("ss_add({}, {})", unparenthesized(a), unparenthesized(b)),
+                    (ast::BinaryOp::Sub, Type::Int_) => format!("ss_sub({}, {})", unparenthesized(a), unparenthesized(b)),
+                    (ast::BinaryOp::Mul, Type::Int_) => format!("ss_mul({}, {})", unparenthesized(a), unparenthesized(b)),
+                    (ast::BinaryOp::Div, Type::Int_) => {
+                        format!("ss_div({}, {}, {}, {})", unparenthesized(a), unparenthesized(b), start.line, start.column)
+                    },
+                    (ast::BinaryOp::Add, Type::String_) => format!("ss_concat({}, {})", unparenthesized(a), unparenthesized(b)),
+                    (ast::BinaryOp::Add | ast::BinaryOp::Sub | ast::BinaryOp::Mul | ast::BinaryOp::Div, _) => {
+                        format!("({} {} {})", a, binary_operator(*op), b)
+                    },
+                    // numbers and booleans are compared by C, everything else by the functions of the runtime
+                    (_, Type::Int_ | Type::Real_ | Type::Bool_) => format!("({} {} {})", a, binary_operator(*op), b),
+                    (_, type_) => {
+                        let compare = self.generator.compare(type_);
+                        format!("({}({}, {}) {} 0)", compare, unparenthesized(a), unparenthesized(b), binary_operator(*op))
+                    },
+                };
+                sequenced(prefix, operation)
+            },
+            ast::ExprKind::Unary { op: ast::UnaryOp::Negate, operand } if resolve(&operand.type_) == &Type::Int_ => {
+                format!("ss_neg({})", unparenthesized(&self.expr(operand)))
+            },
+            ast::ExprKind::Unary { op, operand } => {
+                let operand = self.expr(operand);
+                format!("({}{})", if *op == ast::UnaryOp::Negate { "-" } else { "!" }, operand)
+            },
+            ast::ExprKind::Convert(operand) if converts_to_same_type(expr) => self.expr(operand),
+            ast::ExprKind::Convert(operand) if expr.type_ == Type::Real_ => format!("((double){})", self.expr(operand)),
+            ast::ExprKind::Convert(operand) => format!("ss_to_integer({})", unparenthesized(&self.expr(operand))),
+            ast::ExprKind::IncDec { op, target } => {
+                let (prefix, place, _) = self.place(target, None);
+                let (delta, is_prefix) = match op {
+                    ast::IncDecOp::PreIncrement => (1, true),
+                    ast::IncDecOp::PreDecrement => (-1, true),
+                    ast::IncDecOp::PostIncrement => (1, false),
+                    ast::IncDecOp::PostDecrement => (-1, false),
+                };
+                sequenced(prefix, format!("ss_step(&{}, {}, {})", place, delta, is_prefix))
+            },
+            ast::ExprKind::Call { function, args } => {
+                let args: Vec<&ast::Expr> = args.iter().collect();
+                let (prefix, texts) = self.operands(&args, false);
+                let args: Vec<&str> = texts.iter().map(|text| unparenthesized(text)).collect();
+                sequenced(prefix, format!("{}({})", escape(&function.name), args.join(", ")))
+            },
+        }
+    }
+}
+
+fn binary_operator(op: ast::BinaryOp) -> &'static str {
+    match op {
+        ast::BinaryOp::And => "&&",
+        ast::BinaryOp::Or => "||",
+        ast::BinaryOp::Less => "<",
+        ast::BinaryOp::Greater => ">",
+        ast::BinaryOp::LessEqual => "<=",
+        ast::BinaryOp::GreaterEqual => ">=",
+        ast::BinaryOp::Equal => "==",
+        ast::BinaryOp::NotEqual => "!=",
+        ast::BinaryOp::Add => "+",
+        ast::BinaryOp::Sub => "-",
+        ast::BinaryOp::Mul => "*",
+        ast::BinaryOp::Div => "/",
+    }
+}
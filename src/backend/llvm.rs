@@ -0,0 +1,682 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+use super::{array_length, converts_to_same_type, fields, indices, parts, resolve, unique_name, Aggregates, Parts};
+use crate::interpreter::Value;
+use crate::syntatic_analyzer::ast;
+use crate::syntatic_analyzer::nonterminals::Type;
+
+/*
+ * Translates a program without errors into a textual LLVM module, preceded by the runtime in
+ * simplescript.ll. Pointers are opaque `ptr`s, LLVM 14 reads the module with -opaque-pointers.
+ *
+ * integer, real, char and boolean are i32, double, i32 and i1, a char being the number of the
+ * Unicode character, strings are the %ss.string of the runtime, its length and a pointer to its
+ * UTF-8 characters. Arrays are LLVM arrays and structs
+ * are named after the first type declared with them, both passed around by value. Variables
+ * live in allocas of the entry block and the part of one a left value names is reached with a
+ * single getelementptr. Instructions run in the order they are written, so operands are
+ * evaluated left to right without the temporaries the C backend needs.
+ */
+
+pub const RUNTIME: &str = include_str!("simplescript.ll");
+
+// The functions the runtime declares, a program's functions with one of these names end in `.`
+const RESERVED: &[&str] = &["main", "printf", "dprintf", "snprintf", "putchar", "strtod", "strpbrk", "malloc", "exit"];
+
+// Names of the program have no dots, so the runtime's `ss.` names and escaped ones can't clash with them
+fn escape(name: &str) -> String {
+    if RESERVED.contains(&name) { format!("{}.", name) } else { name.to_string() }
+}
+
+fn real_literal(x: f64) -> String {
+    if !x.is_finite() {
+        return format!("0x{:016X}", x.to_bits());
+    }
+    // LLVM wants a point before the exponent, 1e20 is written 1.0e20
+    let text = format!("{:?}", x);
+    match text.split_once('e') {
+        Some((digits, exponent)) if !digits.contains('.') => format!("{}.0e{}", digits, exponent),
+        _ => text,
+    }
+}
+
+fn char_literal(ch: char) -> String {
+    u32::from(ch).to_string()
+}
+
+// The predicate of `icmp` or `fcmp` for a relational operator, `kind` is `s`, `u` or `o`
+fn predicate(op: ast::BinaryOp, kind: &str) -> String {
+    match (op, kind) {
+        (ast::BinaryOp::Equal, "o") => String::from("oeq"),
+        (ast::BinaryOp::NotEqual, "o") => String::from("une"),
+        (ast::BinaryOp::Equal, _) => String::from("eq"),
+        (ast::BinaryOp::NotEqual, _) => String::from("ne"),
+        (ast::BinaryOp::Less, kind) => format!("{}lt", kind),
+        (ast::BinaryOp::Greater, kind) => format!("{}gt", kind),
+        (ast::BinaryOp::LessEqual, kind) => format!("{}le", kind),
+        (ast::BinaryOp::GreaterEqual, kind) => format!("{}ge", kind),
+        (op, _) => unreachable!("{:?} is not a relational operator", op),
+    }
+}
+
+/*
+ * Translates `program`. With an entry call the module gets a `main` that makes the call and
+ * prints the value it returns, as `--run` does.
+ */
+pub fn generate(program: &ast::Program, entry: Option<(&str, &[Value])>) -> String {
+    let mut generator = Generator { aggregates: Aggregates::new(str::to_string, |kind, n| format!("{}.{}", kind, n)), strings: vec![] };
+    for declaration in program.declarations.iter() {
+        if let ast::Declaration::Type(type_decl) = declaration {
+            if !matches!(type_decl.kind, ast::TypeDeclKind::Alias(_)) {
+                generator.aggregates.intern(&type_decl.type_, Some(&type_decl.name.name));
+            }
+        }
+    }
+
+    let mut functions = String::new();
+    for declaration in program.declarations.iter() {
+        if let ast::Declaration::Function(function) = declaration {
+            functions.push('\n');
+            functions.push_str(&FunctionGenerator::new(&mut generator).generate(function));
+        }
+    }
+
+    let main = entry.map(|(function, args)| {
+        let return_type = program.declarations.iter()
+            .find_map(|declaration| match declaration {
+                ast::Declaration::Function(declared) if declared.name.name == function => Some(&declared.return_type.type_),
+                _ => None
+            })
+            .expect("the entry call is checked against the program");
+        let args: Vec<String> = args.iter().map(|arg| generator.value_literal(arg)).collect();
+        let type_ = generator.llvm_type(return_type);
+        let print = generator.print(return_type);
+        format!("\ndefine i32 @main() {{\nentry:\n  %0 = call {0} @{1}({2})\n  call void @{3}({0} %0)\n  \
+                 %1 = call i32 @putchar(i32 10)\n  ret i32 0\n}}\n", type_, escape(function), args.join(", "), print)
+    });
+
+    // the helpers are written first, their labels are strings too
+    let mut helpers = String::new();
+    for index in 0..generator.aggregates.types.len() {
+        let (type_, name) = generator.aggregates.types[index].clone();
+        if generator.aggregates.compared[index] {
+            helpers.push('\n');
+            helpers.push_str(&generator.compare_function(&type_, &name));
+        }
+        if generator.aggregates.printed[index] {
+            helpers.push('\n');
+            helpers.push_str(&generator.print_function(&type_, &name));
+        }
+    }
+
+    let mut llvm = String::from(RUNTIME);
+    let definitions: Vec<String> = generator.aggregates.types.iter()
+        .filter(|(type_, _)| matches!(type_, Type::Struct_type_(_)))
+        .map(|(type_, name)| generator.definition(type_, name))
+        .collect();
+    if !definitions.is_empty() {
+        llvm.push('\n');
+        llvm.push_str(&definitions.concat());
+    }
+    if !generator.strings.is_empty() {
+        llvm.push('\n');
+        for (index, s) in generator.strings.iter().enumerate() {
+            writeln!(llvm, "@.str.{} = private unnamed_addr constant [{} x i8] c\"{}\"", index, s.len(), bytes_literal(s)).unwrap();
+        }
+    }
+    llvm.push_str(&helpers);
+    llvm.push_str(&functions);
+    llvm.push_str(&main.unwrap_or_default());
+    llvm
+}
+
+fn bytes_literal(s: &str) -> String {
+    let mut literal = String::new();
+    for byte in s.bytes() {
+        match byte {
+            b' '..=b'~' if byte != b'"' && byte != b'\\' => literal.push(byte as char),
+            _ => write!(literal, "\\{:02X}", byte).unwrap(),
+        }
+    }
+    literal
+}
+
+struct Generator {
+    aggregates: Aggregates,
+    // the characters of the string literals, each one a global @.str.<index>
+    strings: Vec<String>,
+}
+
+impl Generator {
+    fn llvm_type(&mut self, type_: &Type) -> String {
+        if let Type::Array_type_(..) | Type::Struct_type_(_) = resolve(type_) {
+            self.aggregates.intern(type_, None);
+        }
+        self.known_type(type_)
+    }
+
+    // The LLVM type of a type already interned
+    fn known_type(&self, type_: &Type) -> String {
+        match resolve(type_) {
+            Type::Int_ => String::from("i32"),
+            Type::Real_ => String::from("double"),
+            Type::Char_ => String::from("i32"),
+            Type::Bool_ => String::from("i1"),
+            Type::String_ => String::from("%ss.string"),
+            Type::Array_type_(length, element) => format!("[{} x {}]", length, self.known_type(element)),
+            Type::Struct_type_(_) => format!("%{}", self.aggregates.name(type_)),
+            Type::Alias_type_(_) | Type::Universal_ => unreachable!("checked programs have no erroneous types"),
+        }
+    }
+
+    fn definition(&self, type_: &Type, name: &str) -> String {
+        let fields: Vec<String> = fields(type_).into_iter().map(|(_, type_)| self.known_type(type_)).collect();
+        format!("%{} = type {{ {} }}\n", name, fields.join(", "))
+    }
+
+    // A string literal as an operand of type %ss.string
+    fn string(&mut self, s: &str) -> String {
+        if s.is_empty() {
+            return String::from("zeroinitializer");
+        }
+        let index = match self.strings.iter().position(|known| known == s) {
+            Some(index) => index,
+            None => {
+                self.strings.push(s.to_string());
+                self.strings.len() - 1
+            },
+        };
+        format!("{{ i32 {}, ptr @.str.{} }}", s.len(), index)
+    }
+
+    fn value_literal(&mut self, value: &Value) -> String {
+        match value {
+            Value::Integer(n) => format!("i32 {}", n),
+            Value::Real(x) => format!("double {}", real_literal(*x)),
+            Value::Char(ch) => format!("i32 {}", char_literal(*ch)),
+            Value::Boolean(b) => format!("i1 {}", b),
+            Value::String(s) => format!("%ss.string {}", self.string(s)),
+            Value::Array(_) | Value::Struct(_) => unreachable!("calls only have scalar and string arguments"),
+        }
+    }
+
+    // The runtime or generated function comparing values of `type_`
+    fn compare(&mut self, type_: &Type) -> String {
+        self.helper(type_, "compare")
+    }
+
+    fn print(&mut self, type_: &Type) -> String {
+        self.helper(type_, "print")
+    }
+
+    fn helper(&mut self, type_: &Type, what: &str) -> String {
+        format!("ss.{}.{}", what, self.aggregates.helper(type_, what))
+    }
+
+    fn compare_function(&mut self, type_: &Type, name: &str) -> String {
+        let llvm_type = self.known_type(type_);
+        let mut function = format!("define internal i32 @ss.compare.{}({1} %a, {1} %b) {{\nentry:\n", name, llvm_type);
+        match parts(type_) {
+            Parts::Elements(length, element) => {
+                let element_type = self.known_type(element);
+                let compare = self.helper(element, "compare");
+                writeln!(function, "  %a.addr = alloca {0}\n  %b.addr = alloca {0}", llvm_type).unwrap();
+                writeln!(function, "  store {0} %a, ptr %a.addr\n  store {0} %b, ptr %b.addr\n  br label %loop\n", llvm_type).unwrap();
+                writeln!(function, "loop:\n  %i = phi i32 [ 0, %entry ], [ %next, %element ]").unwrap();
+                writeln!(function, "  %more = icmp ult i32 %i, {}\n  br i1 %more, label %element, label %equal\n", length).unwrap();
+                writeln!(function, "element:").unwrap();
+                writeln!(function, "  %a.at = getelementptr inbounds {}, ptr %a.addr, i32 0, i32 %i", llvm_type).unwrap();
+                writeln!(function, "  %b.at = getelementptr inbounds {}, ptr %b.addr, i32 0, i32 %i", llvm_type).unwrap();
+                writeln!(function, "  %a.i = load {0}, ptr %a.at\n  %b.i = load {0}, ptr %b.at", element_type).unwrap();
+                writeln!(function, "  %order = call i32 @{}({1} %a.i, {1} %b.i)", compare, element_type).unwrap();
+                function.push_str("  %same = icmp eq i32 %order, 0\n  %next = add i32 %i, 1\n");
+                function.push_str("  br i1 %same, label %loop, label %different\n\ndifferent:\n  ret i32 %order\n\n");
+                function.push_str("equal:\n  ret i32 0\n");
+            },
+            Parts::Fields(fields) => {
+                let fields: Vec<(String, String)> = fields.into_iter()
+                    .map(|(_, type_)| (self.known_type(type_), self.helper(type_, "compare")))
+                    .collect();
+                for (i, (field_type, compare)) in fields.iter().enumerate() {
+                    writeln!(function, "  %a.{0} = extractvalue {1} %a, {0}\n  %b.{0} = extractvalue {1} %b, {0}", i, llvm_type).unwrap();
+                    if i == 0 {
+                        writeln!(function, "  %order.0 = call i32 @{}({1} %a.0, {1} %b.0)", compare, field_type).unwrap();
+                    } else {
+                        // the first field that differs decides
+                        writeln!(function, "  %field.{0} = call i32 @{1}({2} %a.{0}, {2} %b.{0})", i, compare, field_type).unwrap();
+                        writeln!(function, "  %same.{} = icmp eq i32 %order.{}, 0", i, i - 1).unwrap();
+                        writeln!(function, "  %order.{0} = select i1 %same.{0}, i32 %field.{0}, i32 %order.{1}", i, i - 1).unwrap();
+                    }
+                }
+                writeln!(function, "  ret i32 %order.{}", fields.len() - 1).unwrap();
+            },
+        }
+        function.push_str("}\n");
+        function
+    }
+
+    fn print_function(&mut self, type_: &Type, name: &str) -> String {
+        let llvm_type = self.known_type(type_);
+        let mut function = format!("define internal void @ss.print.{}({} %value) {{\nentry:\n", name, llvm_type);
+        match parts(type_) {
+            Parts::Elements(length, element) => {
+                let element_type = self.known_type(element);
+                let print = self.helper(element, "print");
+                let comma = self.string(", ");
+                writeln!(function, "  %value.addr = alloca {0}\n  store {0} %value, ptr %value.addr", llvm_type).unwrap();
+                function.push_str("  %0 = call i32 @putchar(i32 91)\n  br label %loop\n\n");
+                function.push_str("loop:\n  %i = phi i32 [ 0, %entry ], [ %next, %element ]\n");
+                writeln!(function, "  %more = icmp ult i32 %i, {}\n  br i1 %more, label %separator, label %end\n", length).unwrap();
+                function.push_str("separator:\n  %first = icmp eq i32 %i, 0\n  br i1 %first, label %element, label %comma\n\n");
+                writeln!(function, "comma:\n  call void @ss.write(%ss.string {})\n  br label %element\n", comma).unwrap();
+                writeln!(function, "element:\n  %at = getelementptr inbounds {}, ptr %value.addr, i32 0, i32 %i", llvm_type).unwrap();
+                writeln!(function, "  %item = load {0}, ptr %at\n  call void @{1}({0} %item)", element_type, print).unwrap();
+                function.push_str("  %next = add i32 %i, 1\n  br label %loop\n\n");
+                function.push_str("end:\n  %1 = call i32 @putchar(i32 93)\n");
+            },
+            Parts::Fields(fields) => {
+                let fields: Vec<(String, String, String)> = fields.into_iter().enumerate()
+                    .map(|(i, (field, type_))| {
+                        let label = format!("{}{} = ", if i == 0 { "{ " } else { ", " }, field);
+                        (self.string(&label), self.known_type(type_), self.helper(type_, "print"))
+                    })
+                    .collect();
+                for (i, (label, field_type, print)) in fields.iter().enumerate() {
+                    writeln!(function, "  call void @ss.write(%ss.string {})", label).unwrap();
+                    writeln!(function, "  %field.{0} = extractvalue {1} %value, {0}", i, llvm_type).unwrap();
+                    writeln!(function, "  call void @{}({} %field.{})", print, field_type, i).unwrap();
+                }
+                let end = self.string(" }");
+                writeln!(function, "  call void @ss.write(%ss.string {})", end).unwrap();
+            },
+        }
+        function.push_str("  ret void\n}\n");
+        function
+    }
+}
+
+// Where a left value is, a character of a string is not a value of its own
+enum Place {
+    Pointer(String),
+    Char { string: String, index: String, line: u32, column: u32 },
+}
+
+struct FunctionGenerator<'g> {
+    generator: &'g mut Generator,
+    // the allocas of the variables, all at the start of the entry block
+    allocas: String,
+    body: String,
+    // values and labels are numbered in the order they are written
+    values: u32,
+    constructs: u32,
+    // the block instructions go to, and whether it already ended with a terminator
+    block: String,
+    terminated: bool,
+    // the local names in use, and the alloca of each variable in scope
+    taken: HashSet<String>,
+    scopes: Vec<HashMap<String, String>>,
+    // the labels `continue` and `break` jump to in each loop
+    loops: HashMap<ast::LoopId, (String, String)>,
+}
+
+impl<'g> FunctionGenerator<'g> {
+    fn new(generator: &'g mut Generator) -> Self {
+        FunctionGenerator {
+            generator,
+            allocas: String::new(),
+            body: String::new(),
+            values: 0,
+            constructs: 0,
+            block: String::from("entry"),
+            terminated: false,
+            taken: HashSet::from([String::from("entry")]),
+            scopes: vec![],
+            loops: HashMap::new(),
+        }
+    }
+
+    fn generate(mut self, function: &ast::FunctionDecl) -> String {
+        self.taken.extend(function.params.iter().map(|param| param.name.name.clone()));
+        let mut params = vec![];
+        let mut scope = HashMap::new();
+        for param in function.params.iter() {
+            let type_ = self.generator.llvm_type(&param.type_.type_);
+            let address = self.local(&format!("{}.addr", param.name.name));
+            writeln!(self.allocas, "  %{} = alloca {}", address, type_).unwrap();
+            self.instruction(&format!("store {} %{}, ptr %{}", type_, param.name.name, address));
+            params.push(format!("{} %{}", type_, param.name.name));
+            scope.insert(param.name.name.clone(), address);
+        }
+        self.scopes.push(scope);
+        let return_type = self.generator.llvm_type(&function.return_type.type_);
+        self.block_contents(&function.body);
+        // every path returns, the end of the body can't be reached
+        if !self.terminated {
+            self.terminate("unreachable");
+        }
+
+        let mut llvm = format!("define {} @{}({}) {{\nentry:\n", return_type, escape(&function.name.name), params.join(", "));
+        llvm.push_str(&self.allocas);
+        llvm.push_str(&self.body);
+        llvm.push_str("}\n");
+        llvm
+    }
+
+    fn local(&mut self, name: &str) -> String {
+        unique_name(&mut self.taken, name)
+    }
+
+    // The labels of one if or loop, with a number after the first one
+    fn labels<const N: usize>(&mut self, names: [&str; N]) -> [String; N] {
+        let suffix = if self.constructs == 0 { String::new() } else { self.constructs.to_string() };
+        self.constructs += 1;
+        names.map(|name| format!("{}{}", name, suffix))
+    }
+
+    fn instruction(&mut self, instruction: &str) {
+        writeln!(self.body, "  {}", instruction).unwrap();
+    }
+
+    // An instruction giving a value, returns its name
+    fn value(&mut self, instruction: &str) -> String {
+        let name = format!("%{}", self.values);
+        self.values += 1;
+        self.instruction(&format!("{} = {}", name, instruction));
+        name
+    }
+
+    fn terminate(&mut self, instruction: &str) {
+        self.instruction(instruction);
+        self.terminated = true;
+    }
+
+    // Jumps to `label` unless the block already ended
+    fn branch(&mut self, label: &str) {
+        if !self.terminated {
+            self.terminate(&format!("br label %{}", label));
+        }
+    }
+
+    fn label(&mut self, label: &str) {
+        write!(self.body, "\n{}:\n", label).unwrap();
+        self.block = label.to_string();
+        self.terminated = false;
+    }
+
+    fn block_contents(&mut self, block: &ast::Block) {
+        for var in block.vars.iter() {
+            let type_ = self.generator.llvm_type(&var.type_.type_);
+            // variables start as zero every time the block is entered, like the interpreter's
+            let zero = match resolve(&var.type_.type_) {
+                Type::Int_ | Type::Char_ => "0",
+                Type::Real_ => "0.0",
+                Type::Bool_ => "false",
+                _ => "zeroinitializer",
+            };
+            for name in var.names.iter() {
+                let local = self.local(&name.name);
+                writeln!(self.allocas, "  %{} = alloca {}", local, type_).unwrap();
+                self.instruction(&format!("store {} {}, ptr %{}", type_, zero, local));
+                self.scopes.last_mut().unwrap().insert(name.name.clone(), local);
+            }
+        }
+        for stmt in block.stmts.iter() {
+            // what follows a return, break or continue is never run
+            if self.terminated {
+                break;
+            }
+            self.stmt(stmt);
+        }
+    }
+
+    fn stmt(&mut self, stmt: &ast::Stmt) {
+        match &stmt.kind {
+            ast::StmtKind::If { condition, then_branch, else_branch } => {
+                let [then, otherwise, end] = self.labels(["if.then", "if.else", "if.end"]);
+                let condition = self.expr(condition);
+                let target = if else_branch.is_some() { &otherwise } else { &end };
+                self.terminate(&format!("br i1 {}, label %{}, label %{}", condition, then, target));
+                self.label(&then);
+                self.stmt(then_branch);
+                self.branch(&end);
+                if let Some(else_branch) = else_branch {
+                    self.label(&otherwise);
+                    self.stmt(else_branch);
+                    self.branch(&end);
+                }
+                self.label(&end);
+            },
+            ast::StmtKind::While { id, condition, body } => {
+                let [check, body_label, end] = self.labels(["while.cond", "while.body", "while.end"]);
+                self.branch(&check);
+                self.label(&check);
+                let condition = self.expr(condition);
+                self.terminate(&format!("br i1 {}, label %{}, label %{}", condition, body_label, end));
+                self.label(&body_label);
+                self.loops.insert(*id, (check.clone(), end.clone()));
+                self.stmt(body);
+                self.branch(&check);
+                self.label(&end);
+            },
+            ast::StmtKind::DoWhile { id, body, condition } => {
+                let [body_label, check, end] = self.labels(["do.body", "do.cond", "do.end"]);
+                self.branch(&body_label);
+                self.label(&body_label);
+                self.loops.insert(*id, (check.clone(), end.clone()));
+                self.stmt(body);
+                self.branch(&check);
+                self.label(&check);
+                let condition = self.expr(condition);
+                self.terminate(&format!("br i1 {}, label %{}, label %{}", condition, body_label, end));
+                self.label(&end);
+            },
+            ast::StmtKind::Block(block) => {
+                self.scopes.push(HashMap::new());
+                self.block_contents(block);
+                self.scopes.pop();
+            },
+            ast::StmtKind::Assign { target, value } => {
+                let type_ = self.generator.llvm_type(&target.type_);
+                let (place, value) = self.place(target, Some(value));
+                match place {
+                    Place::Pointer(place) => self.instruction(&format!("store {} {}, ptr {}", type_, value.unwrap(), place)),
+                    // a string is a value, assigning one of its characters assigns a new string
+                    Place::Char { string, index, line, column } => {
+                        let old = self.value(&format!("load %ss.string, ptr {}", string));
+                        let new = self.value(&format!("call %ss.string @ss.with_char(%ss.string {}, i32 {}, i32 {}, i32 {}, i32 {})",
+                                                      old, index, value.unwrap(), line, column));
+                        self.instruction(&format!("store %ss.string {}, ptr {}", new, string));
+                    },
+                }
+            },
+            ast::StmtKind::Break(id) => {
+                let end = self.loops[id].1.clone();
+                self.terminate(&format!("br label %{}", end));
+            },
+            ast::StmtKind::Continue(id) => {
+                let check = self.loops[id].0.clone();
+                self.terminate(&format!("br label %{}", check));
+            },
+            ast::StmtKind::Return(value) => {
+                let type_ = self.generator.llvm_type(&value.type_);
+                let value = self.expr(value);
+                self.terminate(&format!("ret {} {}", type_, value));
+            },
+            ast::StmtKind::Error => unreachable!("checked programs have no erroneous statements"),
+        }
+    }
+
+    /*
+     * The part of a variable a left value names, and the value assigned to it if there is one.
+     * Like the interpreter the indices are checked once all of them and the value are known,
+     * the index of a string last, by the runtime when it reads or replaces the character.
+     */
+    fn place(&mut self, lvalue: &ast::LValue, value: Option<&ast::Expr>) -> (Place, Option<String>) {
+        let indices = indices(lvalue);
+        let texts: Vec<String> = indices.iter().map(|(index, _)| self.expr(index)).collect();
+        let value = value.map(|value| self.expr(value));
+
+        let mut checked = vec![];
+        for ((index, array), text) in indices.iter().zip(texts) {
+            if *resolve(array) == Type::String_ {
+                checked.push(text);
+                continue;
+            }
+            let start = index.span.start;
+            checked.push(self.value(&format!("call i32 @ss.index(i32 {}, i32 {}, i32 {}, i32 {})",
+                                             text, array_length(array), start.line, start.column)));
+        }
+        let mut checked = checked.into_iter();
+        match &lvalue.kind {
+            ast::LValueKind::Index { base, index } if *resolve(&base.type_) == Type::String_ => {
+                let string = self.pointer(base, &mut checked);
+                let start = index.span.start;
+                (Place::Char { string, index: checked.next().unwrap(), line: start.line, column: start.column }, value)
+            },
+            _ => (Place::Pointer(self.pointer(lvalue, &mut checked)), value),
+        }
+    }
+
+    // A pointer to the part of a variable a left value names
+    fn pointer(&mut self, lvalue: &ast::LValue, indices: &mut impl Iterator<Item = String>) -> String {
+        let mut path = vec![];
+        let (variable, type_) = self.path(lvalue, indices, &mut path);
+        if path.is_empty() {
+            return variable;
+        }
+        let type_ = self.generator.llvm_type(type_);
+        self.value(&format!("getelementptr inbounds {}, ptr {}, i32 0, {}", type_, variable, path.join(", ")))
+    }
+
+    // The alloca and type of the variable of a left value, adding the indices that reach its part to `path`
+    fn path<'a>(&self, lvalue: &'a ast::LValue, indices: &mut impl Iterator<Item = String>, path: &mut Vec<String>) -> (String, &'a Type) {
+        match &lvalue.kind {
+            ast::LValueKind::Variable(name) => {
+                let local = self.scopes.iter().rev().find_map(|scope| scope.get(&name.name)).expect("the scope analysis checks names");
+                (format!("%{}", local), &lvalue.type_)
+            },
+            ast::LValueKind::Field { base, field } => {
+                let variable = self.path(base, indices, path);
+                let index = fields(&base.type_).iter().position(|(name, _)| *name == field.name).unwrap();
+                path.push(format!("i32 {}", index));
+                variable
+            },
+            ast::LValueKind::Index { base, .. } => {
+                let variable = self.path(base, indices, path);
+                path.push(format!("i32 {}", indices.next().unwrap()));
+                variable
+            },
+        }
+    }
+
+    fn expr(&mut self, expr: &ast::Expr) -> String {
+        match &expr.kind {
+            ast::ExprKind::Integer(n) => n.to_string(),
+            ast::ExprKind::Real(x) => real_literal(*x),
+            ast::ExprKind::Char(ch) => char_literal(*ch),
+            ast::ExprKind::Bool(b) => b.to_string(),
+            ast::ExprKind::String(s) => self.generator.string(s),
+            ast::ExprKind::LValue(lvalue) => {
+                let type_ = self.generator.llvm_type(&expr.type_);
+                match self.place(lvalue, None).0 {
+                    Place::Pointer(place) => self.value(&format!("load {}, ptr {}", type_, place)),
+                    Place::Char { string, index, line, column } => {
+                        let string = self.value(&format!("load %ss.string, ptr {}", string));
+                        self.value(&format!("call i32 @ss.char_at(%ss.string {}, i32 {}, i32 {}, i32 {})", string, index, line, column))
+                    },
+                }
+            },
+            // the right operand is only evaluated when the left one doesn't decide
+            ast::ExprKind::Binary { op: op @ (ast::BinaryOp::And | ast::BinaryOp::Or), left, right } => {
+                let and = *op == ast::BinaryOp::And;
+                let left = self.expr(left);
+                let from = self.block.clone();
+                let [rhs, end] = if and { self.labels(["and.rhs", "and.end"]) } else { self.labels(["or.rhs", "or.end"]) };
+                let (first, second) = if and { (&rhs, &end) } else { (&end, &rhs) };
+                self.terminate(&format!("br i1 {}, label %{}, label %{}", left, first, second));
+                self.label(&rhs);
+                let right = self.expr(right);
+                let rhs_end = self.block.clone();
+                self.branch(&end);
+                self.label(&end);
+                self.value(&format!("phi i1 [ {}, %{} ], [ {}, %{} ]", !and, from, right, rhs_end))
+            },
+            ast::ExprKind::Binary { op, left, right } => {
+                let a = self.expr(left);
+                let b = self.expr(right);
+                let start = expr.span.start;
+                let instruction = match (op, resolve(&left.type_)) {
+                    (ast::BinaryOp::Add, Type::Int_) => format!("add i32 {}, {}", a, b),
+                    (ast::BinaryOp::Sub, Type::Int_) => format!("sub i32 {}, {}", a, b),
+                    (ast::BinaryOp::Mul, Type::Int_) => format!("mul i32 {}, {}", a, b),
+                    (ast::BinaryOp::Div, Type::Int_) => format!("call i32 @ss.div(i32 {}, i32 {}, i32 {}, i32 {})", a, b, start.line, start.column),
+                    (ast::BinaryOp::Add, Type::Real_) => format!("fadd double {}, {}", a, b),
+                    (ast::BinaryOp::Sub, Type::Real_) => format!("fsub double {}, {}", a, b),
+                    (ast::BinaryOp::Mul, Type::Real_) => format!("fmul double {}, {}", a, b),
+                    (ast::BinaryOp::Div, Type::Real_) => format!("fdiv double {}, {}", a, b),
+                    (ast::BinaryOp::Add, Type::String_) => format!("call %ss.string @ss.concat(%ss.string {}, %ss.string {})", a, b),
+                    (_, Type::Int_) => format!("icmp {} i32 {}, {}", predicate(*op, "s"), a, b),
+                    (_, Type::Real_) => format!("fcmp {} double {}, {}", predicate(*op, "o"), a, b),
+                    (_, Type::Char_) => format!("icmp {} i32 {}, {}", predicate(*op, "u"), a, b),
+                    (_, Type::Bool_) => format!("icmp {} i1 {}, {}", predicate(*op, "u"), a, b),
+                    // strings, arrays and structs are ordered by a function
+                    (_, type_) => {
+                        let compare = self.generator.compare(type_);
+                        let llvm_type = self.generator.llvm_type(type_);
+                        let order = self.value(&format!("call i32 @{0}({1} {2}, {1} {3})", compare, llvm_type, a, b));
+                        format!("icmp {} i32 {}, 0", predicate(*op, "s"), order)
+                    },
+                };
+                self.value(&instruction)
+            },
+            ast::ExprKind::Unary { op: ast::UnaryOp::Negate, operand } if resolve(&operand.type_) == &Type::Int_ => {
+                let operand = self.expr(operand);
+                self.value(&format!("sub i32 0, {}", operand))
+            },
+            ast::ExprKind::Unary { op: ast::UnaryOp::Negate, operand } => {
+                let operand = self.expr(operand);
+                self.value(&format!("fneg double {}", operand))
+            },
+            ast::ExprKind::Unary { op: ast::UnaryOp::Not, operand } => {
+                let operand = self.expr(operand);
+                self.value(&format!("xor i1 {}, true", operand))
+            },
+            ast::ExprKind::Convert(operand) if converts_to_same_type(expr) => self.expr(operand),
+            ast::ExprKind::Convert(operand) if expr.type_ == Type::Real_ => {
+                let operand = self.expr(operand);
+                self.value(&format!("sitofp i32 {} to double", operand))
+            },
+            // rounds towards zero and saturates, NaN becomes 0
+            ast::ExprKind::Convert(operand) => {
+                let operand = self.expr(operand);
+                self.value(&format!("call i32 @llvm.fptosi.sat.i32.f64(double {})", operand))
+            },
+            ast::ExprKind::IncDec { op, target } => {
+                let Place::Pointer(place) = self.place(target, None).0 else {
+                    unreachable!("only integers are incremented")
+                };
+                let (delta, prefix) = match op {
+                    ast::IncDecOp::PreIncrement => (1, true),
+                    ast::IncDecOp::PreDecrement => (-1, true),
+                    ast::IncDecOp::PostIncrement => (1, false),
+                    ast::IncDecOp::PostDecrement => (-1, false),
+                };
+                let old = self.value(&format!("load i32, ptr {}", place));
+                let new = self.value(&format!("add i32 {}, {}", old, delta));
+                self.instruction(&format!("store i32 {}, ptr {}", new, place));
+                if prefix { new } else { old }
+            },
+            ast::ExprKind::Call { function, args } => {
+                let mut operands = vec![];
+                for arg in args.iter() {
+                    let type_ = self.generator.llvm_type(&arg.type_);
+                    let value = self.expr(arg);
+                    operands.push(format!("{} {}", type_, value));
+                }
+                let type_ = self.generator.llvm_type(&expr.type_);
+                self.value(&format!("call {} @{}({})", type_, escape(&function.name), operands.join(", ")))
+            },
+        }
+    }
+}
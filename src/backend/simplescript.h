@@ -0,0 +1,235 @@
+/*
+ * Runtime of the C programs generated from Simplescript, compile them with -std=c99.
+ *
+ * Integers wrap around like in the interpreter, and the operations that can fail stop the
+ * program with the error the interpreter would report and exit code 5.
+ */
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+/*
+ * Strings are immutable: their length in bytes comes first and their characters, encoded in
+ * UTF-8, are not NUL terminated. A char is the number of the Unicode character.
+ */
+typedef struct {
+    uint32_t length;
+    const char *chars;
+} ss_string;
+
+#define SS_STRING(literal) ((ss_string){ sizeof(literal) - 1, (literal) })
+
+static inline void ss_fail(const char *code, const char *message, int line, int column) {
+    fprintf(stderr, "error[%s]: %s at %d:%d\n", code, message, line, column);
+    exit(5);
+}
+
+static inline int32_t ss_add(int32_t a, int32_t b) { return (int32_t)((uint32_t)a + (uint32_t)b); }
+static inline int32_t ss_sub(int32_t a, int32_t b) { return (int32_t)((uint32_t)a - (uint32_t)b); }
+static inline int32_t ss_mul(int32_t a, int32_t b) { return (int32_t)((uint32_t)a * (uint32_t)b); }
+static inline int32_t ss_neg(int32_t a) { return (int32_t)(0u - (uint32_t)a); }
+
+static inline int32_t ss_div(int32_t a, int32_t b, int line, int column) {
+    if (b == 0) {
+        ss_fail("E0301", "division by zero", line, column);
+    }
+    return a == INT32_MIN && b == -1 ? INT32_MIN : a / b;
+}
+
+/* `++` and `--`, the new value is returned if `prefix` and the old one if not */
+static inline int32_t ss_step(int32_t *place, int32_t delta, bool prefix) {
+    int32_t old = *place;
+    *place = ss_add(old, delta);
+    return prefix ? *place : old;
+}
+
+static inline int32_t ss_index(int32_t index, uint32_t length, int line, int column) {
+    if (index < 0 || (uint32_t)index >= length) {
+        char message[80];
+        sprintf(message, "index %ld is out of bounds for an array of %lu elements", (long)index, (unsigned long)length);
+        ss_fail("E0300", message, line, column);
+    }
+    return index;
+}
+
+/* The character of `s` starting at byte `*at`, which is moved to the next one */
+static inline uint32_t ss_decode(ss_string s, uint32_t *at) {
+    const unsigned char *bytes = (const unsigned char *)s.chars + *at;
+    uint32_t length = bytes[0] < 0x80 ? 1 : bytes[0] < 0xe0 ? 2 : bytes[0] < 0xf0 ? 3 : 4;
+    uint32_t c = length == 1 ? bytes[0] : bytes[0] & (0x7f >> length);
+    uint32_t i;
+    for (i = 1; i < length; i++) {
+        c = c << 6 | (bytes[i] & 0x3f);
+    }
+    *at += length;
+    return c;
+}
+
+/* Writes the UTF-8 encoding of `c` to `bytes`, returns how many bytes it takes */
+static inline uint32_t ss_encode(uint32_t c, char *bytes) {
+    uint32_t length = c < 0x80 ? 1 : c < 0x800 ? 2 : c < 0x10000 ? 3 : 4;
+    uint32_t i;
+    for (i = length - 1; i > 0; i--) {
+        bytes[i] = (char)(0x80 | (c & 0x3f));
+        c >>= 6;
+    }
+    bytes[0] = (char)(length == 1 ? c : (0xf00 >> length) | c);
+    return length;
+}
+
+static inline void *ss_allocate(size_t size) {
+    void *memory = malloc(size);
+    if (memory == NULL) {
+        fputs("out of memory\n", stderr);
+        exit(5);
+    }
+    return memory;
+}
+
+/* The byte the character `index` of `s` starts at, indices count characters like the interpreter's */
+static inline uint32_t ss_char_index(ss_string s, int32_t index, int line, int column) {
+    uint32_t at = 0;
+    uint32_t length = 0;
+    uint32_t start = 0;
+    while (at < s.length) {
+        if ((int64_t)length == index) {
+            start = at;
+        }
+        ss_decode(s, &at);
+        length++;
+    }
+    if (index < 0 || (uint32_t)index >= length) {
+        char message[80];
+        sprintf(message, "index %ld is out of bounds for a string of %lu characters", (long)index, (unsigned long)length);
+        ss_fail("E0300", message, line, column);
+    }
+    return start;
+}
+
+static inline uint32_t ss_char_at(ss_string s, int32_t index, int line, int column) {
+    uint32_t at = ss_char_index(s, index, line, column);
+    return ss_decode(s, &at);
+}
+
+/* `s` with its character `index` replaced by `c`, the strings of the program are values */
+static inline ss_string ss_with_char(ss_string s, int32_t index, uint32_t c, int line, int column) {
+    uint32_t start = ss_char_index(s, index, line, column);
+    uint32_t end = start;
+    char encoded[4];
+    uint32_t length = ss_encode(c, encoded);
+    char *chars;
+    ss_decode(s, &end);
+    chars = ss_allocate(s.length - (end - start) + length);
+    memcpy(chars, s.chars, start);
+    memcpy(chars + start, encoded, length);
+    memcpy(chars + start + length, s.chars + end, s.length - end);
+    return (ss_string){ s.length - (end - start) + length, chars };
+}
+
+/* `integer(x)` rounds towards zero and saturates, NaN becomes 0 */
+static inline int32_t ss_to_integer(double x) {
+    if (x != x) {
+        return 0;
+    }
+    if (x >= 2147483647.0) {
+        return INT32_MAX;
+    }
+    if (x <= -2147483648.0) {
+        return INT32_MIN;
+    }
+    return (int32_t)x;
+}
+
+/* The characters of the strings made at run time are never freed */
+static inline ss_string ss_concat(ss_string a, ss_string b) {
+    char *chars = ss_allocate(a.length + b.length + 1);
+    if (a.length > 0) {
+        memcpy(chars, a.chars, a.length);
+    }
+    if (b.length > 0) {
+        memcpy(chars + a.length, b.chars, b.length);
+    }
+    return (ss_string){ a.length + b.length, chars };
+}
+
+/*
+ * Comparisons return a negative number, zero or a positive number. Strings compare byte by
+ * byte, which orders UTF-8 like the characters it encodes.
+ */
+static inline int ss_compare_integer(int32_t a, int32_t b) { return (a > b) - (a < b); }
+static inline int ss_compare_real(double a, double b) { return (a > b) - (a < b); }
+static inline int ss_compare_char(uint32_t a, uint32_t b) { return (a > b) - (a < b); }
+static inline int ss_compare_boolean(bool a, bool b) { return (a > b) - (a < b); }
+
+static inline int ss_compare_string(ss_string a, ss_string b) {
+    uint32_t i;
+    for (i = 0; i < a.length && i < b.length; i++) {
+        if (a.chars[i] != b.chars[i]) {
+            return (unsigned char)a.chars[i] < (unsigned char)b.chars[i] ? -1 : 1;
+        }
+    }
+    return (a.length > b.length) - (a.length < b.length);
+}
+
+/* Values are printed as the interpreter shows them */
+static inline void ss_print_integer(int32_t n) { printf("%ld", (long)n); }
+static inline void ss_print_boolean(bool b) { fputs(b ? "true" : "false", stdout); }
+
+/* The shortest digits that read back as the same number, always with a fraction or an exponent */
+static inline void ss_print_real(double x) {
+    char text[40];
+    int precision = 1;
+    if (x != x) {
+        fputs("NaN", stdout);
+        return;
+    }
+    if (x == x + x && x != 0) {
+        fputs(x > 0 ? "inf" : "-inf", stdout);
+        return;
+    }
+    do {
+        sprintf(text, "%.*g", precision++, x);
+    } while (strtod(text, NULL) != x);
+    fputs(text, stdout);
+    if (strpbrk(text, ".e") == NULL) {
+        fputs(".0", stdout);
+    }
+}
+
+/* Control characters are escaped like Rust escapes them, the other ones written in UTF-8 */
+static inline void ss_print_escaped(uint32_t c, char quote) {
+    char encoded[4];
+    switch (c) {
+        case '\n': fputs("\\n", stdout); break;
+        case '\r': fputs("\\r", stdout); break;
+        case '\t': fputs("\\t", stdout); break;
+        case '\0': fputs("\\0", stdout); break;
+        case '\\': fputs("\\\\", stdout); break;
+        default:
+            if (c < 0x20 || c == 0x7f) {
+                printf("\\u{%lx}", (unsigned long)c);
+                break;
+            }
+            if (c == (uint32_t)quote) {
+                putchar('\\');
+            }
+            fwrite(encoded, 1, ss_encode(c, encoded), stdout);
+    }
+}
+
+static inline void ss_print_char(uint32_t c) {
+    putchar('\'');
+    ss_print_escaped(c, '\'');
+    putchar('\'');
+}
+
+static inline void ss_print_string(ss_string s) {
+    uint32_t at = 0;
+    putchar('"');
+    while (at < s.length) {
+        ss_print_escaped(ss_decode(s, &at), '"');
+    }
+    putchar('"');
+}
@@ -0,0 +1,522 @@
+; Runtime of the LLVM modules generated from Simplescript.
+;
+; Integers wrap around like in the interpreter, and the operations that can fail stop the
+; program with the error the interpreter would report and exit code 5.
+
+; Strings are immutable: their length in bytes comes first and their characters, encoded in
+; UTF-8, are not NUL terminated. A char is the number of the Unicode character.
+%ss.string = type { i32, ptr }
+
+declare i32 @printf(ptr, ...)
+declare i32 @dprintf(i32, ptr, ...)
+declare i32 @snprintf(ptr, i64, ptr, ...)
+declare i32 @putchar(i32)
+declare double @strtod(ptr, ptr)
+declare ptr @strpbrk(ptr, ptr)
+declare ptr @malloc(i64)
+declare void @exit(i32) noreturn
+declare void @llvm.memcpy.p0.p0.i64(ptr, ptr, i64, i1)
+declare i32 @llvm.fptosi.sat.i32.f64(double)
+
+@ss.error = private unnamed_addr constant [24 x i8] c"error[%s]: %s at %d:%d\0A\00"
+@ss.out_of_memory = private unnamed_addr constant [15 x i8] c"out of memory\0A\00"
+@ss.E0300 = private unnamed_addr constant [6 x i8] c"E0300\00"
+@ss.E0301 = private unnamed_addr constant [6 x i8] c"E0301\00"
+@ss.out_of_bounds = private unnamed_addr constant [54 x i8] c"index %d is out of bounds for an array of %u elements\00"
+@ss.string_out_of_bounds = private unnamed_addr constant [56 x i8] c"index %d is out of bounds for a string of %u characters\00"
+@ss.division_by_zero = private unnamed_addr constant [17 x i8] c"division by zero\00"
+@ss.d = private unnamed_addr constant [3 x i8] c"%d\00"
+@ss.s = private unnamed_addr constant [3 x i8] c"%s\00"
+@ss.text = private unnamed_addr constant [5 x i8] c"%.*s\00"
+@ss.g = private unnamed_addr constant [5 x i8] c"%.*g\00"
+@ss.true = private unnamed_addr constant [5 x i8] c"true\00"
+@ss.false = private unnamed_addr constant [6 x i8] c"false\00"
+@ss.nan = private unnamed_addr constant [4 x i8] c"NaN\00"
+@ss.inf = private unnamed_addr constant [4 x i8] c"inf\00"
+@ss.minus_inf = private unnamed_addr constant [5 x i8] c"-inf\00"
+@ss.point_or_exponent = private unnamed_addr constant [3 x i8] c".e\00"
+@ss.point_zero = private unnamed_addr constant [3 x i8] c".0\00"
+@ss.unicode_escape = private unnamed_addr constant [7 x i8] c"\5Cu{%x}\00"
+
+define internal void @ss.fail(ptr %code, ptr %message, i32 %line, i32 %column) noreturn {
+entry:
+  %0 = call i32 (i32, ptr, ...) @dprintf(i32 2, ptr @ss.error, ptr %code, ptr %message, i32 %line, i32 %column)
+  call void @exit(i32 5)
+  unreachable
+}
+
+define internal i32 @ss.div(i32 %a, i32 %b, i32 %line, i32 %column) {
+entry:
+  %zero = icmp eq i32 %b, 0
+  br i1 %zero, label %fail, label %divide
+
+fail:
+  call void @ss.fail(ptr @ss.E0301, ptr @ss.division_by_zero, i32 %line, i32 %column)
+  unreachable
+
+divide:
+  ; the one quotient that doesn't fit, it wraps around
+  %minimum = icmp eq i32 %a, -2147483648
+  %minus_one = icmp eq i32 %b, -1
+  %overflow = and i1 %minimum, %minus_one
+  %divisor = select i1 %overflow, i32 1, i32 %b
+  %quotient = sdiv i32 %a, %divisor
+  ret i32 %quotient
+}
+
+define internal i32 @ss.index(i32 %index, i32 %length, i32 %line, i32 %column) {
+entry:
+  %message = alloca [80 x i8]
+  ; negative indices are out of bounds as unsigned numbers too
+  %out = icmp uge i32 %index, %length
+  br i1 %out, label %fail, label %in
+
+fail:
+  %0 = call i32 (ptr, i64, ptr, ...) @snprintf(ptr %message, i64 80, ptr @ss.out_of_bounds, i32 %index, i32 %length)
+  call void @ss.fail(ptr @ss.E0300, ptr %message, i32 %line, i32 %column)
+  unreachable
+
+in:
+  ret i32 %index
+}
+
+; The number of bytes of the UTF-8 character starting at `at`
+define internal i32 @ss.char_size(ptr %at) {
+entry:
+  %byte = load i8, ptr %at
+  %lead = zext i8 %byte to i32
+  %one = icmp ult i32 %lead, 128
+  %two = icmp ult i32 %lead, 224
+  %three = icmp ult i32 %lead, 240
+  %0 = select i1 %three, i32 3, i32 4
+  %1 = select i1 %two, i32 2, i32 %0
+  %size = select i1 %one, i32 1, i32 %1
+  ret i32 %size
+}
+
+; The character encoded at `at`
+define internal i32 @ss.decode(ptr %at) {
+entry:
+  %size = call i32 @ss.char_size(ptr %at)
+  %byte = load i8, ptr %at
+  %lead = zext i8 %byte to i32
+  %one = icmp eq i32 %size, 1
+  %mask = lshr i32 127, %size
+  %bits = and i32 %lead, %mask
+  %first = select i1 %one, i32 %lead, i32 %bits
+  br label %loop
+
+loop:
+  %i = phi i32 [ 1, %entry ], [ %next, %continuation ]
+  %c = phi i32 [ %first, %entry ], [ %added, %continuation ]
+  %more = icmp ult i32 %i, %size
+  br i1 %more, label %continuation, label %end
+
+continuation:
+  %byte.at = getelementptr inbounds i8, ptr %at, i32 %i
+  %byte.i = load i8, ptr %byte.at
+  %0 = zext i8 %byte.i to i32
+  %1 = and i32 %0, 63
+  %2 = shl i32 %c, 6
+  %added = or i32 %2, %1
+  %next = add i32 %i, 1
+  br label %loop
+
+end:
+  ret i32 %c
+}
+
+; Writes the UTF-8 encoding of `c` at `at`, returns how many bytes it takes
+define internal i32 @ss.encode(i32 %c, ptr %at) {
+entry:
+  %one = icmp ult i32 %c, 128
+  %two = icmp ult i32 %c, 2048
+  %three = icmp ult i32 %c, 65536
+  %0 = select i1 %three, i32 3, i32 4
+  %1 = select i1 %two, i32 2, i32 %0
+  %size = select i1 %one, i32 1, i32 %1
+  br label %loop
+
+; the bytes after the first one, from the last
+loop:
+  %i = phi i32 [ %size, %entry ], [ %last, %continuation ]
+  %rest = phi i32 [ %c, %entry ], [ %shifted, %continuation ]
+  %last = sub i32 %i, 1
+  %more = icmp ugt i32 %i, 1
+  br i1 %more, label %continuation, label %lead
+
+continuation:
+  %2 = and i32 %rest, 63
+  %3 = or i32 %2, 128
+  %4 = trunc i32 %3 to i8
+  %byte.at = getelementptr inbounds i8, ptr %at, i32 %last
+  store i8 %4, ptr %byte.at
+  %shifted = lshr i32 %rest, 6
+  br label %loop
+
+lead:
+  %marker = lshr i32 3840, %size
+  %5 = or i32 %marker, %rest
+  %6 = select i1 %one, i32 %rest, i32 %5
+  %7 = trunc i32 %6 to i8
+  store i8 %7, ptr %at
+  ret i32 %size
+}
+
+define internal ptr @ss.allocate(i64 %size) {
+entry:
+  %memory = call ptr @malloc(i64 %size)
+  %failed = icmp eq ptr %memory, null
+  br i1 %failed, label %fail, label %allocated
+
+fail:
+  %0 = call i32 (i32, ptr, ...) @dprintf(i32 2, ptr @ss.out_of_memory)
+  call void @exit(i32 5)
+  unreachable
+
+allocated:
+  ret ptr %memory
+}
+
+; The byte the character `index` of `s` starts at, indices count characters like the interpreter's
+define internal i32 @ss.char_index(%ss.string %s, i32 %index, i32 %line, i32 %column) {
+entry:
+  %message = alloca [80 x i8]
+  %length = extractvalue %ss.string %s, 0
+  %chars = extractvalue %ss.string %s, 1
+  br label %loop
+
+loop:
+  %at = phi i32 [ 0, %entry ], [ %next, %char ]
+  %count = phi i32 [ 0, %entry ], [ %counted, %char ]
+  %start = phi i32 [ 0, %entry ], [ %found, %char ]
+  %more = icmp ult i32 %at, %length
+  br i1 %more, label %char, label %end
+
+char:
+  %is_index = icmp eq i32 %count, %index
+  %found = select i1 %is_index, i32 %at, i32 %start
+  %char.at = getelementptr inbounds i8, ptr %chars, i32 %at
+  %size = call i32 @ss.char_size(ptr %char.at)
+  %next = add i32 %at, %size
+  %counted = add i32 %count, 1
+  br label %loop
+
+end:
+  ; negative indices are out of bounds as unsigned numbers too
+  %out = icmp uge i32 %index, %count
+  br i1 %out, label %fail, label %in
+
+fail:
+  %0 = call i32 (ptr, i64, ptr, ...) @snprintf(ptr %message, i64 80, ptr @ss.string_out_of_bounds, i32 %index, i32 %count)
+  call void @ss.fail(ptr @ss.E0300, ptr %message, i32 %line, i32 %column)
+  unreachable
+
+in:
+  ret i32 %start
+}
+
+define internal i32 @ss.char_at(%ss.string %s, i32 %index, i32 %line, i32 %column) {
+entry:
+  %start = call i32 @ss.char_index(%ss.string %s, i32 %index, i32 %line, i32 %column)
+  %chars = extractvalue %ss.string %s, 1
+  %at = getelementptr inbounds i8, ptr %chars, i32 %start
+  %c = call i32 @ss.decode(ptr %at)
+  ret i32 %c
+}
+
+; `s` with its character `index` replaced by `c`, the strings of the program are values
+define internal %ss.string @ss.with_char(%ss.string %s, i32 %index, i32 %c, i32 %line, i32 %column) {
+entry:
+  %encoded = alloca [4 x i8]
+  %start = call i32 @ss.char_index(%ss.string %s, i32 %index, i32 %line, i32 %column)
+  %length = extractvalue %ss.string %s, 0
+  %chars = extractvalue %ss.string %s, 1
+  %at = getelementptr inbounds i8, ptr %chars, i32 %start
+  %old.size = call i32 @ss.char_size(ptr %at)
+  %new.size = call i32 @ss.encode(i32 %c, ptr %encoded)
+  %end = add i32 %start, %old.size
+  %0 = sub i32 %length, %old.size
+  %new.length = add i32 %0, %new.size
+  %size = zext i32 %new.length to i64
+  %new.chars = call ptr @ss.allocate(i64 %size)
+  %before = zext i32 %start to i64
+  call void @llvm.memcpy.p0.p0.i64(ptr %new.chars, ptr %chars, i64 %before, i1 false)
+  %new.at = getelementptr inbounds i8, ptr %new.chars, i32 %start
+  %encoded.size = zext i32 %new.size to i64
+  call void @llvm.memcpy.p0.p0.i64(ptr %new.at, ptr %encoded, i64 %encoded.size, i1 false)
+  %new.end = getelementptr inbounds i8, ptr %new.at, i32 %new.size
+  %old.end = getelementptr inbounds i8, ptr %chars, i32 %end
+  %1 = sub i32 %length, %end
+  %after = zext i32 %1 to i64
+  call void @llvm.memcpy.p0.p0.i64(ptr %new.end, ptr %old.end, i64 %after, i1 false)
+  %partial = insertvalue %ss.string undef, i32 %new.length, 0
+  %string = insertvalue %ss.string %partial, ptr %new.chars, 1
+  ret %ss.string %string
+}
+
+; The characters of the strings made at run time are never freed
+define internal %ss.string @ss.concat(%ss.string %a, %ss.string %b) {
+entry:
+  %a.length = extractvalue %ss.string %a, 0
+  %a.chars = extractvalue %ss.string %a, 1
+  %b.length = extractvalue %ss.string %b, 0
+  %b.chars = extractvalue %ss.string %b, 1
+  %length = add i32 %a.length, %b.length
+  %size = zext i32 %length to i64
+  %chars = call ptr @ss.allocate(i64 %size)
+  %a.size = zext i32 %a.length to i64
+  %b.size = zext i32 %b.length to i64
+  call void @llvm.memcpy.p0.p0.i64(ptr %chars, ptr %a.chars, i64 %a.size, i1 false)
+  %end = getelementptr inbounds i8, ptr %chars, i64 %a.size
+  call void @llvm.memcpy.p0.p0.i64(ptr %end, ptr %b.chars, i64 %b.size, i1 false)
+  %partial = insertvalue %ss.string undef, i32 %length, 0
+  %string = insertvalue %ss.string %partial, ptr %chars, 1
+  ret %ss.string %string
+}
+
+; Comparisons return a negative number, zero or a positive number. Strings compare byte by
+; byte, which orders UTF-8 like the characters it encodes.
+define internal i32 @ss.compare.integer(i32 %a, i32 %b) {
+entry:
+  %greater = icmp sgt i32 %a, %b
+  %less = icmp slt i32 %a, %b
+  %0 = zext i1 %greater to i32
+  %1 = zext i1 %less to i32
+  %order = sub i32 %0, %1
+  ret i32 %order
+}
+
+define internal i32 @ss.compare.real(double %a, double %b) {
+entry:
+  %greater = fcmp ogt double %a, %b
+  %less = fcmp olt double %a, %b
+  %0 = zext i1 %greater to i32
+  %1 = zext i1 %less to i32
+  %order = sub i32 %0, %1
+  ret i32 %order
+}
+
+define internal i32 @ss.compare.char(i32 %a, i32 %b) {
+entry:
+  %greater = icmp ugt i32 %a, %b
+  %less = icmp ult i32 %a, %b
+  %0 = zext i1 %greater to i32
+  %1 = zext i1 %less to i32
+  %order = sub i32 %0, %1
+  ret i32 %order
+}
+
+define internal i32 @ss.compare.boolean(i1 %a, i1 %b) {
+entry:
+  %greater = icmp ugt i1 %a, %b
+  %less = icmp ult i1 %a, %b
+  %0 = zext i1 %greater to i32
+  %1 = zext i1 %less to i32
+  %order = sub i32 %0, %1
+  ret i32 %order
+}
+
+define internal i32 @ss.compare.string(%ss.string %a, %ss.string %b) {
+entry:
+  %a.length = extractvalue %ss.string %a, 0
+  %a.chars = extractvalue %ss.string %a, 1
+  %b.length = extractvalue %ss.string %b, 0
+  %b.chars = extractvalue %ss.string %b, 1
+  %shorter = icmp ult i32 %a.length, %b.length
+  %common = select i1 %shorter, i32 %a.length, i32 %b.length
+  br label %loop
+
+loop:
+  %i = phi i32 [ 0, %entry ], [ %next, %same ]
+  %more = icmp ult i32 %i, %common
+  br i1 %more, label %byte, label %lengths
+
+byte:
+  %a.at = getelementptr inbounds i8, ptr %a.chars, i32 %i
+  %b.at = getelementptr inbounds i8, ptr %b.chars, i32 %i
+  %a.byte = load i8, ptr %a.at
+  %b.byte = load i8, ptr %b.at
+  %a.unsigned = zext i8 %a.byte to i32
+  %b.unsigned = zext i8 %b.byte to i32
+  %order = call i32 @ss.compare.char(i32 %a.unsigned, i32 %b.unsigned)
+  %equal = icmp eq i32 %order, 0
+  %next = add i32 %i, 1
+  br i1 %equal, label %same, label %different
+
+same:
+  br label %loop
+
+different:
+  ret i32 %order
+
+lengths:
+  %longer = icmp ugt i32 %a.length, %b.length
+  %0 = zext i1 %longer to i32
+  %1 = zext i1 %shorter to i32
+  %by_length = sub i32 %0, %1
+  ret i32 %by_length
+}
+
+; Writes the characters of a string as they are
+define internal void @ss.write(%ss.string %s) {
+entry:
+  %length = extractvalue %ss.string %s, 0
+  %chars = extractvalue %ss.string %s, 1
+  %0 = call i32 (ptr, ...) @printf(ptr @ss.text, i32 %length, ptr %chars)
+  ret void
+}
+
+; Values are printed as the interpreter shows them
+define internal void @ss.print.integer(i32 %n) {
+entry:
+  %0 = call i32 (ptr, ...) @printf(ptr @ss.d, i32 %n)
+  ret void
+}
+
+define internal void @ss.print.boolean(i1 %b) {
+entry:
+  %text = select i1 %b, ptr @ss.true, ptr @ss.false
+  %0 = call i32 (ptr, ...) @printf(ptr @ss.s, ptr %text)
+  ret void
+}
+
+; The shortest digits that read back as the same number, always with a fraction or an exponent
+define internal void @ss.print.real(double %x) {
+entry:
+  %text = alloca [40 x i8]
+  %nan = fcmp uno double %x, %x
+  br i1 %nan, label %not_a_number, label %number
+
+not_a_number:
+  %0 = call i32 (ptr, ...) @printf(ptr @ss.s, ptr @ss.nan)
+  ret void
+
+number:
+  %infinite = fcmp oeq double %x, 0x7FF0000000000000
+  %minus_infinite = fcmp oeq double %x, 0xFFF0000000000000
+  %either = or i1 %infinite, %minus_infinite
+  br i1 %either, label %infinity, label %digits
+
+infinity:
+  %sign = select i1 %infinite, ptr @ss.inf, ptr @ss.minus_inf
+  %1 = call i32 (ptr, ...) @printf(ptr @ss.s, ptr %sign)
+  ret void
+
+digits:
+  %precision = phi i32 [ 1, %number ], [ %next, %digits ]
+  %2 = call i32 (ptr, i64, ptr, ...) @snprintf(ptr %text, i64 40, ptr @ss.g, i32 %precision, double %x)
+  %back = call double @strtod(ptr %text, ptr null)
+  %next = add i32 %precision, 1
+  %same = fcmp oeq double %back, %x
+  br i1 %same, label %done, label %digits
+
+done:
+  %3 = call i32 (ptr, ...) @printf(ptr @ss.s, ptr %text)
+  %fraction = call ptr @strpbrk(ptr %text, ptr @ss.point_or_exponent)
+  %whole = icmp eq ptr %fraction, null
+  br i1 %whole, label %point_zero, label %end
+
+point_zero:
+  %4 = call i32 (ptr, ...) @printf(ptr @ss.s, ptr @ss.point_zero)
+  br label %end
+
+end:
+  ret void
+}
+
+; Control characters are escaped like Rust escapes them, the other ones written in UTF-8
+define internal void @ss.print.escaped(i32 %c, i32 %quote) {
+entry:
+  %encoded = alloca [4 x i8]
+  switch i32 %c, label %plain [
+    i32 10, label %newline
+    i32 13, label %return
+    i32 9, label %tab
+    i32 0, label %nul
+    i32 92, label %backslash
+  ]
+
+newline:
+  call void @ss.print.escape(i32 110)
+  ret void
+
+return:
+  call void @ss.print.escape(i32 114)
+  ret void
+
+tab:
+  call void @ss.print.escape(i32 116)
+  ret void
+
+nul:
+  call void @ss.print.escape(i32 48)
+  ret void
+
+backslash:
+  call void @ss.print.escape(i32 92)
+  ret void
+
+plain:
+  %control = icmp ult i32 %c, 32
+  %delete = icmp eq i32 %c, 127
+  %hidden = or i1 %control, %delete
+  br i1 %hidden, label %unicode, label %visible
+
+unicode:
+  %0 = call i32 (ptr, ...) @printf(ptr @ss.unicode_escape, i32 %c)
+  ret void
+
+visible:
+  %is_quote = icmp eq i32 %c, %quote
+  br i1 %is_quote, label %quoted, label %other
+
+quoted:
+  call void @ss.print.escape(i32 %c)
+  ret void
+
+other:
+  %size = call i32 @ss.encode(i32 %c, ptr %encoded)
+  %1 = call i32 (ptr, ...) @printf(ptr @ss.text, i32 %size, ptr %encoded)
+  ret void
+}
+
+define internal void @ss.print.escape(i32 %c) {
+entry:
+  %0 = call i32 @putchar(i32 92)
+  %1 = call i32 @putchar(i32 %c)
+  ret void
+}
+
+define internal void @ss.print.char(i32 %c) {
+entry:
+  %0 = call i32 @putchar(i32 39)
+  call void @ss.print.escaped(i32 %c, i32 39)
+  %1 = call i32 @putchar(i32 39)
+  ret void
+}
+
+define internal void @ss.print.string(%ss.string %s) {
+entry:
+  %length = extractvalue %ss.string %s, 0
+  %chars = extractvalue %ss.string %s, 1
+  %0 = call i32 @putchar(i32 34)
+  br label %loop
+
+loop:
+  %i = phi i32 [ 0, %entry ], [ %next, %char ]
+  %more = icmp ult i32 %i, %length
+  br i1 %more, label %char, label %end
+
+char:
+  %at = getelementptr inbounds i8, ptr %chars, i32 %i
+  %c = call i32 @ss.decode(ptr %at)
+  %size = call i32 @ss.char_size(ptr %at)
+  call void @ss.print.escaped(i32 %c, i32 34)
+  %next = add i32 %i, %size
+  br label %loop
+
+end:
+  %1 = call i32 @putchar(i32 34)
+  ret void
+}
@@ -0,0 +1,495 @@
+  ;; Runtime of the WebAssembly modules generated from Simplescript.
+  ;;
+  ;; Integers wrap around like in the interpreter. The operations that can fail tell the host
+  ;; which error the interpreter would report, the host is expected to stop the program, the
+  ;; module traps if it returns.
+  ;;
+  ;; Strings are the address of their length in bytes, followed by their characters encoded in
+  ;; UTF-8. The empty string is at address 0, so the strings of a zeroed array or struct are
+  ;; empty. A char is the number of the Unicode character.
+
+  (import "ss" "division_by_zero" (func $ss.division_by_zero (param $line i32) (param $column i32)))
+  (import "ss" "out_of_bounds" (func $ss.out_of_bounds (param $index i32) (param $length i32) (param $line i32) (param $column i32)))
+  (import "ss" "string_out_of_bounds" (func $ss.string_out_of_bounds (param $index i32) (param $length i32) (param $line i32) (param $column i32)))
+
+  (func $ss.div (param $a i32) (param $b i32) (param $line i32) (param $column i32) (result i32)
+    local.get $b
+    i32.eqz
+    if
+      local.get $line
+      local.get $column
+      call $ss.division_by_zero
+      unreachable
+    end
+    ;; the one quotient that doesn't fit wraps around instead of trapping
+    local.get $a
+    i32.const 0x80000000
+    i32.eq
+    local.get $b
+    i32.const -1
+    i32.eq
+    i32.and
+    if
+      local.get $a
+      return
+    end
+    local.get $a
+    local.get $b
+    i32.div_s
+  )
+
+  (func $ss.index (param $index i32) (param $length i32) (param $line i32) (param $column i32) (result i32)
+    ;; negative indices are out of bounds as unsigned numbers too
+    local.get $index
+    local.get $length
+    i32.ge_u
+    if
+      local.get $index
+      local.get $length
+      local.get $line
+      local.get $column
+      call $ss.out_of_bounds
+      unreachable
+    end
+    local.get $index
+  )
+
+  ;; Takes `size` bytes from the heap, growing the memory when it's full. They are never freed
+  (func $ss.alloc (param $size i32) (result i32)
+    (local $address i32)
+    global.get $ss.heap
+    local.set $address
+    local.get $address
+    local.get $size
+    i32.add
+    i32.const 3
+    i32.add
+    i32.const -4
+    i32.and
+    global.set $ss.heap
+    global.get $ss.heap
+    memory.size
+    i32.const 16
+    i32.shl
+    i32.gt_u
+    if
+      global.get $ss.heap
+      memory.size
+      i32.const 16
+      i32.shl
+      i32.sub
+      i32.const 65535
+      i32.add
+      i32.const 16
+      i32.shr_u
+      memory.grow
+      i32.const -1
+      i32.eq
+      if
+        unreachable
+      end
+    end
+    local.get $address
+  )
+
+  (func $ss.concat (param $a i32) (param $b i32) (result i32)
+    (local $a.length i32)
+    (local $b.length i32)
+    (local $string i32)
+    local.get $a
+    i32.load
+    local.set $a.length
+    local.get $b
+    i32.load
+    local.set $b.length
+    local.get $a.length
+    local.get $b.length
+    i32.add
+    i32.const 4
+    i32.add
+    call $ss.alloc
+    local.tee $string
+    local.get $a.length
+    local.get $b.length
+    i32.add
+    i32.store
+    local.get $string
+    i32.const 4
+    i32.add
+    local.get $a
+    i32.const 4
+    i32.add
+    local.get $a.length
+    memory.copy
+    local.get $string
+    i32.const 4
+    i32.add
+    local.get $a.length
+    i32.add
+    local.get $b
+    i32.const 4
+    i32.add
+    local.get $b.length
+    memory.copy
+    local.get $string
+  )
+
+  ;; The number of bytes of the UTF-8 character starting at `at`
+  (func $ss.char_size (param $at i32) (result i32)
+    (local $lead i32)
+    local.get $at
+    i32.load8_u
+    local.tee $lead
+    i32.const 0x80
+    i32.lt_u
+    if
+      i32.const 1
+      return
+    end
+    local.get $lead
+    i32.const 0xe0
+    i32.lt_u
+    if
+      i32.const 2
+      return
+    end
+    i32.const 3
+    i32.const 4
+    local.get $lead
+    i32.const 0xf0
+    i32.lt_u
+    select
+  )
+
+  ;; The character encoded at `at`
+  (func $ss.decode (param $at i32) (result i32)
+    (local $size i32)
+    (local $c i32)
+    (local $i i32)
+    local.get $at
+    call $ss.char_size
+    local.tee $size
+    i32.const 1
+    i32.eq
+    if
+      local.get $at
+      i32.load8_u
+      return
+    end
+    local.get $at
+    i32.load8_u
+    i32.const 0x7f
+    local.get $size
+    i32.shr_u
+    i32.and
+    local.set $c
+    i32.const 1
+    local.set $i
+    block $done
+      loop $next
+        local.get $i
+        local.get $size
+        i32.ge_u
+        br_if $done
+        local.get $c
+        i32.const 6
+        i32.shl
+        local.get $at
+        local.get $i
+        i32.add
+        i32.load8_u
+        i32.const 0x3f
+        i32.and
+        i32.or
+        local.set $c
+        local.get $i
+        i32.const 1
+        i32.add
+        local.set $i
+        br $next
+      end
+    end
+    local.get $c
+  )
+
+  ;; Writes the UTF-8 encoding of `c` at `at`, returns how many bytes it takes
+  (func $ss.encode (param $c i32) (param $at i32) (result i32)
+    (local $size i32)
+    (local $i i32)
+    local.get $c
+    i32.const 0x80
+    i32.lt_u
+    if
+      local.get $at
+      local.get $c
+      i32.store8
+      i32.const 1
+      return
+    end
+    i32.const 2
+    i32.const 3
+    i32.const 4
+    local.get $c
+    i32.const 0x10000
+    i32.lt_u
+    select
+    local.get $c
+    i32.const 0x800
+    i32.lt_u
+    select
+    local.tee $size
+    local.set $i
+    ;; the bytes after the first one, from the last
+    block $done
+      loop $next
+        local.get $i
+        i32.const 1
+        i32.sub
+        local.tee $i
+        i32.eqz
+        br_if $done
+        local.get $at
+        local.get $i
+        i32.add
+        local.get $c
+        i32.const 0x3f
+        i32.and
+        i32.const 0x80
+        i32.or
+        i32.store8
+        local.get $c
+        i32.const 6
+        i32.shr_u
+        local.set $c
+        br $next
+      end
+    end
+    local.get $at
+    i32.const 0xf00
+    local.get $size
+    i32.shr_u
+    local.get $c
+    i32.or
+    i32.store8
+    local.get $size
+  )
+
+  ;; The address of the character `index` of `s`, indices count characters like the interpreter's
+  (func $ss.char_index (param $s i32) (param $index i32) (param $line i32) (param $column i32) (result i32)
+    (local $at i32)
+    (local $end i32)
+    (local $count i32)
+    (local $start i32)
+    local.get $s
+    i32.const 4
+    i32.add
+    local.tee $at
+    local.get $s
+    i32.load
+    i32.add
+    local.set $end
+    block $done
+      loop $next
+        local.get $at
+        local.get $end
+        i32.ge_u
+        br_if $done
+        local.get $count
+        local.get $index
+        i32.eq
+        if
+          local.get $at
+          local.set $start
+        end
+        local.get $at
+        local.get $at
+        call $ss.char_size
+        i32.add
+        local.set $at
+        local.get $count
+        i32.const 1
+        i32.add
+        local.set $count
+        br $next
+      end
+    end
+    ;; negative indices are out of bounds as unsigned numbers too
+    local.get $index
+    local.get $count
+    i32.ge_u
+    if
+      local.get $index
+      local.get $count
+      local.get $line
+      local.get $column
+      call $ss.string_out_of_bounds
+      unreachable
+    end
+    local.get $start
+  )
+
+  (func $ss.char_at (param $s i32) (param $index i32) (param $line i32) (param $column i32) (result i32)
+    local.get $s
+    local.get $index
+    local.get $line
+    local.get $column
+    call $ss.char_index
+    call $ss.decode
+  )
+
+  ;; `s` with its character `index` replaced by `c`, the strings of the program are values
+  (func $ss.with_char (param $s i32) (param $index i32) (param $c i32) (param $line i32) (param $column i32) (result i32)
+    (local $at i32)
+    (local $old i32)
+    (local $before i32)
+    (local $new i32)
+    (local $string i32)
+    local.get $s
+    local.get $index
+    local.get $line
+    local.get $column
+    call $ss.char_index
+    local.tee $at
+    call $ss.char_size
+    local.set $old
+    ;; room for the longest character, the length is written once the new one is encoded
+    local.get $s
+    i32.load
+    local.get $old
+    i32.sub
+    i32.const 8
+    i32.add
+    call $ss.alloc
+    local.tee $string
+    i32.const 4
+    i32.add
+    local.get $s
+    i32.const 4
+    i32.add
+    local.get $at
+    local.get $s
+    i32.sub
+    i32.const 4
+    i32.sub
+    local.tee $before
+    memory.copy
+    local.get $c
+    local.get $string
+    i32.const 4
+    i32.add
+    local.get $before
+    i32.add
+    call $ss.encode
+    local.set $new
+    local.get $string
+    i32.const 4
+    i32.add
+    local.get $before
+    i32.add
+    local.get $new
+    i32.add
+    local.get $at
+    local.get $old
+    i32.add
+    local.get $s
+    i32.load
+    local.get $before
+    i32.sub
+    local.get $old
+    i32.sub
+    memory.copy
+    local.get $string
+    local.get $s
+    i32.load
+    local.get $old
+    i32.sub
+    local.get $new
+    i32.add
+    i32.store
+    local.get $string
+  )
+
+  ;; Comparisons return -1, 0 or 1. Strings compare byte by byte, which orders UTF-8 like the
+  ;; characters it encodes.
+  (func $ss.compare.integer (param $a i32) (param $b i32) (result i32)
+    local.get $a
+    local.get $b
+    i32.gt_s
+    local.get $a
+    local.get $b
+    i32.lt_s
+    i32.sub
+  )
+
+  (func $ss.compare.real (param $a f64) (param $b f64) (result i32)
+    local.get $a
+    local.get $b
+    f64.gt
+    local.get $a
+    local.get $b
+    f64.lt
+    i32.sub
+  )
+
+  (func $ss.compare.char (param $a i32) (param $b i32) (result i32)
+    local.get $a
+    local.get $b
+    i32.gt_u
+    local.get $a
+    local.get $b
+    i32.lt_u
+    i32.sub
+  )
+
+  (func $ss.compare.boolean (param $a i32) (param $b i32) (result i32)
+    local.get $a
+    local.get $b
+    call $ss.compare.char
+  )
+
+  (func $ss.compare.string (param $a i32) (param $b i32) (result i32)
+    (local $a.length i32)
+    (local $b.length i32)
+    (local $i i32)
+    (local $order i32)
+    local.get $a
+    i32.load
+    local.set $a.length
+    local.get $b
+    i32.load
+    local.set $b.length
+    block $done
+      loop $next
+        local.get $i
+        local.get $a.length
+        i32.ge_u
+        br_if $done
+        local.get $i
+        local.get $b.length
+        i32.ge_u
+        br_if $done
+        local.get $a
+        local.get $i
+        i32.add
+        i32.load8_u offset=4
+        local.get $b
+        local.get $i
+        i32.add
+        i32.load8_u offset=4
+        call $ss.compare.char
+        local.tee $order
+        if
+          local.get $order
+          return
+        end
+        local.get $i
+        i32.const 1
+        i32.add
+        local.set $i
+        br $next
+      end
+    end
+    local.get $a.length
+    local.get $b.length
+    call $ss.compare.char
+  )
@@ -0,0 +1,857 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+use super::{array_length, changes_variables, converts_to_same_type, fields, has_effects, indices, parts, resolve, unique_name, Aggregates, Parts};
+use crate::syntatic_analyzer::ast;
+use crate::syntatic_analyzer::diagnostics::{Diagnostic, ErrorCode};
+use crate::syntatic_analyzer::nonterminals::Type;
+
+/*
+ * Translates a program without errors into a WebAssembly text module, the runtime in
+ * simplescript.wat at its top. Every function of the program is exported with its name.
+ *
+ * integer, char and boolean are i32 and real is f64, a char being the number of the Unicode
+ * character, strings are the address of their length followed by their UTF-8 characters. Arrays and structs live in linear memory, laid out like C
+ * lays them out, and are passed around as their address. Scalar variables are locals, the
+ * arrays and structs of a function are in its frame, on a stack that grows down from
+ * STACK_TOP towards the string literals. Strings made at run time go on the heap above it.
+ *
+ *   0          8                 DATA_END                      STACK_TOP
+ *   | ""       | string literals |  <- frames                  | heap ->
+ *
+ * A function copies the arrays and structs it's given into its frame, and the caller copies
+ * the one it returns into its own frame before anything else can reuse the memory.
+ */
+
+pub const RUNTIME: &str = include_str!("simplescript.wat");
+
+const STACK_SIZE: u32 = 1 << 20;
+
+const PAGE_SIZE: u32 = 1 << 16;
+
+fn is_aggregate(type_: &Type) -> bool {
+    matches!(resolve(type_), Type::Array_type_(..) | Type::Struct_type_(_))
+}
+
+fn value_type(type_: &Type) -> &'static str {
+    if resolve(type_) == &Type::Real_ { "f64" } else { "i32" }
+}
+
+// The size and alignment of the values of `type_` in memory, `None` if they don't fit in the 32 bit memory
+fn checked_layout(type_: &Type) -> Option<(u32, u32)> {
+    match resolve(type_) {
+        Type::Int_ | Type::Char_ | Type::String_ => Some((4, 4)),
+        Type::Real_ => Some((8, 8)),
+        Type::Bool_ => Some((1, 1)),
+        Type::Array_type_(length, element) => {
+            let (size, alignment) = checked_layout(element)?;
+            Some((size.checked_mul(*length)?, alignment))
+        },
+        Type::Struct_type_(_) => {
+            let (mut size, mut alignment) = (0u32, 1);
+            for (_, field) in fields(type_) {
+                let (field_size, field_alignment) = checked_layout(field)?;
+                size = size.checked_next_multiple_of(field_alignment)?.checked_add(field_size)?;
+                alignment = alignment.max(field_alignment);
+            }
+            Some((size.checked_next_multiple_of(alignment)?, alignment))
+        },
+        Type::Alias_type_(_) | Type::Universal_ => unreachable!("checked programs have no erroneous types"),
+    }
+}
+
+// The layout of a type `generate` already checked
+fn layout(type_: &Type) -> (u32, u32) {
+    checked_layout(type_).expect("the types of the program fit in memory")
+}
+
+// The offsets of the fields of a struct, in the order they are declared
+fn offsets(type_: &Type) -> Vec<(&str, u32, &Type)> {
+    let mut size = 0u32;
+    fields(type_).into_iter()
+        .map(|(name, field)| {
+            let (field_size, alignment) = layout(field);
+            let offset = size.next_multiple_of(alignment);
+            size = offset + field_size;
+            (name, offset, field)
+        })
+        .collect()
+}
+
+fn memarg(instruction: &str, offset: u32) -> String {
+    if offset == 0 { instruction.to_string() } else { format!("{} offset={}", instruction, offset) }
+}
+
+// Reads the value of `type_` at `offset` from the address on the stack, arrays and structs stay an address
+fn load(type_: &Type, offset: u32) -> Vec<String> {
+    match resolve(type_) {
+        Type::Int_ | Type::Char_ | Type::String_ => vec![memarg("i32.load", offset)],
+        Type::Real_ => vec![memarg("f64.load", offset)],
+        Type::Bool_ => vec![memarg("i32.load8_u", offset)],
+        _ if offset == 0 => vec![],
+        _ => vec![format!("i32.const {}", offset), String::from("i32.add")],
+    }
+}
+
+// Writes the value on the stack at `offset` from the address below it, arrays and structs at offset 0
+fn store(type_: &Type, offset: u32) -> Vec<String> {
+    match resolve(type_) {
+        Type::Int_ | Type::Char_ | Type::String_ => vec![memarg("i32.store", offset)],
+        Type::Real_ => vec![memarg("f64.store", offset)],
+        Type::Bool_ => vec![memarg("i32.store8", offset)],
+        _ => vec![format!("i32.const {}", layout(type_).0), String::from("memory.copy")],
+    }
+}
+
+fn real_literal(x: f64) -> String {
+    if x.is_nan() { String::from("nan") } else { format!("{:?}", x) }
+}
+
+fn char_literal(ch: char) -> u32 {
+    u32::from(ch)
+}
+
+// The suffix of the comparison instructions for a relational operator, `sign` is `_s`, `_u` or empty
+fn relation(op: ast::BinaryOp, sign: &str) -> String {
+    match op {
+        ast::BinaryOp::Equal => String::from("eq"),
+        ast::BinaryOp::NotEqual => String::from("ne"),
+        ast::BinaryOp::Less => format!("lt{}", sign),
+        ast::BinaryOp::Greater => format!("gt{}", sign),
+        ast::BinaryOp::LessEqual => format!("le{}", sign),
+        ast::BinaryOp::GreaterEqual => format!("ge{}", sign),
+        op => unreachable!("{:?} is not a relational operator", op),
+    }
+}
+
+fn bytes_literal(bytes: &[u8]) -> String {
+    let mut literal = String::new();
+    for &byte in bytes {
+        match byte {
+            b' '..=b'~' if byte != b'"' && byte != b'\\' => literal.push(byte as char),
+            _ => write!(literal, "\\{:02x}", byte).unwrap(),
+        }
+    }
+    literal
+}
+
+// Whether a function keeps anything in its frame, arrays and structs of its own or returned to it
+fn block_uses_frame(block: &ast::Block) -> bool {
+    block.vars.iter().any(|var| is_aggregate(&var.type_.type_)) || block.stmts.iter().any(stmt_uses_frame)
+}
+
+fn stmt_uses_frame(stmt: &ast::Stmt) -> bool {
+    match &stmt.kind {
+        ast::StmtKind::If { condition, then_branch, else_branch } =>
+            expr_uses_frame(condition) || stmt_uses_frame(then_branch) || else_branch.as_deref().is_some_and(stmt_uses_frame),
+        ast::StmtKind::While { condition, body, .. } | ast::StmtKind::DoWhile { body, condition, .. } =>
+            expr_uses_frame(condition) || stmt_uses_frame(body),
+        ast::StmtKind::Block(block) => block_uses_frame(block),
+        ast::StmtKind::Assign { target, value } =>
+            indices(target).into_iter().any(|(index, _)| expr_uses_frame(index)) || expr_uses_frame(value),
+        ast::StmtKind::Return(value) => expr_uses_frame(value),
+        _ => false
+    }
+}
+
+fn expr_uses_frame(expr: &ast::Expr) -> bool {
+    is_aggregate(&expr.type_) || match &expr.kind {
+        ast::ExprKind::Binary { left, right, .. } => expr_uses_frame(left) || expr_uses_frame(right),
+        ast::ExprKind::Unary { operand, .. } | ast::ExprKind::Convert(operand) => expr_uses_frame(operand),
+        ast::ExprKind::Call { args, .. } => args.iter().any(expr_uses_frame),
+        ast::ExprKind::LValue(lvalue) | ast::ExprKind::IncDec { target: lvalue, .. } =>
+            indices(lvalue).into_iter().any(|(index, _)| expr_uses_frame(index)),
+        _ => false
+    }
+}
+
+/*
+ * Translates `program`, unless its arrays and structs don't fit in the 32 bit memory: the
+ * values of a type, or the ones a function keeps in its frame, taking more than 4 GiB.
+ */
+pub fn generate(program: &ast::Program) -> Result<String, Vec<Diagnostic>> {
+    let aggregates = Aggregates::new(str::to_string, |kind, n| format!("{}.{}", kind, n));
+    let mut generator = Generator { aggregates, strings: vec![], data_end: 8 };
+    let mut errors = vec![];
+    for declaration in program.declarations.iter() {
+        if let ast::Declaration::Type(type_decl) = declaration {
+            if !matches!(type_decl.kind, ast::TypeDeclKind::Alias(_)) {
+                generator.aggregates.intern(&type_decl.type_, Some(&type_decl.name.name));
+                if checked_layout(&type_decl.type_).is_none() {
+                    let message = format!("the values of type `{}` take more than {} bytes of memory", type_decl.name.name, u32::MAX);
+                    errors.push(Diagnostic::error(ErrorCode::TypeTooLarge, message, type_decl.name.span));
+                }
+            }
+        }
+    }
+    // the functions lay out the types
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let mut functions = String::new();
+    for declaration in program.declarations.iter() {
+        if let ast::Declaration::Function(function) = declaration {
+            match FunctionGenerator::new(&mut generator).generate(function) {
+                Some(wat) => {
+                    functions.push('\n');
+                    functions.push_str(&wat);
+                },
+                None => {
+                    let message = format!("the arrays and structs of `{}` take more than {} bytes of memory", function.name.name, u32::MAX);
+                    errors.push(Diagnostic::error(ErrorCode::TypeTooLarge, message, function.name.span));
+                },
+            }
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let mut helpers = String::new();
+    for index in 0..generator.aggregates.types.len() {
+        if generator.aggregates.compared[index] {
+            let (type_, name) = generator.aggregates.types[index].clone();
+            helpers.push('\n');
+            helpers.push_str(&generator.compare_function(&type_, &name));
+        }
+    }
+
+    let stack_top = generator.data_end.next_multiple_of(16) + STACK_SIZE;
+    let mut wat = String::from("(module\n");
+    wat.push_str(RUNTIME);
+    writeln!(wat, "\n  (memory (export \"memory\") {})", stack_top.div_ceil(PAGE_SIZE)).unwrap();
+    writeln!(wat, "  (global $ss.sp (mut i32) (i32.const {}))", stack_top).unwrap();
+    writeln!(wat, "  (global $ss.heap (mut i32) (i32.const {}))", stack_top).unwrap();
+    writeln!(wat, "  (global $ss.limit i32 (i32.const {}))", generator.data_end).unwrap();
+    if !generator.strings.is_empty() {
+        wat.push('\n');
+        for (s, address) in generator.strings.iter() {
+            let mut bytes = (s.len() as u32).to_le_bytes().to_vec();
+            bytes.extend(s.bytes());
+            writeln!(wat, "  (data (i32.const {}) \"{}\")", address, bytes_literal(&bytes)).unwrap();
+        }
+    }
+    wat.push_str(&helpers);
+    wat.push_str(&functions);
+    wat.push_str(")\n");
+    Ok(wat)
+}
+
+struct Generator {
+    aggregates: Aggregates,
+    // the string literals and their addresses, the first byte after them
+    strings: Vec<(String, u32)>,
+    data_end: u32,
+}
+
+impl Generator {
+    // The address of a string literal, the empty string is the zeroes at address 0
+    fn string(&mut self, s: &str) -> u32 {
+        if s.is_empty() {
+            return 0;
+        }
+        if let Some((_, address)) = self.strings.iter().find(|(known, _)| known == s) {
+            return *address;
+        }
+        let address = self.data_end;
+        self.data_end = (address + 4 + s.len() as u32).next_multiple_of(4);
+        self.strings.push((s.to_string(), address));
+        address
+    }
+
+    // The runtime or generated function comparing values of `type_`
+    fn compare(&mut self, type_: &Type) -> String {
+        format!("$ss.compare.{}", self.aggregates.helper(type_, "compare"))
+    }
+
+    fn compare_function(&mut self, type_: &Type, name: &str) -> String {
+        let mut function = format!("  (func $ss.compare.{} (param $a i32) (param $b i32) (result i32)\n", name);
+        match parts(type_) {
+            Parts::Elements(length, element) => {
+                let compare = self.compare(element);
+                let size = layout(element).0;
+                let load = load(element, 0);
+                function.push_str("    (local $i i32)\n    (local $order i32)\n    block $done\n      loop $next\n");
+                writeln!(function, "        local.get $i\n        i32.const {}\n        i32.ge_u\n        br_if $done", length).unwrap();
+                for operand in ["$a", "$b"] {
+                    writeln!(function, "        local.get {}\n        local.get $i", operand).unwrap();
+                    if size != 1 {
+                        writeln!(function, "        i32.const {}\n        i32.mul", size).unwrap();
+                    }
+                    function.push_str("        i32.add\n");
+                    for instruction in load.iter() {
+                        writeln!(function, "        {}", instruction).unwrap();
+                    }
+                }
+                writeln!(function, "        call {}", compare).unwrap();
+                function.push_str("        local.tee $order\n        if\n          local.get $order\n          return\n        end\n");
+                function.push_str("        local.get $i\n        i32.const 1\n        i32.add\n        local.set $i\n        br $next\n");
+                function.push_str("      end\n    end\n    i32.const 0\n");
+            },
+            Parts::Fields(_) => {
+                let offsets = offsets(type_);
+                if offsets.len() > 1 {
+                    function.push_str("    (local $order i32)\n");
+                }
+                for (i, (_, offset, field)) in offsets.iter().enumerate() {
+                    for operand in ["$a", "$b"] {
+                        writeln!(function, "    local.get {}", operand).unwrap();
+                        for instruction in load(field, *offset) {
+                            writeln!(function, "    {}", instruction).unwrap();
+                        }
+                    }
+                    writeln!(function, "    call {}", self.compare(field)).unwrap();
+                    // the first field that differs decides
+                    if i + 1 < offsets.len() {
+                        function.push_str("    local.tee $order\n    if\n      local.get $order\n      return\n    end\n");
+                    }
+                }
+            },
+        }
+        function.push_str("  )\n");
+        function
+    }
+}
+
+// Where the left value `place` reached is
+enum Place {
+    Local(String),
+    // at an offset from the address on the stack
+    Memory(u32),
+    // a character of the string in a place, the string and the index are on the stack
+    Char { string: Box<Place>, line: u32, column: u32 },
+}
+
+struct FunctionGenerator<'g> {
+    generator: &'g mut Generator,
+    locals: Vec<(String, &'static str)>,
+    body: String,
+    // how deep the instructions being written are nested in blocks
+    depth: usize,
+    // whether the instructions written last leave the block, what follows them is never run
+    terminated: bool,
+    // the bytes of the frame, if the function has one, and whether they outgrew the memory
+    frame: Option<u32>,
+    overflowed: bool,
+    temporaries: u32,
+    // the local names in use, and the local of each variable in scope
+    taken: HashSet<String>,
+    scopes: Vec<HashMap<String, String>>,
+}
+
+impl<'g> FunctionGenerator<'g> {
+    fn new(generator: &'g mut Generator) -> Self {
+        FunctionGenerator {
+            generator,
+            locals: vec![],
+            body: String::new(),
+            depth: 0,
+            terminated: false,
+            frame: None,
+            overflowed: false,
+            temporaries: 0,
+            taken: HashSet::new(),
+            scopes: vec![],
+        }
+    }
+
+    // The function, `None` if its frame doesn't fit in memory
+    fn generate(mut self, function: &ast::FunctionDecl) -> Option<String> {
+        let name = &function.name.name;
+        let mut wat = format!("  (func ${} (export \"{}\")", name, name);
+        if function.params.iter().any(|param| is_aggregate(&param.type_.type_)) || block_uses_frame(&function.body) {
+            self.frame = Some(0);
+        }
+
+        self.taken.extend(function.params.iter().map(|param| param.name.name.clone()));
+        let mut scope = HashMap::new();
+        for param in function.params.iter() {
+            let type_ = &param.type_.type_;
+            write!(wat, " (param ${} {})", param.name.name, value_type(type_)).unwrap();
+            // arrays and structs are copied, changing them doesn't change the caller's
+            if is_aggregate(type_) {
+                let offset = self.slot(type_);
+                self.address(offset);
+                self.op(&format!("local.get ${}", param.name.name));
+                self.ops(store(type_, 0));
+                self.address(offset);
+                self.op(&format!("local.set ${}", param.name.name));
+            }
+            scope.insert(param.name.name.clone(), param.name.name.clone());
+        }
+        self.scopes.push(scope);
+        writeln!(wat, " (result {})", value_type(&function.return_type.type_)).unwrap();
+
+        self.block_contents(&function.body);
+        // every path returns, the end of the body can't be reached
+        if !self.terminated {
+            self.op("unreachable");
+        }
+
+        for (local, type_) in self.locals.iter() {
+            writeln!(wat, "    (local ${} {})", local, type_).unwrap();
+        }
+        if let Some(size) = self.frame {
+            if self.overflowed {
+                return None;
+            }
+            wat.push_str("    (local $ss.top i32)\n    (local $ss.fp i32)\n");
+            let size = size.checked_next_multiple_of(8)?;
+            // the stack would run into the string literals
+            wat.push_str("    global.get $ss.sp\n    local.tee $ss.top\n    global.get $ss.limit\n    i32.sub\n");
+            writeln!(wat, "    i32.const {}\n    i32.lt_u\n    if\n      unreachable\n    end", size).unwrap();
+            writeln!(wat, "    local.get $ss.top\n    i32.const {}\n    i32.sub\n    local.tee $ss.fp\n    global.set $ss.sp", size).unwrap();
+        }
+        wat.push_str(&self.body);
+        wat.push_str("  )\n");
+        Some(wat)
+    }
+
+    fn op(&mut self, instruction: &str) {
+        writeln!(self.body, "    {}{}", "  ".repeat(self.depth), instruction).unwrap();
+    }
+
+    fn ops(&mut self, instructions: Vec<String>) {
+        for instruction in instructions {
+            self.op(&instruction);
+        }
+    }
+
+    fn local(&mut self, name: &str, type_: &'static str) -> String {
+        let local = unique_name(&mut self.taken, name);
+        self.locals.push((local.clone(), type_));
+        local
+    }
+
+    fn temporary(&mut self, type_: &'static str) -> String {
+        let name = format!("ss.t{}", self.temporaries);
+        self.temporaries += 1;
+        self.local(&name, type_)
+    }
+
+    // Room for a value of `type_` in the frame, returns its offset
+    fn slot(&mut self, type_: &Type) -> u32 {
+        let (size, alignment) = layout(type_);
+        let frame = self.frame.as_mut().expect("functions with arrays or structs have a frame");
+        match frame.checked_next_multiple_of(alignment).and_then(|offset| Some((offset, offset.checked_add(size)?))) {
+            Some((offset, end)) => {
+                *frame = end;
+                offset
+            },
+            None => {
+                self.overflowed = true;
+                0
+            },
+        }
+    }
+
+    fn address(&mut self, offset: u32) {
+        self.op("local.get $ss.fp");
+        if offset > 0 {
+            self.op(&format!("i32.const {}", offset));
+            self.op("i32.add");
+        }
+    }
+
+    fn block_contents(&mut self, block: &ast::Block) {
+        for var in block.vars.iter() {
+            let type_ = &var.type_.type_;
+            for name in var.names.iter() {
+                let local = self.local(&name.name, value_type(type_));
+                // variables start as zero every time the block is entered, like the interpreter's
+                if is_aggregate(type_) {
+                    let offset = self.slot(type_);
+                    self.address(offset);
+                    self.op(&format!("local.tee ${}", local));
+                    self.op("i32.const 0");
+                    self.op(&format!("i32.const {}", layout(type_).0));
+                    self.op("memory.fill");
+                } else {
+                    self.op(if value_type(type_) == "f64" { "f64.const 0" } else { "i32.const 0" });
+                    self.op(&format!("local.set ${}", local));
+                }
+                self.scopes.last_mut().unwrap().insert(name.name.clone(), local);
+            }
+        }
+        for stmt in block.stmts.iter() {
+            if self.terminated {
+                break;
+            }
+            self.stmt(stmt);
+        }
+    }
+
+    // Opens a block, loop or if, `end` closes it
+    fn open(&mut self, instruction: &str) {
+        self.op(instruction);
+        self.depth += 1;
+    }
+
+    fn end(&mut self) {
+        self.depth -= 1;
+        self.op("end");
+        self.terminated = false;
+    }
+
+    fn stmt(&mut self, stmt: &ast::Stmt) {
+        match &stmt.kind {
+            ast::StmtKind::If { condition, then_branch, else_branch } => {
+                self.expr(condition);
+                self.open("if");
+                self.stmt(then_branch);
+                if let Some(else_branch) = else_branch {
+                    self.depth -= 1;
+                    self.op("else");
+                    self.depth += 1;
+                    self.terminated = false;
+                    self.stmt(else_branch);
+                }
+                self.end();
+            },
+            ast::StmtKind::While { id, condition, body } => {
+                self.open(&format!("block $break.{}", id.0));
+                self.open(&format!("loop $continue.{}", id.0));
+                self.expr(condition);
+                self.op("i32.eqz");
+                self.op(&format!("br_if $break.{}", id.0));
+                self.stmt(body);
+                if !self.terminated {
+                    self.op(&format!("br $continue.{}", id.0));
+                }
+                self.end();
+                self.end();
+            },
+            ast::StmtKind::DoWhile { id, body, condition } => {
+                self.open(&format!("block $break.{}", id.0));
+                self.open(&format!("loop $do.{}", id.0));
+                self.open(&format!("block $continue.{}", id.0));
+                self.stmt(body);
+                self.end();
+                self.expr(condition);
+                self.op(&format!("br_if $do.{}", id.0));
+                self.end();
+                self.end();
+            },
+            ast::StmtKind::Block(block) => {
+                self.scopes.push(HashMap::new());
+                self.block_contents(block);
+                self.scopes.pop();
+            },
+            ast::StmtKind::Assign { target, value } => {
+                match self.place(target, Some(value)) {
+                    Place::Local(local) => self.op(&format!("local.set ${}", local)),
+                    Place::Memory(offset) => self.ops(store(&target.type_, offset)),
+                    // a string is a value, assigning one of its characters assigns a new string
+                    Place::Char { string, line, column } => {
+                        self.op(&format!("i32.const {}", line));
+                        self.op(&format!("i32.const {}", column));
+                        self.op("call $ss.with_char");
+                        match *string {
+                            Place::Local(local) => self.op(&format!("local.set ${}", local)),
+                            Place::Memory(offset) => self.ops(store(&Type::String_, offset)),
+                            Place::Char { .. } => unreachable!("a character is not a string"),
+                        }
+                    },
+                }
+            },
+            ast::StmtKind::Break(id) => {
+                self.op(&format!("br $break.{}", id.0));
+                self.terminated = true;
+            },
+            ast::StmtKind::Continue(id) => {
+                self.op(&format!("br $continue.{}", id.0));
+                self.terminated = true;
+            },
+            ast::StmtKind::Return(value) => {
+                self.expr(value);
+                if self.frame.is_some() {
+                    self.op("local.get $ss.top");
+                    self.op("global.set $ss.sp");
+                }
+                self.op("return");
+                self.terminated = true;
+            },
+            ast::StmtKind::Error => unreachable!("checked programs have no erroneous statements"),
+        }
+    }
+
+    /*
+     * Pushes the address of the part of a variable a left value names, unless it's a scalar
+     * variable in a local, then the value assigned to it if there is one. Like the interpreter
+     * the indices are checked once all of them and the value are known, they are kept in
+     * temporaries until then when evaluating what follows them can fail. The index of a string
+     * is checked last, by the runtime when it reads or replaces the character.
+     */
+    fn place(&mut self, lvalue: &ast::LValue, value: Option<&ast::Expr>) -> Place {
+        let indices = indices(lvalue);
+        let deferred = !indices.is_empty() && indices.iter().skip(1).map(|(index, _)| *index).chain(value).any(has_effects);
+        let mut temporaries = vec![];
+        let mut value_temporary = None;
+        if deferred {
+            for (index, _) in indices.iter() {
+                self.expr(index);
+                let temporary = self.temporary("i32");
+                self.op(&format!("local.set ${}", temporary));
+                temporaries.push(temporary);
+            }
+            if let Some(value) = value {
+                self.expr(value);
+                let temporary = self.temporary(value_type(&value.type_));
+                self.op(&format!("local.set ${}", temporary));
+                value_temporary = Some(temporary);
+            }
+        }
+
+        let mut temporaries = temporaries.into_iter();
+        let place = match &lvalue.kind {
+            ast::LValueKind::Variable(name) if !is_aggregate(&lvalue.type_) => Place::Local(self.variable(&name.name)),
+            ast::LValueKind::Index { base, index } if *resolve(&base.type_) == Type::String_ => {
+                let string = match &base.kind {
+                    ast::LValueKind::Variable(name) => {
+                        let local = self.variable(&name.name);
+                        self.op(&format!("local.get ${}", local));
+                        Place::Local(local)
+                    },
+                    _ => {
+                        let offset = self.path(base, &mut temporaries);
+                        // the address stays below the string, the new one is stored there
+                        if value.is_some() {
+                            let address = self.temporary("i32");
+                            self.op(&format!("local.tee ${}", address));
+                            self.op(&format!("local.get ${}", address));
+                        }
+                        self.ops(load(&Type::String_, offset));
+                        Place::Memory(offset)
+                    },
+                };
+                match temporaries.next() {
+                    Some(temporary) => self.op(&format!("local.get ${}", temporary)),
+                    None => self.expr(index),
+                }
+                let start = index.span.start;
+                Place::Char { string: Box::new(string), line: start.line, column: start.column }
+            },
+            _ => {
+                let offset = self.path(lvalue, &mut temporaries);
+                // the address of an array or struct is below the value, the offset has to be added now
+                if is_aggregate(&lvalue.type_) && offset > 0 {
+                    self.op(&format!("i32.const {}", offset));
+                    self.op("i32.add");
+                    Place::Memory(0)
+                } else {
+                    Place::Memory(offset)
+                }
+            },
+        };
+        match (value_temporary, value) {
+            (Some(temporary), _) => self.op(&format!("local.get ${}", temporary)),
+            (None, Some(value)) => self.expr(value),
+            (None, None) => {},
+        }
+        place
+    }
+
+    fn variable(&self, name: &str) -> String {
+        self.scopes.iter().rev().find_map(|scope| scope.get(name)).expect("the scope analysis checks names").clone()
+    }
+
+    // Pushes the address the part of a left value is at an offset from, returns the offset
+    fn path(&mut self, lvalue: &ast::LValue, temporaries: &mut impl Iterator<Item = String>) -> u32 {
+        match &lvalue.kind {
+            ast::LValueKind::Variable(name) => {
+                let local = self.variable(&name.name);
+                self.op(&format!("local.get ${}", local));
+                0
+            },
+            ast::LValueKind::Field { base, field } => {
+                let offset = self.path(base, temporaries);
+                let (_, field_offset, _) = offsets(&base.type_).into_iter().find(|(name, _, _)| *name == field.name).unwrap();
+                offset + field_offset
+            },
+            ast::LValueKind::Index { base, index } => {
+                let offset = self.path(base, temporaries);
+                match temporaries.next() {
+                    Some(temporary) => self.op(&format!("local.get ${}", temporary)),
+                    None => self.expr(index),
+                }
+                let start = index.span.start;
+                self.op(&format!("i32.const {}", array_length(&base.type_)));
+                self.op(&format!("i32.const {}", start.line));
+                self.op(&format!("i32.const {}", start.column));
+                self.op("call $ss.index");
+                let size = layout(&lvalue.type_).0;
+                if size != 1 {
+                    self.op(&format!("i32.const {}", size));
+                    self.op("i32.mul");
+                }
+                self.op("i32.add");
+                offset
+            },
+        }
+    }
+
+    /*
+     * Pushes the values of operands evaluated left to right. An array or struct read from a
+     * variable is its address, so it's copied first when an operand after it changes variables.
+     */
+    fn operands(&mut self, operands: &[&ast::Expr]) {
+        for (i, operand) in operands.iter().enumerate() {
+            let copied = is_aggregate(&operand.type_) && matches!(operand.kind, ast::ExprKind::LValue(_)) &&
+                         operands[i + 1..].iter().any(|later| changes_variables(later));
+            if copied {
+                let offset = self.slot(&operand.type_);
+                self.address(offset);
+                self.expr(operand);
+                self.ops(store(&operand.type_, 0));
+                self.address(offset);
+            } else {
+                self.expr(operand);
+            }
+        }
+    }
+
+    fn expr(&mut self, expr: &ast::Expr) {
+        match &expr.kind {
+            ast::ExprKind::Integer(n) => self.op(&format!("i32.const {}", n)),
+            ast::ExprKind::Real(x) => self.op(&format!("f64.const {}", real_literal(*x))),
+            ast::ExprKind::Char(ch) => self.op(&format!("i32.const {}", char_literal(*ch))),
+            ast::ExprKind::Bool(b) => self.op(&format!("i32.const {}", u8::from(*b))),
+            ast::ExprKind::String(s) => {
+                let address = self.generator.string(s);
+                self.op(&format!("i32.const {}", address));
+            },
+            ast::ExprKind::LValue(lvalue) => match self.place(lvalue, None) {
+                Place::Local(local) => self.op(&format!("local.get ${}", local)),
+                Place::Memory(offset) => self.ops(load(&expr.type_, offset)),
+                Place::Char { line, column, .. } => {
+                    self.op(&format!("i32.const {}", line));
+                    self.op(&format!("i32.const {}", column));
+                    self.op("call $ss.char_at");
+                },
+            },
+            // the right operand is only evaluated when the left one doesn't decide
+            ast::ExprKind::Binary { op: op @ (ast::BinaryOp::And | ast::BinaryOp::Or), left, right } => {
+                self.expr(left);
+                self.open("if (result i32)");
+                if *op == ast::BinaryOp::And {
+                    self.expr(right);
+                } else {
+                    self.op("i32.const 1");
+                }
+                self.depth -= 1;
+                self.op("else");
+                self.depth += 1;
+                if *op == ast::BinaryOp::And {
+                    self.op("i32.const 0");
+                } else {
+                    self.expr(right);
+                }
+                self.end();
+            },
+            ast::ExprKind::Binary { op, left, right } => {
+                self.operands(&[left.as_ref(), right.as_ref()]);
+                let start = expr.span.start;
+                let instruction = match (op, resolve(&left.type_)) {
+                    (ast::BinaryOp::Add, Type::Int_) => String::from("i32.add"),
+                    (ast::BinaryOp::Sub, Type::Int_) => String::from("i32.sub"),
+                    (ast::BinaryOp::Mul, Type::Int_) => String::from("i32.mul"),
+                    (ast::BinaryOp::Div, Type::Int_) => {
+                        self.op(&format!("i32.const {}", start.line));
+                        self.op(&format!("i32.const {}", start.column));
+                        String::from("call $ss.div")
+                    },
+                    (ast::BinaryOp::Add, Type::Real_) => String::from("f64.add"),
+                    (ast::BinaryOp::Sub, Type::Real_) => String::from("f64.sub"),
+                    (ast::BinaryOp::Mul, Type::Real_) => String::from("f64.mul"),
+                    (ast::BinaryOp::Div, Type::Real_) => String::from("f64.div"),
+                    (ast::BinaryOp::Add, Type::String_) => String::from("call $ss.concat"),
+                    (_, Type::Int_) => format!("i32.{}", relation(*op, "_s")),
+                    (_, Type::Real_) => format!("f64.{}", relation(*op, "")),
+                    (_, Type::Char_ | Type::Bool_) => format!("i32.{}", relation(*op, "_u")),
+                    // strings, arrays and structs are ordered by a function
+                    (_, type_) => {
+                        let compare = self.generator.compare(type_);
+                        self.op(&format!("call {}", compare));
+                        self.op("i32.const 0");
+                        format!("i32.{}", relation(*op, "_s"))
+                    },
+                };
+                self.op(&instruction);
+            },
+            ast::ExprKind::Unary { op: ast::UnaryOp::Negate, operand } if resolve(&operand.type_) == &Type::Int_ => {
+                self.op("i32.const 0");
+                self.expr(operand);
+                self.op("i32.sub");
+            },
+            ast::ExprKind::Unary { op: ast::UnaryOp::Negate, operand } => {
+                self.expr(operand);
+                self.op("f64.neg");
+            },
+            ast::ExprKind::Unary { op: ast::UnaryOp::Not, operand } => {
+                self.expr(operand);
+                self.op("i32.eqz");
+            },
+            ast::ExprKind::Convert(operand) if converts_to_same_type(expr) => self.expr(operand),
+            ast::ExprKind::Convert(operand) if expr.type_ == Type::Real_ => {
+                self.expr(operand);
+                self.op("f64.convert_i32_s");
+            },
+            // rounds towards zero and saturates, NaN becomes 0
+            ast::ExprKind::Convert(operand) => {
+                self.expr(operand);
+                self.op("i32.trunc_sat_f64_s");
+            },
+            ast::ExprKind::IncDec { op, target } => {
+                let (delta, prefix) = match op {
+                    ast::IncDecOp::PreIncrement => ("i32.add", true),
+                    ast::IncDecOp::PreDecrement => ("i32.sub", true),
+                    ast::IncDecOp::PostIncrement => ("i32.add", false),
+                    ast::IncDecOp::PostDecrement => ("i32.sub", false),
+                };
+                match self.place(target, None) {
+                    Place::Local(local) => {
+                        self.op(&format!("local.get ${}", local));
+                        if !prefix {
+                            self.op(&format!("local.get ${}", local));
+                        }
+                        self.op("i32.const 1");
+                        self.op(delta);
+                        self.op(&format!("{} ${}", if prefix { "local.tee" } else { "local.set" }, local));
+                    },
+                    Place::Char { .. } => unreachable!("only integers are incremented"),
+                    Place::Memory(offset) => {
+                        let address = self.temporary("i32");
+                        let value = self.temporary("i32");
+                        self.op(&format!("local.tee ${}", address));
+                        self.op(&format!("local.get ${}", address));
+                        self.op(&memarg("i32.load", offset));
+                        if !prefix {
+                            self.op(&format!("local.tee ${}", value));
+                        }
+                        self.op("i32.const 1");
+                        self.op(delta);
+                        if prefix {
+                            self.op(&format!("local.tee ${}", value));
+                        }
+                        self.op(&memarg("i32.store", offset));
+                        self.op(&format!("local.get ${}", value));
+                    },
+                }
+            },
+            // an array or struct returned is in the frame of the function, copied before another call reuses it
+            ast::ExprKind::Call { function, args } if is_aggregate(&expr.type_) => {
+                let offset = self.slot(&expr.type_);
+                self.address(offset);
+                self.operands(&args.iter().collect::<Vec<_>>());
+                self.op(&format!("call ${}", function.name));
+                self.ops(store(&expr.type_, 0));
+                self.address(offset);
+            },
+            ast::ExprKind::Call { function, args } => {
+                self.operands(&args.iter().collect::<Vec<_>>());
+                self.op(&format!("call ${}", function.name));
+            },
+        }
+    }
+}
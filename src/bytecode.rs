@@ -0,0 +1,444 @@
+mod format;
+mod ssbc;
+pub(crate) mod vm;
+
+use std::collections::HashMap;
+
+use crate::syntatic_analyzer::ast;
+use crate::syntatic_analyzer::nonterminals::{Kind, Type};
+use crate::syntatic_analyzer::span::Span;
+pub use format::{Constant, Function, Instruction, Layout, Module};
+pub use ssbc::{MAGIC, VERSION};
+
+/*
+ * Compiles a program without errors to bytecode. `source` is kept in the module so the
+ * errors of the VM can point into it.
+ *
+ * Locals get a slot each, a block that declares a name the function already has gets a new
+ * slot, so the slots of a function never change meaning. Conditions leave a boolean on the
+ * stack, `&&` and `||` jump over their right operand when the left one decides.
+ */
+pub(crate) fn compile(program: &ast::Program, source: &str) -> Module {
+    let mut module = Module { source: source.to_string(), ..Module::default() };
+    let mut functions = HashMap::new();
+    for declaration in program.declarations.iter() {
+        match declaration {
+            // struct layouts are named after the first type declared with them
+            ast::Declaration::Type(type_decl) => {
+                if let ast::TypeDeclKind::Struct { .. } = type_decl.kind {
+                    intern_named(&mut module, &type_decl.type_, &type_decl.name.name);
+                }
+            },
+            ast::Declaration::Function(function) => {
+                let index = functions.len() as u16;
+                functions.insert(function.name.name.clone(), index);
+            },
+        }
+    }
+
+    for declaration in program.declarations.iter() {
+        if let ast::Declaration::Function(function) = declaration {
+            let function = FunctionCompiler::new(&mut module, &functions).compile(function);
+            module.functions.push(function);
+        }
+    }
+    module
+}
+
+fn intern(module: &mut Module, type_: &Type) -> u16 {
+    intern_named(module, type_, "")
+}
+
+// The index of the layout of `type_`, added to the module's types if it isn't there yet
+fn intern_named(module: &mut Module, type_: &Type, name: &str) -> u16 {
+    let layout = match type_ {
+        Type::Int_ => Layout::Integer,
+        Type::Real_ => Layout::Real,
+        Type::Char_ => Layout::Char,
+        Type::Bool_ => Layout::Boolean,
+        Type::String_ => Layout::String,
+        Type::Array_type_(length, element) => Layout::Array { length: *length, element: intern(module, element) },
+        Type::Alias_type_(type_) => return intern_named(module, type_, name),
+        Type::Struct_type_(fields) => {
+            let fields: Vec<(String, u16)> = fields.iter()
+                .filter_map(|field| match &field.1 {
+                    Kind::field(type_) => Some((field.0.clone(), intern(module, type_))),
+                    _ => None
+                })
+                .collect();
+            // the same struct declared again under another name is the same layout
+            let existing = module.types.iter().position(|layout| matches!(layout, Layout::Struct { fields: known, .. } if *known == fields));
+            if let Some(index) = existing {
+                return index as u16;
+            }
+            Layout::Struct { name: name.to_string(), fields }
+        },
+        Type::Universal_ => unreachable!("checked programs have no erroneous types"),
+    };
+    match module.types.iter().position(|known| *known == layout) {
+        Some(index) => index as u16,
+        None => {
+            module.types.push(layout);
+            (module.types.len() - 1) as u16
+        }
+    }
+}
+
+// The position of `field` among the fields of the struct `type_`
+fn field_index(type_: &Type, field: &str) -> u32 {
+    match type_ {
+        Type::Alias_type_(type_) => field_index(type_, field),
+        Type::Struct_type_(fields) => fields.iter()
+            .filter(|object| matches!(object.1, Kind::field(_)))
+            .position(|object| object.0 == field)
+            .expect("the semantic analysis checks fields") as u32,
+        _ => unreachable!("the semantic analysis checks fields"),
+    }
+}
+
+// The length of the array type `type_`, none for a string whose length is only known when the program runs
+fn array_length(type_: &Type) -> Option<u32> {
+    match type_ {
+        Type::Alias_type_(type_) => array_length(type_),
+        Type::Array_type_(length, _) => Some(*length),
+        Type::String_ => None,
+        _ => unreachable!("the semantic analysis checks indexing"),
+    }
+}
+
+struct FunctionCompiler<'m> {
+    module: &'m mut Module,
+    functions: &'m HashMap<String, u16>,
+    code: Vec<Instruction>,
+    spans: Vec<(u32, Span)>,
+    locals: Vec<u16>,
+    local_names: Vec<String>,
+    // source name to slot, one map per open block
+    scopes: Vec<HashMap<String, u16>>,
+    // the jumps of `continue` and `break` still to be patched, for each loop being compiled
+    loops: Vec<(ast::LoopId, Vec<usize>, Vec<usize>)>,
+}
+
+impl<'m> FunctionCompiler<'m> {
+    fn new(module: &'m mut Module, functions: &'m HashMap<String, u16>) -> Self {
+        FunctionCompiler {
+            module,
+            functions,
+            code: vec![],
+            spans: vec![],
+            locals: vec![],
+            local_names: vec![],
+            scopes: vec![HashMap::new()],
+            loops: vec![],
+        }
+    }
+
+    fn compile(mut self, function: &ast::FunctionDecl) -> Function {
+        for param in function.params.iter() {
+            self.declare(&param.name.name, &param.type_.type_);
+        }
+        // the body shares the scope of the parameters
+        self.declare_vars(&function.body.vars);
+        for stmt in function.body.stmts.iter() {
+            self.stmt(stmt);
+        }
+
+        Function {
+            name: function.name.name.clone(),
+            n_params: function.params.len() as u16,
+            locals: self.locals,
+            local_names: self.local_names,
+            return_type: intern(self.module, &function.return_type.type_),
+            code: self.code,
+            spans: self.spans,
+        }
+    }
+
+    fn declare(&mut self, name: &str, type_: &Type) {
+        let slot = self.locals.len() as u16;
+        let layout = intern(self.module, type_);
+        self.locals.push(layout);
+        // a name declared again gets a slot of its own, numbered like the IR renames it
+        let count = self.local_names.iter().filter(|known| known.split('.').next() == Some(name)).count();
+        self.local_names.push(if count == 0 { name.to_string() } else { format!("{}.{}", name, count) });
+        self.scopes.last_mut().unwrap().insert(name.to_string(), slot);
+    }
+
+    fn declare_vars(&mut self, vars: &[ast::VarDecl]) {
+        for var in vars.iter() {
+            for name in var.names.iter() {
+                self.declare(&name.name, &var.type_.type_);
+            }
+        }
+    }
+
+    fn lookup(&self, name: &str) -> u16 {
+        *self.scopes.iter().rev()
+            .find_map(|scope| scope.get(name))
+            .expect("the semantic analysis rejects undeclared variables")
+    }
+
+    fn emit(&mut self, instruction: Instruction) -> usize {
+        self.code.push(instruction);
+        self.code.len() - 1
+    }
+
+    // An instruction that can fail, the VM reports its errors at `span`
+    fn emit_at(&mut self, instruction: Instruction, span: Span) {
+        let pc = self.emit(instruction);
+        self.spans.push((pc as u32, span));
+    }
+
+    fn constant(&mut self, constant: Constant) {
+        let index = match self.module.constants.iter().position(|known| *known == constant) {
+            Some(index) => index,
+            None => {
+                self.module.constants.push(constant);
+                self.module.constants.len() - 1
+            }
+        };
+        self.emit(Instruction::Const(index as u16));
+    }
+
+    // A jump to be patched once its target is known
+    fn jump(&mut self, instruction: fn(i32) -> Instruction) -> usize {
+        self.emit(instruction(0))
+    }
+
+    // Points the jump at `from` to the next instruction
+    fn patch(&mut self, from: usize) {
+        let offset = (self.code.len() - from - 1) as i32;
+        self.code[from] = match self.code[from] {
+            Instruction::Jump(_) => Instruction::Jump(offset),
+            Instruction::JumpIfFalse(_) => Instruction::JumpIfFalse(offset),
+            instruction => unreachable!("{:?} is not a jump", instruction),
+        };
+    }
+
+    fn jump_back(&mut self, instruction: fn(i32) -> Instruction, target: usize) {
+        let offset = target as i32 - self.code.len() as i32 - 1;
+        self.emit(instruction(offset));
+    }
+
+    fn stmt(&mut self, stmt: &ast::Stmt) {
+        match &stmt.kind {
+            ast::StmtKind::If { condition, then_branch, else_branch } => {
+                self.expr(condition);
+                let to_else = self.jump(Instruction::JumpIfFalse);
+                self.stmt(then_branch);
+                match else_branch {
+                    Some(else_branch) => {
+                        let to_end = self.jump(Instruction::Jump);
+                        self.patch(to_else);
+                        self.stmt(else_branch);
+                        self.patch(to_end);
+                    },
+                    None => self.patch(to_else),
+                }
+            },
+            ast::StmtKind::While { id, condition, body } => {
+                let start = self.code.len();
+                self.expr(condition);
+                let to_end = self.jump(Instruction::JumpIfFalse);
+                self.loops.push((*id, vec![], vec![]));
+                self.stmt(body);
+                let (_, continues, breaks) = self.loops.pop().unwrap();
+                for from in continues {
+                    let offset = start as i32 - from as i32 - 1;
+                    self.code[from] = Instruction::Jump(offset);
+                }
+                self.jump_back(Instruction::Jump, start);
+                self.patch(to_end);
+                for from in breaks {
+                    self.patch(from);
+                }
+            },
+            ast::StmtKind::DoWhile { id, body, condition } => {
+                let start = self.code.len();
+                self.loops.push((*id, vec![], vec![]));
+                self.stmt(body);
+                let (_, continues, breaks) = self.loops.pop().unwrap();
+                for from in continues {
+                    self.patch(from);
+                }
+                self.expr(condition);
+                // jump back while the condition holds
+                let to_end = self.jump(Instruction::JumpIfFalse);
+                self.jump_back(Instruction::Jump, start);
+                self.patch(to_end);
+                for from in breaks {
+                    self.patch(from);
+                }
+            },
+            ast::StmtKind::Block(block) => {
+                self.scopes.push(HashMap::new());
+                self.declare_vars(&block.vars);
+                for stmt in block.stmts.iter() {
+                    self.stmt(stmt);
+                }
+                self.scopes.pop();
+            },
+            // like the interpreter, indices are checked once the value is known
+            ast::StmtKind::Assign { target, value } => {
+                let path = self.path(target);
+                self.expr(value);
+                self.check_indices(&path, 1);
+                match path.char_index {
+                    Some(span) => self.emit_at(Instruction::StoreChar { local: path.local, depth: path.depth }, span),
+                    None => { self.emit(Instruction::Store { local: path.local, depth: path.depth }); },
+                }
+            },
+            ast::StmtKind::Break(id) => {
+                let from = self.jump(Instruction::Jump);
+                self.loop_jumps(*id).2.push(from);
+            },
+            ast::StmtKind::Continue(id) => {
+                let from = self.jump(Instruction::Jump);
+                self.loop_jumps(*id).1.push(from);
+            },
+            ast::StmtKind::Return(value) => {
+                self.expr(value);
+                self.emit(Instruction::Return);
+            },
+            ast::StmtKind::Error => unreachable!("checked programs have no erroneous statements"),
+        }
+    }
+
+    fn loop_jumps(&mut self, id: ast::LoopId) -> &mut (ast::LoopId, Vec<usize>, Vec<usize>) {
+        self.loops.iter_mut().rev()
+            .find(|(loop_id, _, _)| *loop_id == id)
+            .expect("the semantic analysis rejects `break` and `continue` outside loops")
+    }
+
+    fn expr(&mut self, expr: &ast::Expr) {
+        match &expr.kind {
+            ast::ExprKind::Integer(n) => self.constant(Constant::Integer(*n)),
+            ast::ExprKind::Real(x) => self.constant(Constant::Real(*x)),
+            ast::ExprKind::Char(ch) => self.constant(Constant::Char(*ch)),
+            ast::ExprKind::Bool(b) => self.constant(Constant::Boolean(*b)),
+            ast::ExprKind::String(s) => self.constant(Constant::String(s.clone())),
+            ast::ExprKind::LValue(lvalue) => {
+                let path = self.path(lvalue);
+                self.check_indices(&path, 0);
+                match path.char_index {
+                    Some(span) => self.emit_at(Instruction::LoadChar { local: path.local, depth: path.depth }, span),
+                    None => { self.emit(Instruction::Load { local: path.local, depth: path.depth }); },
+                }
+            },
+            // `a && b` is `a ? b : false` and `a || b` is `a ? true : b`
+            ast::ExprKind::Binary { op: ast::BinaryOp::And, left, right } => {
+                self.expr(left);
+                let to_false = self.jump(Instruction::JumpIfFalse);
+                self.expr(right);
+                let to_end = self.jump(Instruction::Jump);
+                self.patch(to_false);
+                self.constant(Constant::Boolean(false));
+                self.patch(to_end);
+            },
+            ast::ExprKind::Binary { op: ast::BinaryOp::Or, left, right } => {
+                self.expr(left);
+                let to_right = self.jump(Instruction::JumpIfFalse);
+                self.constant(Constant::Boolean(true));
+                let to_end = self.jump(Instruction::Jump);
+                self.patch(to_right);
+                self.expr(right);
+                self.patch(to_end);
+            },
+            ast::ExprKind::Binary { op, left, right } => {
+                self.expr(left);
+                self.expr(right);
+                let instruction = match op {
+                    ast::BinaryOp::Add => Instruction::Add,
+                    ast::BinaryOp::Sub => Instruction::Sub,
+                    ast::BinaryOp::Mul => Instruction::Mul,
+                    ast::BinaryOp::Div => Instruction::Div,
+                    ast::BinaryOp::Less => Instruction::Less,
+                    ast::BinaryOp::Greater => Instruction::Greater,
+                    ast::BinaryOp::LessEqual => Instruction::LessEqual,
+                    ast::BinaryOp::GreaterEqual => Instruction::GreaterEqual,
+                    ast::BinaryOp::Equal => Instruction::Equal,
+                    ast::BinaryOp::NotEqual => Instruction::NotEqual,
+                    ast::BinaryOp::And | ast::BinaryOp::Or => unreachable!(),
+                };
+                // only a division can fail
+                if instruction == Instruction::Div {
+                    self.emit_at(instruction, expr.span);
+                } else {
+                    self.emit(instruction);
+                }
+            },
+            ast::ExprKind::Unary { op, operand } => {
+                self.expr(operand);
+                self.emit(match op {
+                    ast::UnaryOp::Negate => Instruction::Negate,
+                    ast::UnaryOp::Not => Instruction::Not,
+                });
+            },
+            // converting to the type the operand already has changes nothing
+            ast::ExprKind::Convert(operand) if operand.type_ == expr.type_ => self.expr(operand),
+            ast::ExprKind::Convert(operand) => {
+                self.expr(operand);
+                self.emit(if expr.type_ == Type::Real_ { Instruction::ToReal } else { Instruction::ToInteger });
+            },
+            ast::ExprKind::IncDec { op, target } => {
+                let path = self.path(target);
+                self.check_indices(&path, 0);
+                let (delta, prefix) = match op {
+                    ast::IncDecOp::PreIncrement => (1, true),
+                    ast::IncDecOp::PreDecrement => (-1, true),
+                    ast::IncDecOp::PostIncrement => (1, false),
+                    ast::IncDecOp::PostDecrement => (-1, false),
+                };
+                self.emit(Instruction::IncDec { local: path.local, depth: path.depth, delta, prefix });
+            },
+            ast::ExprKind::Call { function, args } => {
+                for arg in args.iter() {
+                    self.expr(arg);
+                }
+                let index = self.functions[&function.name];
+                self.emit_at(Instruction::Call(index), expr.span);
+            },
+        }
+    }
+
+    // Pushes the keys of a left value, the indices among them are checked by `check_indices`
+    fn path(&mut self, lvalue: &ast::LValue) -> Path {
+        match &lvalue.kind {
+            ast::LValueKind::Variable(name) => Path { local: self.lookup(&name.name), depth: 0, checks: vec![], char_index: None },
+            ast::LValueKind::Field { base, field } => {
+                let mut path = self.path(base);
+                let index = field_index(&base.type_, &field.name);
+                self.constant(Constant::Integer(index as i32));
+                path.depth += 1;
+                path
+            },
+            ast::LValueKind::Index { base, index } => {
+                let mut path = self.path(base);
+                self.expr(index);
+                match array_length(&base.type_) {
+                    Some(length) => path.checks.push((path.depth, length, index.span)),
+                    None => path.char_index = Some(index.span),
+                }
+                path.depth += 1;
+                path
+            },
+        }
+    }
+
+    // Checks the indices of `path` in order, `above` values were pushed after its keys
+    fn check_indices(&mut self, path: &Path, above: u8) {
+        for (key, length, span) in path.checks.iter() {
+            self.emit_at(Instruction::CheckIndex { length: *length, depth: path.depth - 1 - key + above }, *span);
+        }
+    }
+}
+
+// A left value whose keys are on the stack
+struct Path {
+    local: u16,
+    depth: u8,
+    // the key each index is, the length of the array it indexes and where the index is written
+    checks: Vec<(u8, u32, Span)>,
+    // where the index is written if the last key is a character of a string, checked by LoadChar or StoreChar
+    char_index: Option<Span>,
+}
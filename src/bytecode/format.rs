@@ -0,0 +1,242 @@
+use std::fmt;
+
+use crate::syntatic_analyzer::nonterminals::{Kind, Object, Type};
+use crate::syntatic_analyzer::span::Span;
+
+/*
+ * Bytecode of a program, as the VM runs it and as .ssbc files store it.
+ *
+ * Every function has numbered local slots, its parameters first. Values live in the slots
+ * and on the operand stack, aggregates included, so assignments copy them. Parts of an array
+ * or a struct are reached through a path of keys popped from the stack: the index of an
+ * element or the position of a field in its struct layout.
+ */
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Constant {
+    Integer(i32),
+    Real(f64),
+    Char(char),
+    Boolean(bool),
+    String(String),
+}
+
+// How the values of a type are made up, types refer to each other by their index in Module::types
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Layout {
+    Integer,
+    Real,
+    Char,
+    Boolean,
+    String,
+    Array { length: u32, element: u16 },
+    // the name the type was declared with, and the fields in declaration order
+    Struct { name: String, fields: Vec<(String, u16)> },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    // push a constant of the pool
+    Const(u16),
+    // push the value found by following `depth` keys from a local, the last key on top
+    Load { local: u16, depth: u8 },
+    // pop a value, then `depth` keys, and store the value where they lead from the local
+    Store { local: u16, depth: u8 },
+    // add `delta` to the integer the keys lead to, pushing the new value if `prefix` and the old one if not
+    IncDec { local: u16, depth: u8, delta: i8, prefix: bool },
+    // stop with an error unless the integer `depth` values below the top of the stack is an index of an array of `length` elements
+    CheckIndex { length: u32, depth: u8 },
+    // like Load and Store, with the last key an index of a character of the string the others lead to
+    LoadChar { local: u16, depth: u8 },
+    StoreChar { local: u16, depth: u8 },
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Negate,
+    Not,
+    Less,
+    Greater,
+    LessEqual,
+    GreaterEqual,
+    Equal,
+    NotEqual,
+    ToInteger,
+    ToReal,
+    // jumps are relative to the instruction after them
+    Jump(i32),
+    JumpIfFalse(i32),
+    // pop the arguments, the last one on top, and run the function with that index
+    Call(u16),
+    Return,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Function {
+    pub name: String,
+    pub n_params: u16,
+    // the type of every local slot, parameters first
+    pub locals: Vec<u16>,
+    // the name of every local slot, for the disassembler
+    pub local_names: Vec<String>,
+    pub return_type: u16,
+    pub code: Vec<Instruction>,
+    // where the instructions that can fail come from, ordered by instruction
+    pub spans: Vec<(u32, Span)>,
+}
+
+impl Function {
+    // Where the instruction at `pc` comes from, if it can fail
+    pub fn span(&self, pc: usize) -> Option<Span> {
+        self.spans.binary_search_by_key(&(pc as u32), |(at, _)| *at).ok().map(|i| self.spans[i].1)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Module {
+    pub constants: Vec<Constant>,
+    pub types: Vec<Layout>,
+    pub functions: Vec<Function>,
+    // the program the module was compiled from, so runtime errors can show it
+    pub source: String,
+}
+
+impl Module {
+    pub fn function(&self, name: &str) -> Option<usize> {
+        self.functions.iter().position(|function| function.name == name)
+    }
+
+    // The checker's view of a type of the module, to check values against it
+    pub fn type_(&self, index: u16) -> Type {
+        match &self.types[index as usize] {
+            Layout::Integer => Type::Int_,
+            Layout::Real => Type::Real_,
+            Layout::Char => Type::Char_,
+            Layout::Boolean => Type::Bool_,
+            Layout::String => Type::String_,
+            Layout::Array { length, element } => Type::Array_type_(*length, Box::new(self.type_(*element))),
+            Layout::Struct { fields, .. } => Type::Struct_type_(Box::new(fields.iter()
+                .map(|(name, type_)| Object(name.clone(), Kind::field(self.type_(*type_))))
+                .collect())),
+        }
+    }
+}
+
+impl fmt::Display for Constant {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Constant::Integer(n) => write!(f, "{}", n),
+            Constant::Real(x) => write!(f, "{:?}", x),
+            Constant::Char(ch) => write!(f, "{:?}", ch),
+            Constant::Boolean(b) => write!(f, "{}", b),
+            Constant::String(s) => write!(f, "{:?}", s),
+        }
+    }
+}
+
+impl fmt::Display for Layout {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Layout::Integer => write!(f, "integer"),
+            Layout::Real => write!(f, "real"),
+            Layout::Char => write!(f, "char"),
+            Layout::Boolean => write!(f, "boolean"),
+            Layout::String => write!(f, "string"),
+            Layout::Array { length, element } => write!(f, "array[{}] of %{}", length, element),
+            Layout::Struct { name, fields } => {
+                write!(f, "struct {} {{ ", name)?;
+                for (i, (field, type_)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "; ")?;
+                    }
+                    write!(f, "{} : %{}", field, type_)?;
+                }
+                write!(f, " }}")
+            },
+        }
+    }
+}
+
+/*
+ * The disassembly of a module:
+ *
+ * constants:
+ *     #0 = 1
+ * types:
+ *     %0 = integer
+ * function f(n : %0) : %0
+ *     local x : %0
+ *     0000  load n
+ *     0001  const #0              ; 1
+ *     0002  add
+ *     0003  store x
+ */
+impl fmt::Display for Module {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "constants:")?;
+        for (i, constant) in self.constants.iter().enumerate() {
+            writeln!(f, "    #{} = {}", i, constant)?;
+        }
+        writeln!(f, "types:")?;
+        for (i, layout) in self.types.iter().enumerate() {
+            writeln!(f, "    %{} = {}", i, layout)?;
+        }
+        for function in self.functions.iter() {
+            let params: Vec<String> = (0..function.n_params as usize)
+                .map(|i| format!("{} : %{}", function.local_names[i], function.locals[i]))
+                .collect();
+            writeln!(f, "function {}({}) : %{}", function.name, params.join(", "), function.return_type)?;
+            for i in function.n_params as usize..function.locals.len() {
+                writeln!(f, "    local {} : %{}", function.local_names[i], function.locals[i])?;
+            }
+            for (pc, instruction) in function.code.iter().enumerate() {
+                let text = self.instruction(function, pc, instruction);
+                writeln!(f, "    {:04}  {}", pc, text.trim_end())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Module {
+    fn instruction(&self, function: &Function, pc: usize, instruction: &Instruction) -> String {
+        let local = |local: &u16, depth: &u8| {
+            let name = &function.local_names[*local as usize];
+            if *depth == 0 { name.clone() } else { format!("{} {}", name, depth) }
+        };
+        let jump = |name: &str, offset: &i32| {
+            let target = pc as i64 + 1 + i64::from(*offset);
+            format!("{:<20}; to {:04}", format!("{} {:+}", name, offset), target)
+        };
+        match instruction {
+            Instruction::Const(index) => format!("{:<20}; {}", format!("const #{}", index), self.constants[*index as usize]),
+            Instruction::Load { local: slot, depth } => format!("load {}", local(slot, depth)),
+            Instruction::Store { local: slot, depth } => format!("store {}", local(slot, depth)),
+            Instruction::IncDec { local: slot, depth, delta, prefix } => {
+                format!("{} {} {:+}", if *prefix { "inc_pre" } else { "inc_post" }, local(slot, depth), delta)
+            },
+            Instruction::CheckIndex { length, depth: 0 } => format!("check_index {}", length),
+            Instruction::CheckIndex { length, depth } => format!("check_index {} {}", length, depth),
+            Instruction::LoadChar { local: slot, depth } => format!("load_char {}", local(slot, depth)),
+            Instruction::StoreChar { local: slot, depth } => format!("store_char {}", local(slot, depth)),
+            Instruction::Add => String::from("add"),
+            Instruction::Sub => String::from("sub"),
+            Instruction::Mul => String::from("mul"),
+            Instruction::Div => String::from("div"),
+            Instruction::Negate => String::from("negate"),
+            Instruction::Not => String::from("not"),
+            Instruction::Less => String::from("less"),
+            Instruction::Greater => String::from("greater"),
+            Instruction::LessEqual => String::from("less_equal"),
+            Instruction::GreaterEqual => String::from("greater_equal"),
+            Instruction::Equal => String::from("equal"),
+            Instruction::NotEqual => String::from("not_equal"),
+            Instruction::ToInteger => String::from("to_integer"),
+            Instruction::ToReal => String::from("to_real"),
+            Instruction::Jump(offset) => jump("jump", offset),
+            Instruction::JumpIfFalse(offset) => jump("jump_if_false", offset),
+            Instruction::Call(index) => format!("call {}", self.functions[*index as usize].name),
+            Instruction::Return => String::from("return"),
+        }
+    }
+}
@@ -0,0 +1,337 @@
+use crate::bytecode::format::{Constant, Function, Instruction, Layout, Module};
+use crate::intermediate_code::layout;
+use crate::syntatic_analyzer::span::{Position, Span};
+
+/*
+ * The .ssbc file of a module, all numbers little endian:
+ *
+ * "SSBC" version:u16 source:str
+ * constants:u32 (tag:u8 value)*       0 integer:i32, 1 real:f64, 2 char:u32, 3 boolean:u8, 4 string:str
+ * types:u32 (tag:u8 layout)*          0-4 the scalars, 5 array length:u32 element:u16,
+ *                                     6 struct name:str fields:u32 (name:str type:u16)*
+ * functions:u32 (name:str params:u16 locals:u32 (type:u16 name:str)* return:u16
+ *                code:u32 (opcode:u8 operands)* spans:u32 (pc:u32 span)*)*
+ *
+ * A str is its length as a u32 followed by its UTF-8 bytes, a span is the offset, line and
+ * column of its start and of its end as u32s.
+ */
+
+pub const MAGIC: &[u8; 4] = b"SSBC";
+pub const VERSION: u16 = 1;
+
+// Types nested deeper than this would make the VM recurse too deep building and comparing their values
+const MAX_TYPE_DEPTH: u32 = 256;
+
+impl Module {
+    pub fn to_ssbc(&self) -> Vec<u8> {
+        let mut out = Writer { bytes: MAGIC.to_vec() };
+        out.u16(VERSION);
+        out.str(&self.source);
+
+        out.u32(self.constants.len() as u32);
+        for constant in self.constants.iter() {
+            match constant {
+                Constant::Integer(n) => { out.u8(0); out.u32(*n as u32); },
+                Constant::Real(x) => { out.u8(1); out.bytes.extend(x.to_bits().to_le_bytes()); },
+                Constant::Char(ch) => { out.u8(2); out.u32(u32::from(*ch)); },
+                Constant::Boolean(b) => { out.u8(3); out.u8(u8::from(*b)); },
+                Constant::String(s) => { out.u8(4); out.str(s); },
+            }
+        }
+
+        out.u32(self.types.len() as u32);
+        for layout in self.types.iter() {
+            match layout {
+                Layout::Integer => out.u8(0),
+                Layout::Real => out.u8(1),
+                Layout::Char => out.u8(2),
+                Layout::Boolean => out.u8(3),
+                Layout::String => out.u8(4),
+                Layout::Array { length, element } => { out.u8(5); out.u32(*length); out.u16(*element); },
+                Layout::Struct { name, fields } => {
+                    out.u8(6);
+                    out.str(name);
+                    out.u32(fields.len() as u32);
+                    for (field, type_) in fields.iter() {
+                        out.str(field);
+                        out.u16(*type_);
+                    }
+                },
+            }
+        }
+
+        out.u32(self.functions.len() as u32);
+        for function in self.functions.iter() {
+            out.str(&function.name);
+            out.u16(function.n_params);
+            out.u32(function.locals.len() as u32);
+            for (type_, name) in function.locals.iter().zip(function.local_names.iter()) {
+                out.u16(*type_);
+                out.str(name);
+            }
+            out.u16(function.return_type);
+            out.u32(function.code.len() as u32);
+            for instruction in function.code.iter() {
+                out.instruction(instruction);
+            }
+            out.u32(function.spans.len() as u32);
+            for (pc, span) in function.spans.iter() {
+                out.u32(*pc);
+                for position in [span.start, span.end] {
+                    out.u32(position.offset as u32);
+                    out.u32(position.line);
+                    out.u32(position.column);
+                }
+            }
+        }
+        out.bytes
+    }
+
+    // Reads a module back, checking that everything in it refers to something that exists
+    pub fn from_ssbc(bytes: &[u8]) -> Result<Module, String> {
+        let mut input = Reader { bytes, at: 0 };
+        if input.take(4).ok() != Some(&MAGIC[..]) {
+            return Err(String::from("not a Simplescript bytecode file"));
+        }
+        let version = input.u16()?;
+        if version != VERSION {
+            return Err(format!("unsupported bytecode version {}, expected {}", version, VERSION));
+        }
+        let source = input.str()?;
+
+        let mut constants = vec![];
+        for _ in 0..input.u32()? {
+            constants.push(match input.u8()? {
+                0 => Constant::Integer(input.u32()? as i32),
+                1 => Constant::Real(f64::from_bits(u64::from_le_bytes(input.take(8)?.try_into().unwrap()))),
+                2 => Constant::Char(char::from_u32(input.u32()?).ok_or("invalid char constant")?),
+                3 => Constant::Boolean(input.u8()? != 0),
+                4 => Constant::String(input.str()?),
+                tag => return Err(format!("invalid constant tag {}", tag)),
+            });
+        }
+
+        let mut types = vec![];
+        for _ in 0..input.u32()? {
+            // a layout only refers to the ones before it, so types can't contain themselves
+            let known = types.len();
+            let type_index = |index: u16| if (index as usize) < known { Ok(index) } else { Err(format!("invalid type %{}", index)) };
+            types.push(match input.u8()? {
+                0 => Layout::Integer,
+                1 => Layout::Real,
+                2 => Layout::Char,
+                3 => Layout::Boolean,
+                4 => Layout::String,
+                5 => Layout::Array { length: input.u32()?, element: type_index(input.u16()?)? },
+                6 => {
+                    let name = input.str()?;
+                    let mut fields = vec![];
+                    for _ in 0..input.u32()? {
+                        fields.push((input.str()?, type_index(input.u16()?)?));
+                    }
+                    Layout::Struct { name, fields }
+                },
+                tag => return Err(format!("invalid type tag {}", tag)),
+            });
+        }
+
+        let mut functions = vec![];
+        for _ in 0..input.u32()? {
+            let name = input.str()?;
+            let n_params = input.u16()?;
+            let mut locals = vec![];
+            let mut local_names = vec![];
+            for _ in 0..input.u32()? {
+                locals.push(input.u16()?);
+                local_names.push(input.str()?);
+            }
+            let return_type = input.u16()?;
+            let mut code = vec![];
+            for _ in 0..input.u32()? {
+                code.push(input.instruction()?);
+            }
+            let mut spans = vec![];
+            for _ in 0..input.u32()? {
+                let pc = input.u32()?;
+                let start = input.position()?;
+                let end = input.position()?;
+                spans.push((pc, Span::new(start, end)));
+            }
+            functions.push(Function { name, n_params, locals, local_names, return_type, code, spans });
+        }
+        if input.at != bytes.len() {
+            return Err(String::from("unexpected bytes after the last function"));
+        }
+
+        let module = Module { constants, types, functions, source };
+        module.validate()?;
+        Ok(module)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        // the size and depth of every type, the values of a type are no larger than a compiled program's can be
+        let mut sizes: Vec<(u32, u32)> = vec![];
+        for (index, type_) in self.types.iter().enumerate() {
+            let (size, depth) = match type_ {
+                Layout::Array { length, element } => {
+                    let (size, depth) = sizes[*element as usize];
+                    (length.checked_mul(size), depth + 1)
+                },
+                Layout::Struct { fields, .. } => fields.iter().fold((Some(0), 1), |(size, depth), (_, type_)| {
+                    let (field_size, field_depth) = sizes[*type_ as usize];
+                    (size.and_then(|size: u32| size.checked_add(field_size)), depth.max(field_depth + 1))
+                }),
+                _ => (Some(layout::size_of(&self.type_(index as u16))), 1),
+            };
+            match size.filter(|size| *size <= layout::MAX_SIZE) {
+                Some(size) if depth <= MAX_TYPE_DEPTH => sizes.push((size, depth)),
+                Some(_) => return Err(format!("type %{} is nested more than {} deep", index, MAX_TYPE_DEPTH)),
+                None => return Err(format!("the values of type %{} take more than {} bytes", index, layout::MAX_SIZE)),
+            }
+        }
+
+        let check_type = |type_: u16| if (type_ as usize) < self.types.len() { Ok(()) } else { Err(format!("invalid type %{}", type_)) };
+        for function in self.functions.iter() {
+            let context = |message: String| format!("in function `{}`: {}", function.name, message);
+            if function.n_params as usize > function.locals.len() {
+                return Err(context(String::from("more parameters than locals")));
+            }
+            function.locals.iter().chain([&function.return_type]).try_for_each(|type_| check_type(*type_)).map_err(context)?;
+
+            for (pc, instruction) in function.code.iter().enumerate() {
+                let valid = match instruction {
+                    Instruction::Const(index) => (*index as usize) < self.constants.len(),
+                    Instruction::Load { local, .. } | Instruction::Store { local, .. } | Instruction::IncDec { local, .. } |
+                    Instruction::LoadChar { local, .. } | Instruction::StoreChar { local, .. } => {
+                        (*local as usize) < function.locals.len()
+                    },
+                    Instruction::Call(index) => (*index as usize) < self.functions.len(),
+                    // a jump may land right after the last instruction, which a checked program never reaches
+                    Instruction::Jump(offset) | Instruction::JumpIfFalse(offset) => {
+                        let target = pc as i64 + 1 + i64::from(*offset);
+                        (0..=function.code.len() as i64).contains(&target)
+                    },
+                    _ => true,
+                };
+                if !valid {
+                    return Err(context(format!("instruction {:04} refers to nothing", pc)));
+                }
+            }
+            let ordered = function.spans.windows(2).all(|spans| spans[0].0 < spans[1].0);
+            if !ordered || function.spans.last().is_some_and(|(pc, _)| *pc as usize >= function.code.len()) {
+                return Err(context(String::from("the spans are out of order")));
+            }
+        }
+        Ok(())
+    }
+}
+
+struct Writer {
+    bytes: Vec<u8>,
+}
+
+impl Writer {
+    fn u8(&mut self, n: u8) {
+        self.bytes.push(n);
+    }
+
+    fn u16(&mut self, n: u16) {
+        self.bytes.extend(n.to_le_bytes());
+    }
+
+    fn u32(&mut self, n: u32) {
+        self.bytes.extend(n.to_le_bytes());
+    }
+
+    fn str(&mut self, s: &str) {
+        self.u32(s.len() as u32);
+        self.bytes.extend(s.as_bytes());
+    }
+
+    fn instruction(&mut self, instruction: &Instruction) {
+        match instruction {
+            Instruction::Const(index) => { self.u8(0); self.u16(*index); },
+            Instruction::Load { local, depth } => { self.u8(1); self.u16(*local); self.u8(*depth); },
+            Instruction::Store { local, depth } => { self.u8(2); self.u16(*local); self.u8(*depth); },
+            Instruction::IncDec { local, depth, delta, prefix } => {
+                self.u8(3);
+                self.u16(*local);
+                self.u8(*depth);
+                self.u8(*delta as u8);
+                self.u8(u8::from(*prefix));
+            },
+            Instruction::CheckIndex { length, depth } => { self.u8(4); self.u32(*length); self.u8(*depth); },
+            Instruction::Jump(offset) => { self.u8(20); self.u32(*offset as u32); },
+            Instruction::JumpIfFalse(offset) => { self.u8(21); self.u32(*offset as u32); },
+            Instruction::Call(index) => { self.u8(22); self.u16(*index); },
+            Instruction::LoadChar { local, depth } => { self.u8(23); self.u16(*local); self.u8(*depth); },
+            Instruction::StoreChar { local, depth } => { self.u8(24); self.u16(*local); self.u8(*depth); },
+            instruction => {
+                let opcode = SIMPLE.iter().position(|simple| simple == instruction).unwrap();
+                self.u8(SIMPLE_OPCODE + opcode as u8);
+            },
+        }
+    }
+}
+
+// The instructions without operands, numbered from SIMPLE_OPCODE in this order, up to 19
+const SIMPLE_OPCODE: u8 = 5;
+const SIMPLE: [Instruction; 15] = [
+    Instruction::Add, Instruction::Sub, Instruction::Mul, Instruction::Div, Instruction::Negate, Instruction::Not,
+    Instruction::Less, Instruction::Greater, Instruction::LessEqual, Instruction::GreaterEqual, Instruction::Equal,
+    Instruction::NotEqual, Instruction::ToInteger, Instruction::ToReal, Instruction::Return,
+];
+
+struct Reader<'b> {
+    bytes: &'b [u8],
+    at: usize,
+}
+
+impl<'b> Reader<'b> {
+    fn take(&mut self, n: usize) -> Result<&'b [u8], String> {
+        let bytes = self.bytes.get(self.at..self.at + n).ok_or("the file is truncated")?;
+        self.at += n;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, String> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn str(&mut self) -> Result<String, String> {
+        let length = self.u32()? as usize;
+        let bytes = self.take(length)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| String::from("a string is not valid UTF-8"))
+    }
+
+    fn position(&mut self) -> Result<Position, String> {
+        Ok(Position { offset: self.u32()? as usize, line: self.u32()?, column: self.u32()? })
+    }
+
+    fn instruction(&mut self) -> Result<Instruction, String> {
+        let instruction = match self.u8()? {
+            0 => Instruction::Const(self.u16()?),
+            1 => Instruction::Load { local: self.u16()?, depth: self.u8()? },
+            2 => Instruction::Store { local: self.u16()?, depth: self.u8()? },
+            3 => Instruction::IncDec { local: self.u16()?, depth: self.u8()?, delta: self.u8()? as i8, prefix: self.u8()? != 0 },
+            4 => Instruction::CheckIndex { length: self.u32()?, depth: self.u8()? },
+            20 => Instruction::Jump(self.u32()? as i32),
+            21 => Instruction::JumpIfFalse(self.u32()? as i32),
+            22 => Instruction::Call(self.u16()?),
+            23 => Instruction::LoadChar { local: self.u16()?, depth: self.u8()? },
+            24 => Instruction::StoreChar { local: self.u16()?, depth: self.u8()? },
+            opcode => *opcode.checked_sub(SIMPLE_OPCODE)
+                .and_then(|index| SIMPLE.get(index as usize))
+                .ok_or_else(|| format!("invalid opcode {}", opcode))?,
+        };
+        Ok(instruction)
+    }
+}
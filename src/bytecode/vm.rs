@@ -0,0 +1,310 @@
+use crate::bytecode::format::{Constant, Instruction, Layout, Module};
+use crate::interpreter::{runtime_error, Value, MAX_CALL_DEPTH};
+use crate::syntatic_analyzer::diagnostics::{Diagnostic, ErrorCode};
+use crate::syntatic_analyzer::span::Span;
+
+/*
+ * Stack machine running the functions of a module.
+ *
+ * Every call pushes a frame holding where its locals start in `locals` and the instruction
+ * it is at, the operand stack is shared by all frames. The VM runs the same program the
+ * interpreter does with the same results, errors included.
+ *
+ * A module read from a .ssbc file may have been corrupted, so an instruction finding what a
+ * compiled program never leaves for it stops the VM with an error instead of a panic.
+ */
+
+struct Frame {
+    function: usize,
+    pc: usize,
+    // index of the frame's first local in Vm::locals
+    base: usize,
+}
+
+struct Vm<'m> {
+    module: &'m Module,
+    stack: Vec<Value>,
+    locals: Vec<Value>,
+    frames: Vec<Frame>,
+}
+
+// Calls the function with index `function`, `args` must match its parameters
+pub fn run(module: &Module, function: usize, args: Vec<Value>) -> Result<Value, Box<Diagnostic>> {
+    let mut vm = Vm { module, stack: args, locals: vec![], frames: vec![] };
+    vm.call(function)?;
+    vm.run()
+}
+
+pub fn zero(module: &Module, layout: u16) -> Value {
+    match &module.types[layout as usize] {
+        Layout::Integer => Value::Integer(0),
+        Layout::Real => Value::Real(0.0),
+        Layout::Char => Value::Char('\0'),
+        Layout::Boolean => Value::Boolean(false),
+        Layout::String => Value::String(String::new()),
+        Layout::Array { length, element } => Value::Array(vec![zero(module, *element); *length as usize]),
+        Layout::Struct { fields, .. } => Value::Struct(fields.iter().map(|(name, type_)| (name.clone(), zero(module, *type_))).collect()),
+    }
+}
+
+impl<'m> Vm<'m> {
+    // Moves the arguments on top of the stack into the locals of a new frame
+    fn call(&mut self, index: usize) -> Result<(), Box<Diagnostic>> {
+        let function = &self.module.functions[index];
+        let base = self.locals.len();
+        let args = self.stack.len().checked_sub(function.n_params as usize)
+            .ok_or_else(|| self.invalid(format!("`{}` is called with fewer than {} arguments", function.name, function.n_params)))?;
+        self.locals.extend(self.stack.drain(args..));
+        for layout in function.locals[function.n_params as usize..].iter() {
+            self.locals.push(zero(self.module, *layout));
+        }
+        self.frames.push(Frame { function: index, pc: 0, base });
+        Ok(())
+    }
+
+    fn invalid(&self, message: String) -> Box<Diagnostic> {
+        match self.frames.last() {
+            Some(_) => self.error(ErrorCode::InvalidBytecode, format!("invalid bytecode: {}", message)),
+            None => Box::new(Diagnostic::error(ErrorCode::InvalidBytecode, format!("invalid bytecode: {}", message), Span::default())),
+        }
+    }
+
+    fn error(&self, code: ErrorCode, message: String) -> Box<Diagnostic> {
+        // the frames below the current one are all stopped at the call that made the next frame
+        let calls: Vec<(&str, Span)> = self.frames.windows(2)
+            .map(|frames| {
+                let caller = &self.module.functions[frames[0].function];
+                (self.module.functions[frames[1].function].name.as_str(), caller.span(frames[0].pc - 1).unwrap_or_default())
+            })
+            .collect();
+        let frame = self.frames.last().unwrap();
+        let span = self.module.functions[frame.function].span(frame.pc - 1).unwrap_or_default();
+        runtime_error(&calls, code, message, span)
+    }
+
+    fn pop(&mut self) -> Result<Value, Box<Diagnostic>> {
+        self.stack.pop().ok_or_else(|| self.invalid(String::from("the operand stack is empty")))
+    }
+
+    // The part of a local reached through the `depth` keys on top of the stack
+    fn place(&mut self, local: u16, depth: u8) -> Result<&mut Value, Box<Diagnostic>> {
+        let keys = self.stack.len().checked_sub(depth as usize)
+            .ok_or_else(|| self.invalid(format!("fewer than {} keys on the operand stack", depth)))?;
+        let keys = self.stack.drain(keys..)
+            .map(|key| match key {
+                Value::Integer(key) => usize::try_from(key).map_err(|_| key.to_string()),
+                value => Err(value.to_string()),
+            })
+            .collect::<Result<Vec<usize>, String>>()
+            .map_err(|key| self.invalid(format!("{} is not a key", key)))?;
+        let local = self.frames.last().unwrap().base + local as usize;
+
+        // the keys are checked first, an error can't be made while a part of the locals is borrowed
+        let mut part = &self.locals[local];
+        for key in keys.iter() {
+            part = match part {
+                Value::Array(elements) => elements.get(*key),
+                Value::Struct(fields) => fields.get(*key).map(|(_, value)| value),
+                _ => None,
+            }.ok_or_else(|| self.invalid(format!("{} has no part {}", part, key)))?;
+        }
+        let mut place = &mut self.locals[local];
+        for key in keys {
+            place = match place {
+                Value::Array(elements) => &mut elements[key],
+                Value::Struct(fields) => &mut fields[key].1,
+                _ => unreachable!("the keys were checked"),
+            };
+        }
+        Ok(place)
+    }
+
+    /*
+     * The character the `depth` keys on top of the stack lead to, the last one an index into the string the
+     * others reach. It is replaced by `new` if there is one.
+     */
+    fn char_at(&mut self, local: u16, depth: u8, new: Option<char>) -> Result<char, Box<Diagnostic>> {
+        let index = match self.pop()? {
+            Value::Integer(index) => index,
+            value => return Err(self.invalid(format!("{} is not a key", value))),
+        };
+        let depth = depth.checked_sub(1).ok_or_else(|| self.invalid(String::from("no key leads to a string")))?;
+        // the errors are made once the local is no longer borrowed
+        let found = match self.place(local, depth)? {
+            Value::String(string) => {
+                let length = string.chars().count();
+                match usize::try_from(index).ok().and_then(|index| string.char_indices().nth(index)) {
+                    Some((start, old)) => {
+                        if let Some(new) = new {
+                            string.replace_range(start..start + old.len_utf8(), new.encode_utf8(&mut [0; 4]));
+                        }
+                        Ok(old)
+                    },
+                    None => Err((ErrorCode::IndexOutOfBounds, format!("index {} is out of bounds for a string of {} characters", index, length))),
+                }
+            },
+            value => Err((ErrorCode::InvalidBytecode, format!("{} is not a string", value))),
+        };
+        found.map_err(|(code, message)| match code {
+            ErrorCode::IndexOutOfBounds => self.error(code, message),
+            _ => self.invalid(message),
+        })
+    }
+
+    fn run(&mut self) -> Result<Value, Box<Diagnostic>> {
+        loop {
+            let frame = self.frames.last_mut().unwrap();
+            let function = &self.module.functions[frame.function];
+            let Some(&instruction) = function.code.get(frame.pc) else {
+                return Err(self.invalid(format!("the code of `{}` ends without returning", function.name)));
+            };
+            frame.pc += 1;
+
+            match instruction {
+                Instruction::Const(index) => {
+                    let value = match &self.module.constants[index as usize] {
+                        Constant::Integer(n) => Value::Integer(*n),
+                        Constant::Real(x) => Value::Real(*x),
+                        Constant::Char(ch) => Value::Char(*ch),
+                        Constant::Boolean(b) => Value::Boolean(*b),
+                        Constant::String(s) => Value::String(s.clone()),
+                    };
+                    self.stack.push(value);
+                },
+                Instruction::Load { local, depth } => {
+                    let value = self.place(local, depth)?.clone();
+                    self.stack.push(value);
+                },
+                Instruction::Store { local, depth } => {
+                    let value = self.pop()?;
+                    *self.place(local, depth)? = value;
+                },
+                Instruction::IncDec { local, depth, delta, prefix } => {
+                    let place = self.place(local, depth)?;
+                    let Value::Integer(old) = *place else {
+                        let message = format!("`++` or `--` applied to {}", place);
+                        return Err(self.invalid(message));
+                    };
+                    let new = old.wrapping_add(i32::from(delta));
+                    *place = Value::Integer(new);
+                    self.stack.push(Value::Integer(if prefix { new } else { old }));
+                },
+                Instruction::LoadChar { local, depth } => {
+                    let ch = self.char_at(local, depth, None)?;
+                    self.stack.push(Value::Char(ch));
+                },
+                Instruction::StoreChar { local, depth } => {
+                    let new = match self.pop()? {
+                        Value::Char(ch) => ch,
+                        value => return Err(self.invalid(format!("{} stored in a string", value))),
+                    };
+                    self.char_at(local, depth, Some(new))?;
+                },
+                Instruction::CheckIndex { length, depth } => {
+                    let index = match self.stack.len().checked_sub(1 + depth as usize).map(|at| &self.stack[at]) {
+                        Some(Value::Integer(index)) => *index,
+                        _ => return Err(self.invalid(String::from("no integer to check as an index"))),
+                    };
+                    if index < 0 || index as u32 >= length {
+                        return Err(self.error(ErrorCode::IndexOutOfBounds,
+                                              format!("index {} is out of bounds for an array of {} elements", index, length)));
+                    }
+                },
+                Instruction::Add | Instruction::Sub | Instruction::Mul | Instruction::Div |
+                Instruction::Less | Instruction::Greater | Instruction::LessEqual | Instruction::GreaterEqual |
+                Instruction::Equal | Instruction::NotEqual => {
+                    let right = self.pop()?;
+                    let left = self.pop()?;
+                    let value = self.binary(instruction, left, right)?;
+                    self.stack.push(value);
+                },
+                Instruction::Negate => {
+                    let value = match self.pop()? {
+                        Value::Integer(n) => Value::Integer(n.wrapping_neg()),
+                        Value::Real(x) => Value::Real(-x),
+                        value => return Err(self.invalid(format!("negating {}", value))),
+                    };
+                    self.stack.push(value);
+                },
+                Instruction::Not => {
+                    let value = match self.pop()? {
+                        Value::Boolean(b) => Value::Boolean(!b),
+                        value => return Err(self.invalid(format!("`!` applied to {}", value))),
+                    };
+                    self.stack.push(value);
+                },
+                Instruction::ToInteger => {
+                    let value = match self.pop()? {
+                        // rounds towards zero and saturates
+                        Value::Real(x) => Value::Integer(x as i32),
+                        value => value,
+                    };
+                    self.stack.push(value);
+                },
+                Instruction::ToReal => {
+                    let value = match self.pop()? {
+                        Value::Integer(n) => Value::Real(f64::from(n)),
+                        value => value,
+                    };
+                    self.stack.push(value);
+                },
+                Instruction::Jump(offset) => self.jump(offset),
+                Instruction::JumpIfFalse(offset) => {
+                    match self.pop()? {
+                        Value::Boolean(false) => self.jump(offset),
+                        Value::Boolean(true) => (),
+                        value => return Err(self.invalid(format!("condition evaluated to {}", value))),
+                    }
+                },
+                Instruction::Call(index) => {
+                    if self.frames.len() == MAX_CALL_DEPTH + 1 {
+                        return Err(self.error(ErrorCode::StackOverflow,
+                                              format!("stack overflow, calls are nested more than {} deep", MAX_CALL_DEPTH)));
+                    }
+                    self.call(index as usize)?;
+                },
+                Instruction::Return => {
+                    // the value is taken while the frame still exists to locate an error
+                    if self.frames.len() == 1 {
+                        return self.pop();
+                    }
+                    let frame = self.frames.pop().unwrap();
+                    self.locals.truncate(frame.base);
+                },
+            }
+        }
+    }
+
+    fn jump(&mut self, offset: i32) {
+        let frame = self.frames.last_mut().unwrap();
+        frame.pc = (frame.pc as i64 + i64::from(offset)) as usize;
+    }
+
+    fn binary(&self, instruction: Instruction, left: Value, right: Value) -> Result<Value, Box<Diagnostic>> {
+        let value = match (instruction, left, right) {
+            (Instruction::Less, left, right) => Value::Boolean(left < right),
+            (Instruction::Greater, left, right) => Value::Boolean(left > right),
+            (Instruction::LessEqual, left, right) => Value::Boolean(left <= right),
+            (Instruction::GreaterEqual, left, right) => Value::Boolean(left >= right),
+            (Instruction::Equal, left, right) => Value::Boolean(left == right),
+            (Instruction::NotEqual, left, right) => Value::Boolean(left != right),
+
+            (Instruction::Add, Value::Integer(a), Value::Integer(b)) => Value::Integer(a.wrapping_add(b)),
+            (Instruction::Sub, Value::Integer(a), Value::Integer(b)) => Value::Integer(a.wrapping_sub(b)),
+            (Instruction::Mul, Value::Integer(a), Value::Integer(b)) => Value::Integer(a.wrapping_mul(b)),
+            (Instruction::Div, Value::Integer(_), Value::Integer(0)) => {
+                return Err(self.error(ErrorCode::DivisionByZero, String::from("division by zero")));
+            },
+            (Instruction::Div, Value::Integer(a), Value::Integer(b)) => Value::Integer(a.wrapping_div(b)),
+
+            (Instruction::Add, Value::Real(a), Value::Real(b)) => Value::Real(a + b),
+            (Instruction::Sub, Value::Real(a), Value::Real(b)) => Value::Real(a - b),
+            (Instruction::Mul, Value::Real(a), Value::Real(b)) => Value::Real(a * b),
+            (Instruction::Div, Value::Real(a), Value::Real(b)) => Value::Real(a / b),
+
+            (Instruction::Add, Value::String(a), Value::String(b)) => Value::String(a + &b),
+            (instruction, left, right) => return Err(self.invalid(format!("{:?} applied to {} and {}", instruction, left, right))),
+        };
+        Ok(value)
+    }
+}
@@ -0,0 +1,384 @@
+pub mod tac;
+pub mod layout;
+
+use std::collections::HashMap;
+
+use crate::syntatic_analyzer::ast;
+use crate::syntatic_analyzer::nonterminals::Type;
+use tac::{Instruction, Label, Local, Op, Operand, UnaryOp};
+
+/*
+ * Lowers the abstract syntax tree of a program without errors into three address code.
+ *
+ * Conditions are lowered to jumps, so `&&` and `||` only evaluate their right operand when
+ * needed. Variables are renamed to `name.N` when a block declares a name the function has
+ * already used, so every local has a single name in the function.
+ */
+pub fn generate(program: &ast::Program) -> tac::Module {
+    let mut module = tac::Module::default();
+    for declaration in program.declarations.iter() {
+        if let ast::Declaration::Function(function) = declaration {
+            module.functions.push(FunctionGenerator::new().generate(function));
+        }
+    }
+    module
+}
+
+// Where a left value is stored
+#[derive(Clone)]
+enum Place {
+    Var(String),
+    Element { base: String, offset: Operand },
+}
+
+struct FunctionGenerator {
+    body: Vec<Instruction>,
+    locals: Vec<Local>,
+    // source name to unique name, one map per open block
+    scopes: Vec<HashMap<String, String>>,
+    // how many variables with each source name were declared so far
+    declared: HashMap<String, u32>,
+    n_temps: u32,
+    n_labels: u32,
+    // continue and break targets of each loop started so far
+    loops: HashMap<ast::LoopId, (Label, Label)>,
+}
+
+impl FunctionGenerator {
+    fn new() -> Self {
+        FunctionGenerator {
+            body: vec![],
+            locals: vec![],
+            scopes: vec![HashMap::new()],
+            declared: HashMap::new(),
+            n_temps: 0,
+            n_labels: 0,
+            loops: HashMap::new(),
+        }
+    }
+
+    fn generate(mut self, function: &ast::FunctionDecl) -> tac::Function {
+        let mut params = vec![];
+        for param in function.params.iter() {
+            params.push(self.declare(&param.name.name, &param.type_.type_));
+        }
+        // the body shares the scope of the parameters
+        self.declare_vars(&function.body.vars);
+        for stmt in function.body.stmts.iter() {
+            self.stmt(stmt);
+        }
+
+        tac::Function {
+            name: function.name.name.clone(),
+            params,
+            locals: self.locals,
+            return_type: function.return_type.type_.clone(),
+            body: self.body,
+        }
+    }
+
+    fn declare(&mut self, name: &str, type_: &Type) -> Local {
+        let count = self.declared.entry(name.to_string()).or_insert(0);
+        let unique = if *count == 0 { name.to_string() } else { format!("{}.{}", name, count) };
+        *count += 1;
+        self.scopes.last_mut().unwrap().insert(name.to_string(), unique.clone());
+        Local { name: unique, type_: type_.clone(), size: layout::size_of(type_) }
+    }
+
+    fn declare_vars(&mut self, vars: &[ast::VarDecl]) {
+        for var in vars.iter() {
+            for name in var.names.iter() {
+                let local = self.declare(&name.name, &var.type_.type_);
+                self.locals.push(local);
+            }
+        }
+    }
+
+    fn lookup(&self, name: &str) -> String {
+        self.scopes.iter().rev()
+            .find_map(|scope| scope.get(name))
+            .cloned()
+            .unwrap_or_else(|| name.to_string())
+    }
+
+    fn temp(&mut self) -> Operand {
+        self.n_temps += 1;
+        Operand::Temp(self.n_temps - 1)
+    }
+
+    fn label(&mut self) -> Label {
+        self.n_labels += 1;
+        Label(self.n_labels - 1)
+    }
+
+    fn emit(&mut self, instruction: Instruction) {
+        self.body.push(instruction);
+    }
+
+    fn stmt(&mut self, stmt: &ast::Stmt) {
+        match &stmt.kind {
+            ast::StmtKind::If { condition, then_branch, else_branch: None } => {
+                let end = self.label();
+                self.jump_if(condition, false, end);
+                self.stmt(then_branch);
+                self.emit(Instruction::Label(end));
+            },
+            ast::StmtKind::If { condition, then_branch, else_branch: Some(else_branch) } => {
+                let else_label = self.label();
+                let end = self.label();
+                self.jump_if(condition, false, else_label);
+                self.stmt(then_branch);
+                self.emit(Instruction::Jump(end));
+                self.emit(Instruction::Label(else_label));
+                self.stmt(else_branch);
+                self.emit(Instruction::Label(end));
+            },
+            ast::StmtKind::While { id, condition, body } => {
+                let start = self.label();
+                let end = self.label();
+                self.emit(Instruction::Label(start));
+                self.jump_if(condition, false, end);
+                self.loops.insert(*id, (start, end));
+                self.stmt(body);
+                self.emit(Instruction::Jump(start));
+                self.emit(Instruction::Label(end));
+            },
+            ast::StmtKind::DoWhile { id, body, condition } => {
+                let start = self.label();
+                let test = self.label();
+                let end = self.label();
+                self.emit(Instruction::Label(start));
+                self.loops.insert(*id, (test, end));
+                self.stmt(body);
+                self.emit(Instruction::Label(test));
+                self.jump_if(condition, true, start);
+                self.emit(Instruction::Label(end));
+            },
+            ast::StmtKind::Block(block) => {
+                self.scopes.push(HashMap::new());
+                self.declare_vars(&block.vars);
+                for stmt in block.stmts.iter() {
+                    self.stmt(stmt);
+                }
+                self.scopes.pop();
+            },
+            ast::StmtKind::Assign { target, value } => {
+                let place = self.place(target);
+                let value = self.expr(value);
+                self.store(place, value);
+            },
+            ast::StmtKind::Break(id) => {
+                let (_, end) = self.loops[id];
+                self.emit(Instruction::Jump(end));
+            },
+            ast::StmtKind::Continue(id) => {
+                let (start, _) = self.loops[id];
+                self.emit(Instruction::Jump(start));
+            },
+            ast::StmtKind::Return(value) => {
+                let value = self.expr(value);
+                self.emit(Instruction::Return(value));
+            },
+            ast::StmtKind::Error => (),
+        }
+    }
+
+    // Jumps to `target` when `condition` evaluates to `when`, falls through otherwise
+    fn jump_if(&mut self, condition: &ast::Expr, when: bool, target: Label) {
+        match &condition.kind {
+            ast::ExprKind::Binary { op: op @ (ast::BinaryOp::And | ast::BinaryOp::Or), left, right } => {
+                // `a && b` is false as soon as `a` is, `a || b` is true as soon as `a` is
+                let short_circuit = *op == ast::BinaryOp::Or;
+                if when == short_circuit {
+                    self.jump_if(left, when, target);
+                    self.jump_if(right, when, target);
+                } else {
+                    let skip = self.label();
+                    self.jump_if(left, short_circuit, skip);
+                    self.jump_if(right, when, target);
+                    self.emit(Instruction::Label(skip));
+                }
+            },
+            ast::ExprKind::Unary { op: ast::UnaryOp::Not, operand } => self.jump_if(operand, !when, target),
+            ast::ExprKind::Bool(value) => {
+                if *value == when {
+                    self.emit(Instruction::Jump(target));
+                }
+            },
+            _ => {
+                let condition = self.expr(condition);
+                if when {
+                    self.emit(Instruction::JumpIf { condition, target });
+                } else {
+                    self.emit(Instruction::JumpIfNot { condition, target });
+                }
+            }
+        }
+    }
+
+    fn expr(&mut self, expr: &ast::Expr) -> Operand {
+        match &expr.kind {
+            ast::ExprKind::Integer(n) => Operand::Int(*n),
+            ast::ExprKind::Real(x) => Operand::Real(*x),
+            ast::ExprKind::Char(ch) => Operand::Char(*ch),
+            ast::ExprKind::Bool(b) => Operand::Bool(*b),
+            ast::ExprKind::String(s) => Operand::Str(s.clone()),
+            ast::ExprKind::LValue(lvalue) => {
+                let place = self.place(lvalue);
+                self.load(place)
+            },
+            ast::ExprKind::Binary { op: ast::BinaryOp::And | ast::BinaryOp::Or, .. } => {
+                let dest = self.temp();
+                let false_label = self.label();
+                let end = self.label();
+                self.jump_if(expr, false, false_label);
+                self.emit(Instruction::Copy { dest: dest.clone(), src: Operand::Bool(true) });
+                self.emit(Instruction::Jump(end));
+                self.emit(Instruction::Label(false_label));
+                self.emit(Instruction::Copy { dest: dest.clone(), src: Operand::Bool(false) });
+                self.emit(Instruction::Label(end));
+                dest
+            },
+            ast::ExprKind::Binary { op, left, right } => {
+                let op = match op {
+                    ast::BinaryOp::Add if left.type_ == Type::String_ => Op::Concat,
+                    ast::BinaryOp::Add => Op::Add,
+                    ast::BinaryOp::Sub => Op::Sub,
+                    ast::BinaryOp::Mul => Op::Mul,
+                    ast::BinaryOp::Div => Op::Div,
+                    ast::BinaryOp::Less => Op::Less,
+                    ast::BinaryOp::Greater => Op::Greater,
+                    ast::BinaryOp::LessEqual => Op::LessEqual,
+                    ast::BinaryOp::GreaterEqual => Op::GreaterEqual,
+                    ast::BinaryOp::Equal => Op::Equal,
+                    ast::BinaryOp::NotEqual => Op::NotEqual,
+                    ast::BinaryOp::And | ast::BinaryOp::Or => unreachable!(),
+                };
+                let left = self.expr(left);
+                let right = self.expr(right);
+                let dest = self.temp();
+                self.emit(Instruction::Binary { dest: dest.clone(), op, left, right });
+                dest
+            },
+            ast::ExprKind::Unary { op, operand } => {
+                let op = match op {
+                    ast::UnaryOp::Negate => UnaryOp::Negate,
+                    ast::UnaryOp::Not => UnaryOp::Not,
+                };
+                let operand = self.expr(operand);
+                let dest = self.temp();
+                self.emit(Instruction::Unary { dest: dest.clone(), op, operand });
+                dest
+            },
+            // converting to the type the operand already has changes nothing
+            ast::ExprKind::Convert(operand) if operand.type_ == expr.type_ => self.expr(operand),
+            ast::ExprKind::Convert(operand) => {
+                let op = match expr.type_ {
+                    Type::Real_ => UnaryOp::ToReal,
+                    _ => UnaryOp::ToInteger,
+                };
+                let operand = self.expr(operand);
+                let dest = self.temp();
+                self.emit(Instruction::Unary { dest: dest.clone(), op, operand });
+                dest
+            },
+            ast::ExprKind::IncDec { op, target } => {
+                let place = self.place(target);
+                let old = self.load(place.clone());
+                // the old value is kept in a temporary, the variable itself is about to change
+                let old = match old {
+                    Operand::Temp(_) => old,
+                    _ => {
+                        let temp = self.temp();
+                        self.emit(Instruction::Copy { dest: temp.clone(), src: old });
+                        temp
+                    }
+                };
+                let (arith, prefix) = match op {
+                    ast::IncDecOp::PreIncrement => (Op::Add, true),
+                    ast::IncDecOp::PreDecrement => (Op::Sub, true),
+                    ast::IncDecOp::PostIncrement => (Op::Add, false),
+                    ast::IncDecOp::PostDecrement => (Op::Sub, false),
+                };
+                let new = self.temp();
+                self.emit(Instruction::Binary { dest: new.clone(), op: arith, left: old.clone(), right: Operand::Int(1) });
+                self.store(place, new.clone());
+                if prefix { new } else { old }
+            },
+            ast::ExprKind::Call { function, args } => {
+                let args: Vec<Operand> = args.iter().map(|arg| self.expr(arg)).collect();
+                let n_args = args.len();
+                for arg in args {
+                    self.emit(Instruction::Param(arg));
+                }
+                let dest = self.temp();
+                self.emit(Instruction::Call { dest: dest.clone(), function: function.name.clone(), n_args });
+                dest
+            },
+        }
+    }
+
+    fn place(&mut self, lvalue: &ast::LValue) -> Place {
+        match &lvalue.kind {
+            ast::LValueKind::Variable(name) => Place::Var(self.lookup(&name.name)),
+            ast::LValueKind::Field { base, field } => {
+                let offset = layout::field_offset(&base.type_, &field.name).map_or(0, |(offset, _)| offset);
+                let place = self.place(base);
+                self.offset(place, Operand::Int(offset as i32))
+            },
+            ast::LValueKind::Index { base, index } => {
+                let place = self.place(base);
+                let index = self.expr(index);
+                let size = layout::size_of(&lvalue.type_) as i32;
+                let offset = match index {
+                    Operand::Int(n) if n.checked_mul(size).is_some() => Operand::Int(n * size),
+                    _ if size == 1 => index,
+                    _ => {
+                        let dest = self.temp();
+                        self.emit(Instruction::Binary { dest: dest.clone(), op: Op::Mul, left: index, right: Operand::Int(size) });
+                        dest
+                    }
+                };
+                self.offset(place, offset)
+            },
+        }
+    }
+
+    // The place `offset` bytes after `place`
+    fn offset(&mut self, place: Place, offset: Operand) -> Place {
+        match place {
+            Place::Var(base) => Place::Element { base, offset },
+            Place::Element { base, offset: previous } => {
+                let offset = match (previous, offset) {
+                    // an offset out of the value is only an error if the program reaches it
+                    (Operand::Int(a), Operand::Int(b)) if a.checked_add(b).is_some() => Operand::Int(a + b),
+                    (Operand::Int(0), offset) | (offset, Operand::Int(0)) => offset,
+                    (previous, offset) => {
+                        let dest = self.temp();
+                        self.emit(Instruction::Binary { dest: dest.clone(), op: Op::Add, left: previous, right: offset });
+                        dest
+                    }
+                };
+                Place::Element { base, offset }
+            },
+        }
+    }
+
+    fn load(&mut self, place: Place) -> Operand {
+        match place {
+            Place::Var(name) => Operand::Var(name),
+            Place::Element { base, offset } => {
+                let dest = self.temp();
+                self.emit(Instruction::Load { dest: dest.clone(), base, offset });
+                dest
+            },
+        }
+    }
+
+    fn store(&mut self, place: Place, src: Operand) {
+        match place {
+            Place::Var(name) => self.emit(Instruction::Copy { dest: Operand::Var(name), src }),
+            Place::Element { base, offset } => self.emit(Instruction::Store { base, offset, src }),
+        }
+    }
+}
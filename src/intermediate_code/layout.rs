@@ -0,0 +1,54 @@
+use crate::syntatic_analyzer::nonterminals::{Kind, Type};
+
+/*
+ * Memory layout of the values of each type, in bytes. Strings are references to their
+ * characters, so they take the size of a pointer wherever they are stored. Fields are laid
+ * out in declaration order without padding.
+ */
+
+pub const POINTER_SIZE: u32 = 8;
+
+// The largest value a program can have, so every offset inside one fits an integer operand
+pub const MAX_SIZE: u32 = i32::MAX as u32;
+
+pub fn size_of(type_: &Type) -> u32 {
+    checked_size_of(type_).expect("the semantic analysis limits the size of types")
+}
+
+// The size of the values of `type_`, none if it is more than MAX_SIZE
+pub fn checked_size_of(type_: &Type) -> Option<u32> {
+    let size = match type_ {
+        Type::Int_ => 4,
+        Type::Real_ => 8,
+        Type::Char_ | Type::Bool_ => 1,
+        Type::String_ => POINTER_SIZE,
+        Type::Array_type_(n_elements, element) => n_elements.checked_mul(checked_size_of(element)?)?,
+        Type::Alias_type_(type_) => checked_size_of(type_)?,
+        Type::Struct_type_(fields) => fields.iter().try_fold(0u32, |size, field| match &field.1 {
+            Kind::field(type_) => size.checked_add(checked_size_of(type_)?),
+            _ => Some(size)
+        })?,
+        Type::Universal_ => 0,
+    };
+    Some(size).filter(|size| *size <= MAX_SIZE)
+}
+
+// Offset and type of `field` inside a value of the struct type `type_`
+pub fn field_offset(type_: &Type, field: &str) -> Option<(u32, Type)> {
+    match type_ {
+        Type::Alias_type_(type_) => field_offset(type_, field),
+        Type::Struct_type_(fields) => {
+            let mut offset = 0;
+            for object in fields.iter() {
+                if let Kind::field(field_type) = &object.1 {
+                    if object.0 == field {
+                        return Some((offset, field_type.clone()));
+                    }
+                    offset += size_of(field_type);
+                }
+            }
+            None
+        },
+        _ => None
+    }
+}
@@ -0,0 +1,198 @@
+use std::fmt;
+
+use crate::syntatic_analyzer::nonterminals::Type;
+
+/*
+ * Three address code. Every instruction has at most one operator, its operands are
+ * variables, temporaries or constants, and control flow only goes through labels and
+ * jumps. Aggregates (arrays and structs) live in variables and their elements are reached
+ * with byte offsets, see layout.rs.
+ */
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operand {
+    Temp(u32),
+    Var(String),
+    Int(i32),
+    Real(f64),
+    Char(char),
+    Bool(bool),
+    Str(String),
+}
+
+// Real constants come from literals, which are never NaN
+impl Eq for Operand {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Label(pub u32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    // `+` on strings
+    Concat,
+    Less,
+    Greater,
+    LessEqual,
+    GreaterEqual,
+    Equal,
+    NotEqual,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOp {
+    Negate,
+    Not,
+    // conversions between integer and real, towards zero for integer
+    ToInteger,
+    ToReal,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Instruction {
+    // dest = src
+    Copy { dest: Operand, src: Operand },
+    // dest = left op right
+    Binary { dest: Operand, op: Op, left: Operand, right: Operand },
+    // dest = op operand
+    Unary { dest: Operand, op: UnaryOp, operand: Operand },
+    // dest = base[offset], offset in bytes
+    Load { dest: Operand, base: String, offset: Operand },
+    // base[offset] = src
+    Store { base: String, offset: Operand, src: Operand },
+    Label(Label),
+    Jump(Label),
+    JumpIf { condition: Operand, target: Label },
+    JumpIfNot { condition: Operand, target: Label },
+    // arguments are pushed with param right before the call that uses them
+    Param(Operand),
+    Call { dest: Operand, function: String, n_args: usize },
+    Return(Operand),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Local {
+    pub name: String,
+    pub type_: Type,
+    pub size: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Function {
+    pub name: String,
+    pub params: Vec<Local>,
+    // every variable declared in the body, renamed so each name is unique in the function
+    pub locals: Vec<Local>,
+    pub return_type: Type,
+    pub body: Vec<Instruction>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Module {
+    pub functions: Vec<Function>,
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Operand::Temp(n) => write!(f, "t{}", n),
+            Operand::Var(name) => write!(f, "{}", name),
+            Operand::Int(n) => write!(f, "{}", n),
+            Operand::Real(x) => write!(f, "{:?}", x),
+            Operand::Char(ch) => write!(f, "'{}'", ch.escape_default()),
+            Operand::Bool(b) => write!(f, "{}", b),
+            Operand::Str(s) => write!(f, "\"{}\"", s.escape_default()),
+        }
+    }
+}
+
+impl fmt::Display for Label {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "L{}", self.0)
+    }
+}
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let symbol = match self {
+            Op::Add => "+",
+            Op::Sub => "-",
+            Op::Mul => "*",
+            Op::Div => "/",
+            Op::Concat => "++",
+            Op::Less => "<",
+            Op::Greater => ">",
+            Op::LessEqual => "<=",
+            Op::GreaterEqual => ">=",
+            Op::Equal => "==",
+            Op::NotEqual => "!=",
+        };
+        write!(f, "{}", symbol)
+    }
+}
+
+impl fmt::Display for UnaryOp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UnaryOp::Negate => write!(f, "-"),
+            UnaryOp::Not => write!(f, "!"),
+            UnaryOp::ToInteger => write!(f, "(integer) "),
+            UnaryOp::ToReal => write!(f, "(real) "),
+        }
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Instruction::Copy { dest, src } => write!(f, "    {} = {}", dest, src),
+            Instruction::Binary { dest, op, left, right } => write!(f, "    {} = {} {} {}", dest, left, op, right),
+            Instruction::Unary { dest, op, operand } => write!(f, "    {} = {}{}", dest, op, operand),
+            Instruction::Load { dest, base, offset } => write!(f, "    {} = {}[{}]", dest, base, offset),
+            Instruction::Store { base, offset, src } => write!(f, "    {}[{}] = {}", base, offset, src),
+            Instruction::Label(label) => write!(f, "{}:", label),
+            Instruction::Jump(label) => write!(f, "    goto {}", label),
+            Instruction::JumpIf { condition, target } => write!(f, "    if {} goto {}", condition, target),
+            Instruction::JumpIfNot { condition, target } => write!(f, "    ifFalse {} goto {}", condition, target),
+            Instruction::Param(operand) => write!(f, "    param {}", operand),
+            Instruction::Call { dest, function, n_args } => write!(f, "    {} = call {}, {}", dest, function, n_args),
+            Instruction::Return(operand) => write!(f, "    return {}", operand),
+        }
+    }
+}
+
+/*
+ * function f(x : 4) : 4
+ *     local p : 16
+ *     ifFalse x goto L0
+ *     ...
+ * end
+ */
+impl fmt::Display for Function {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let params: Vec<String> = self.params.iter().map(|param| format!("{} : {}", param.name, param.size)).collect();
+        writeln!(f, "function {}({}) : {}", self.name, params.join(", "), super::layout::size_of(&self.return_type))?;
+        for local in self.locals.iter() {
+            writeln!(f, "    local {} : {}", local.name, local.size)?;
+        }
+        for instruction in self.body.iter() {
+            writeln!(f, "{}", instruction)?;
+        }
+        writeln!(f, "end")
+    }
+}
+
+impl fmt::Display for Module {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, function) in self.functions.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", function)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,677 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::syntatic_analyzer::ast;
+use crate::syntatic_analyzer::diagnostics::{Diagnostic, ErrorCode};
+use crate::syntatic_analyzer::nonterminals::{Kind, Type};
+use crate::syntatic_analyzer::span::Span;
+use crate::syntatic_analyzer::terminals::{self, Token};
+use crate::syntatic_analyzer::token_stream::TokenStream;
+
+/*
+ * Tree-walking interpreter for programs without errors.
+ *
+ * Values are copied on assignment and when passed to a function, arrays and structs
+ * included. Variables start out as the zero value of their type: 0, 0.0, '\0', false, the
+ * empty string, and arrays and structs of zero values. Integer arithmetic wraps around on
+ * overflow, dividing an integer by zero or indexing past the end of an array is an error.
+ */
+
+// How deep calls can nest before the program is stopped with a stack overflow
+pub const MAX_CALL_DEPTH: usize = 10_000;
+
+// The innermost calls shown as labels of a runtime error
+const CALLS_SHOWN: usize = 3;
+
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub enum Value {
+    Integer(i32),
+    Real(f64),
+    Char(char),
+    Boolean(bool),
+    String(String),
+    Array(Vec<Value>),
+    // fields in declaration order
+    Struct(Vec<(String, Value)>),
+}
+
+impl Value {
+    pub fn zero(type_: &Type) -> Value {
+        match type_ {
+            Type::Int_ => Value::Integer(0),
+            Type::Real_ => Value::Real(0.0),
+            Type::Char_ => Value::Char('\0'),
+            Type::Bool_ => Value::Boolean(false),
+            Type::String_ => Value::String(String::new()),
+            Type::Array_type_(n_elements, element) => Value::Array(vec![Value::zero(element); *n_elements as usize]),
+            Type::Alias_type_(type_) => Value::zero(type_),
+            Type::Struct_type_(fields) => Value::Struct(fields.iter()
+                .filter_map(|field| match &field.1 {
+                    Kind::field(type_) => Some((field.0.clone(), Value::zero(type_))),
+                    _ => None
+                })
+                .collect()),
+            Type::Universal_ => unreachable!("checked programs have no erroneous types"),
+        }
+    }
+
+    // Whether the value can be stored in a variable of type `type_`
+    pub fn has_type(&self, type_: &Type) -> bool {
+        match (self, type_) {
+            (_, Type::Alias_type_(type_)) => self.has_type(type_),
+            (Value::Integer(_), Type::Int_) | (Value::Real(_), Type::Real_) | (Value::Char(_), Type::Char_) |
+            (Value::Boolean(_), Type::Bool_) | (Value::String(_), Type::String_) => true,
+            (Value::Array(elements), Type::Array_type_(n_elements, element)) => {
+                elements.len() == *n_elements as usize && elements.iter().all(|value| value.has_type(element))
+            },
+            (Value::Struct(values), Type::Struct_type_(fields)) => {
+                let fields: Vec<(&String, &Type)> = fields.iter()
+                    .filter_map(|field| match &field.1 {
+                        Kind::field(type_) => Some((&field.0, type_)),
+                        _ => None
+                    })
+                    .collect();
+                values.len() == fields.len() && values.iter().zip(fields)
+                    .all(|((name, value), (field, type_))| name == field && value.has_type(type_))
+            },
+            _ => false
+        }
+    }
+}
+
+// Values are shown as the literals that would write them, arrays and structs between brackets
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Integer(n) => write!(f, "{}", n),
+            Value::Real(x) => write!(f, "{:?}", x),
+            Value::Char(ch) => write!(f, "{:?}", ch),
+            Value::Boolean(b) => write!(f, "{}", b),
+            Value::String(s) => write!(f, "{:?}", s),
+            Value::Array(elements) => {
+                write!(f, "[")?;
+                for (i, element) in elements.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", element)?;
+                }
+                write!(f, "]")
+            },
+            Value::Struct(fields) => {
+                write!(f, "{{ ")?;
+                for (i, (name, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{} = {}", name, value)?;
+                }
+                write!(f, " }}")
+            },
+        }
+    }
+}
+
+/*
+ * Reads a call written the way the program would write it, e.g. `f(1, 'a', "text", -2.5)`,
+ * and checks it against the function's parameters. Only integers, reals, chars, booleans and
+ * strings can be written as arguments.
+ */
+pub fn parse_call(program: &ast::Program, call: &str) -> Result<(String, Vec<Value>), String> {
+    parse_call_with(call, |name| params(program, name))
+}
+
+// `parse_call` for any program that can tell the types of the parameters of its functions
+pub fn parse_call_with(call: &str, params: impl Fn(&str) -> Option<Vec<Type>>) -> Result<(String, Vec<Value>), String> {
+    let mut tokens = TokenStream::new(call.chars());
+    let name = match tokens.next_spanned().token {
+        Token::ID(name) => name.to_string(),
+        _ => return Err(format!("`{}` is not a call, expected `function(arguments)`", call)),
+    };
+    let params = params(&name).ok_or_else(|| format!("the program has no function `{}`", name))?;
+
+    if tokens.next_spanned().token != Token::LEFTPARENTHESIS {
+        return Err(format!("expected `(` after `{}`", name));
+    }
+    let mut args = vec![];
+    let mut token = tokens.next_spanned().token;
+    // `f()` has no arguments, otherwise every argument is followed by `,` or the closing `)`
+    while token != Token::RIGHTPARENTHESIS || !args.is_empty() {
+        let negative = token == Token::MINUS;
+        if negative {
+            token = tokens.next_spanned().token;
+        }
+        let value = match token {
+            Token::NUMERAL(numeral) if numeral.contains('.') => {
+                let value = terminals::real_value(numeral);
+                Some(Value::Real(if negative { -value } else { value }))
+            },
+            Token::NUMERAL(numeral) => terminals::integer_value(numeral)
+                .and_then(|value| if negative { value.checked_neg() } else { Some(value) })
+                .map(Value::Integer),
+            Token::CHARACTER(ch) if !negative => Some(Value::Char(ch)),
+            Token::STRINGVAL(s) if !negative => Some(Value::String(s.into_owned())),
+            Token::TRUE if !negative => Some(Value::Boolean(true)),
+            Token::FALSE if !negative => Some(Value::Boolean(false)),
+            _ => None
+        };
+        let value = value.ok_or_else(|| format!("argument {} of `{}` is not a literal", args.len() + 1, name))?;
+        args.push(value);
+
+        match tokens.next_spanned().token {
+            Token::COMMA => token = tokens.next_spanned().token,
+            Token::RIGHTPARENTHESIS => break,
+            _ => return Err(format!("expected `,` or `)` after argument {} of `{}`", args.len(), name)),
+        }
+    }
+    if tokens.next_spanned().token != Token::DOLLAR {
+        return Err(format!("unexpected text after the call to `{}`", name));
+    }
+    check_args(&name, &params, &args)?;
+    Ok((name, args))
+}
+
+// Whether `args` can be passed to the function `name` with `params`, what is wrong with them if not
+pub fn check_args(name: &str, params: &[Type], args: &[Value]) -> Result<(), String> {
+    if args.len() != params.len() {
+        let plural = if params.len() == 1 { "" } else { "s" };
+        return Err(format!("`{}` takes {} argument{} but {} were given", name, params.len(), plural, args.len()));
+    }
+    for (i, (arg, param)) in args.iter().zip(params.iter()).enumerate() {
+        if !arg.has_type(param) {
+            return Err(format!("argument {} of `{}` must be {} but `{}` was given", i + 1, name, param, arg));
+        }
+    }
+    Ok(())
+}
+
+// The types of the parameters of the function `name`
+pub fn params(program: &ast::Program, name: &str) -> Option<Vec<Type>> {
+    find_function(program, name).map(|function| function.params.iter().map(|param| param.type_.type_.clone()).collect())
+}
+
+fn find_function<'p>(program: &'p ast::Program, name: &str) -> Option<&'p ast::FunctionDecl> {
+    program.declarations.iter().find_map(|declaration| match declaration {
+        ast::Declaration::Function(function) if function.name.name == name => Some(function),
+        _ => None
+    })
+}
+
+/*
+ * Calls `function` with `args`, which must match its parameters as parse_call checks. Calls,
+ * statements and expressions are run from explicit stacks rather than by recursion, so how
+ * deeply the program nests them is only limited by MAX_CALL_DEPTH and the memory.
+ */
+pub fn run(program: &ast::Program, function: &str, args: Vec<Value>) -> Result<Value, Box<Diagnostic>> {
+    let function = find_function(program, function).unwrap_or_else(|| panic!("the program has no function `{}`", function));
+    assert!(args.len() == function.params.len() && args.iter().zip(function.params.iter()).all(|(arg, param)| arg.has_type(&param.type_.type_)),
+            "the arguments don't match the parameters of `{}`", function.name.name);
+
+    let mut interpreter = Interpreter::new(program);
+    interpreter.enter(function, args);
+    while let Some(task) = interpreter.tasks.pop() {
+        interpreter.step(task)?;
+    }
+    Ok(interpreter.values.pop().expect("the outermost call returned a value"))
+}
+
+/*
+ * What is left to do, the task on top of the stack runs first. Operators, calls and stores
+ * take their operands from the value stack, where the tasks evaluating them left them.
+ */
+enum Task<'p> {
+    Expr(&'p ast::Expr),
+    Stmt(&'p ast::Stmt),
+    Binary { op: ast::BinaryOp, span: Span },
+    // `&&` and `||` with their left operand evaluated
+    ShortCircuit { op: ast::BinaryOp, right: &'p ast::Expr },
+    Unary(ast::UnaryOp),
+    Convert(&'p Type),
+    Call { function: &'p ast::FunctionDecl, n_args: usize, span: Span },
+    // left values with their indices evaluated
+    Load(&'p ast::LValue),
+    Store(&'p ast::LValue),
+    IncDec { op: ast::IncDecOp, target: &'p ast::LValue },
+    // statements with their condition evaluated
+    If { then_branch: &'p ast::Stmt, else_branch: Option<&'p ast::Stmt> },
+    LoopTest(&'p ast::Stmt),
+    // the body of a loop is being run, `break` and `continue` unwind to here
+    Loop(&'p ast::Stmt),
+    EndBlock,
+    Return,
+    // the body of a function is being run, `return` unwinds to here
+    EndCall,
+}
+
+// A step from a variable to the part of it a left value stands for
+enum Step {
+    Field(String),
+    Index { index: i32, span: Span },
+}
+
+// Where a left value is stored, a character of a string is not a value of its own
+enum Place<'v> {
+    Value(&'v mut Value),
+    Char { string: &'v mut String, index: usize },
+}
+
+struct Frame {
+    // one map per open block, the parameters share the first one with the body's variables
+    scopes: Vec<HashMap<String, Value>>,
+}
+
+struct Interpreter<'p> {
+    functions: HashMap<&'p str, &'p ast::FunctionDecl>,
+    frames: Vec<Frame>,
+    // the function and call site of every call being run, outermost first
+    calls: Vec<(&'p str, Span)>,
+    tasks: Vec<Task<'p>>,
+    values: Vec<Value>,
+}
+
+impl<'p> Interpreter<'p> {
+    fn new(program: &'p ast::Program) -> Self {
+        let mut functions = HashMap::new();
+        for declaration in program.declarations.iter() {
+            if let ast::Declaration::Function(function) = declaration {
+                functions.insert(function.name.name.as_str(), function);
+            }
+        }
+        Interpreter { functions, frames: vec![], calls: vec![], tasks: vec![], values: vec![] }
+    }
+
+    fn error(&self, code: ErrorCode, message: String, span: Span) -> Box<Diagnostic> {
+        runtime_error(&self.calls, code, message, span)
+    }
+
+    // Starts running the body of `function`, its frame is dropped when a `return` reaches EndCall
+    fn enter(&mut self, function: &'p ast::FunctionDecl, args: Vec<Value>) {
+        let mut scope = HashMap::new();
+        for (param, arg) in function.params.iter().zip(args) {
+            scope.insert(param.name.name.clone(), arg);
+        }
+        self.frames.push(Frame { scopes: vec![scope] });
+        self.declare_vars(&function.body.vars);
+        self.tasks.push(Task::EndCall);
+        self.tasks.extend(function.body.stmts.iter().rev().map(Task::Stmt));
+    }
+
+    fn frame(&mut self) -> &mut Frame {
+        self.frames.last_mut().unwrap()
+    }
+
+    fn declare_vars(&mut self, vars: &[ast::VarDecl]) {
+        for var in vars.iter() {
+            for name in var.names.iter() {
+                let zero = Value::zero(&var.type_.type_);
+                self.frame().scopes.last_mut().unwrap().insert(name.name.clone(), zero);
+            }
+        }
+    }
+
+    fn pop(&mut self) -> Value {
+        self.values.pop().expect("every operand was evaluated")
+    }
+
+    fn pop_condition(&mut self) -> bool {
+        match self.pop() {
+            Value::Boolean(b) => b,
+            value => unreachable!("condition evaluated to {}", value),
+        }
+    }
+
+    fn step(&mut self, task: Task<'p>) -> Result<(), Box<Diagnostic>> {
+        match task {
+            Task::Expr(expr) => self.expr(expr),
+            Task::Stmt(stmt) => self.stmt(stmt),
+            Task::Binary { op, span } => {
+                let right = self.pop();
+                let left = self.pop();
+                let value = self.binary(op, left, right, span)?;
+                self.values.push(value);
+            },
+            // `&&` and `||` only evaluate their right operand when it decides the result
+            Task::ShortCircuit { op, right } => {
+                let left = self.pop_condition();
+                if left == (op == ast::BinaryOp::Or) {
+                    self.values.push(Value::Boolean(left));
+                } else {
+                    self.tasks.push(Task::Expr(right));
+                }
+            },
+            Task::Unary(op) => {
+                let value = match (op, self.pop()) {
+                    (ast::UnaryOp::Negate, Value::Integer(n)) => Value::Integer(n.wrapping_neg()),
+                    (ast::UnaryOp::Negate, Value::Real(x)) => Value::Real(-x),
+                    (ast::UnaryOp::Not, Value::Boolean(b)) => Value::Boolean(!b),
+                    (op, value) => unreachable!("{:?} applied to {}", op, value),
+                };
+                self.values.push(value);
+            },
+            Task::Convert(type_) => {
+                let value = match (type_, self.pop()) {
+                    // the conversion to integer rounds towards zero and saturates
+                    (Type::Int_, Value::Real(x)) => Value::Integer(x as i32),
+                    (Type::Real_, Value::Integer(n)) => Value::Real(f64::from(n)),
+                    (_, value) => value,
+                };
+                self.values.push(value);
+            },
+            Task::Call { function, n_args, span } => {
+                if self.calls.len() == MAX_CALL_DEPTH {
+                    return Err(self.error(ErrorCode::StackOverflow,
+                                          format!("stack overflow, calls are nested more than {} deep", MAX_CALL_DEPTH),
+                                          span));
+                }
+                let args = self.values.split_off(self.values.len() - n_args);
+                self.calls.push((function.name.name.as_str(), span));
+                self.enter(function, args);
+            },
+            Task::Load(lvalue) => {
+                let path = self.path(lvalue);
+                let value = match self.place(lvalue, &path)? {
+                    Place::Value(value) => value.clone(),
+                    Place::Char { string, index } => Value::Char(string.chars().nth(index).unwrap()),
+                };
+                self.values.push(value);
+            },
+            Task::Store(target) => {
+                let value = self.pop();
+                let path = self.path(target);
+                match (self.place(target, &path)?, value) {
+                    (Place::Value(place), value) => *place = value,
+                    (Place::Char { string, index }, Value::Char(ch)) => {
+                        let (start, old) = string.char_indices().nth(index).unwrap();
+                        string.replace_range(start..start + old.len_utf8(), ch.encode_utf8(&mut [0; 4]));
+                    },
+                    (_, value) => unreachable!("{} stored in a string", value),
+                }
+            },
+            Task::IncDec { op, target } => {
+                let path = self.path(target);
+                let place = match self.place(target, &path)? {
+                    Place::Value(place) => place,
+                    Place::Char { .. } => unreachable!("`++` or `--` applied to a character"),
+                };
+                let old = match place {
+                    Value::Integer(n) => *n,
+                    value => unreachable!("`++` or `--` applied to {}", value),
+                };
+                let (new, prefix) = match op {
+                    ast::IncDecOp::PreIncrement => (old.wrapping_add(1), true),
+                    ast::IncDecOp::PreDecrement => (old.wrapping_sub(1), true),
+                    ast::IncDecOp::PostIncrement => (old.wrapping_add(1), false),
+                    ast::IncDecOp::PostDecrement => (old.wrapping_sub(1), false),
+                };
+                *place = Value::Integer(new);
+                self.values.push(Value::Integer(if prefix { new } else { old }));
+            },
+            Task::If { then_branch, else_branch } => {
+                if self.pop_condition() {
+                    self.tasks.push(Task::Stmt(then_branch));
+                } else if let Some(else_branch) = else_branch {
+                    self.tasks.push(Task::Stmt(else_branch));
+                }
+            },
+            Task::LoopTest(stmt) => {
+                if self.pop_condition() {
+                    self.run_body(stmt);
+                }
+            },
+            // the body ran to its end or continued, the condition decides whether it runs again
+            Task::Loop(stmt) => {
+                let condition = match &stmt.kind {
+                    ast::StmtKind::While { condition, .. } | ast::StmtKind::DoWhile { condition, .. } => condition,
+                    _ => unreachable!("only loops have a body that runs again"),
+                };
+                self.tasks.push(Task::LoopTest(stmt));
+                self.tasks.push(Task::Expr(condition));
+            },
+            Task::EndBlock => {
+                self.frame().scopes.pop();
+            },
+            // the returned value stays on the value stack for the caller
+            Task::Return => {
+                while !matches!(self.tasks.pop(), Some(Task::EndCall)) {}
+                self.frames.pop();
+                self.calls.pop();
+            },
+            Task::EndCall => unreachable!("the flow analysis rejects functions that can end without a return"),
+        }
+        Ok(())
+    }
+
+    fn run_body(&mut self, stmt: &'p ast::Stmt) {
+        if let ast::StmtKind::While { body, .. } | ast::StmtKind::DoWhile { body, .. } = &stmt.kind {
+            self.tasks.push(Task::Loop(stmt));
+            self.tasks.push(Task::Stmt(body));
+        }
+    }
+
+    // Drops the tasks down to the body of the loop `id`, closing the blocks left on the way
+    fn unwind_to_loop(&mut self, id: ast::LoopId) -> Task<'p> {
+        loop {
+            match self.tasks.pop() {
+                Some(Task::Loop(stmt)) if matches!(&stmt.kind, ast::StmtKind::While { id: loop_id, .. } |
+                                                                ast::StmtKind::DoWhile { id: loop_id, .. } if *loop_id == id) => {
+                    return Task::Loop(stmt);
+                },
+                Some(Task::EndBlock) => {
+                    self.frame().scopes.pop();
+                },
+                Some(_) => (),
+                None => unreachable!("`break` and `continue` are only allowed in loops"),
+            }
+        }
+    }
+
+    fn stmt(&mut self, stmt: &'p ast::Stmt) {
+        match &stmt.kind {
+            ast::StmtKind::If { condition, then_branch, else_branch } => {
+                self.tasks.push(Task::If { then_branch, else_branch: else_branch.as_deref() });
+                self.tasks.push(Task::Expr(condition));
+            },
+            ast::StmtKind::While { condition, .. } => {
+                self.tasks.push(Task::LoopTest(stmt));
+                self.tasks.push(Task::Expr(condition));
+            },
+            ast::StmtKind::DoWhile { .. } => self.run_body(stmt),
+            ast::StmtKind::Block(block) => {
+                self.frame().scopes.push(HashMap::new());
+                self.declare_vars(&block.vars);
+                self.tasks.push(Task::EndBlock);
+                self.tasks.extend(block.stmts.iter().rev().map(Task::Stmt));
+            },
+            // the indices of the target are evaluated before the value assigned
+            ast::StmtKind::Assign { target, value } => {
+                self.tasks.push(Task::Store(target));
+                self.tasks.push(Task::Expr(value));
+                self.indices(target);
+            },
+            ast::StmtKind::Break(id) => {
+                self.unwind_to_loop(*id);
+            },
+            ast::StmtKind::Continue(id) => {
+                let body = self.unwind_to_loop(*id);
+                self.tasks.push(body);
+            },
+            ast::StmtKind::Return(value) => {
+                self.tasks.push(Task::Return);
+                self.tasks.push(Task::Expr(value));
+            },
+            ast::StmtKind::Error => unreachable!("checked programs have no erroneous statements"),
+        }
+    }
+
+    fn expr(&mut self, expr: &'p ast::Expr) {
+        match &expr.kind {
+            ast::ExprKind::Integer(n) => self.values.push(Value::Integer(*n)),
+            ast::ExprKind::Real(x) => self.values.push(Value::Real(*x)),
+            ast::ExprKind::Char(ch) => self.values.push(Value::Char(*ch)),
+            ast::ExprKind::Bool(b) => self.values.push(Value::Boolean(*b)),
+            ast::ExprKind::String(s) => self.values.push(Value::String(s.clone())),
+            ast::ExprKind::LValue(lvalue) => {
+                self.tasks.push(Task::Load(lvalue));
+                self.indices(lvalue);
+            },
+            ast::ExprKind::Binary { op: op @ (ast::BinaryOp::And | ast::BinaryOp::Or), left, right } => {
+                self.tasks.push(Task::ShortCircuit { op: *op, right });
+                self.tasks.push(Task::Expr(left));
+            },
+            ast::ExprKind::Binary { op, left, right } => {
+                self.tasks.push(Task::Binary { op: *op, span: expr.span });
+                self.tasks.push(Task::Expr(right));
+                self.tasks.push(Task::Expr(left));
+            },
+            ast::ExprKind::Unary { op, operand } => {
+                self.tasks.push(Task::Unary(*op));
+                self.tasks.push(Task::Expr(operand));
+            },
+            ast::ExprKind::Convert(operand) => {
+                self.tasks.push(Task::Convert(&expr.type_));
+                self.tasks.push(Task::Expr(operand));
+            },
+            ast::ExprKind::IncDec { op, target } => {
+                self.tasks.push(Task::IncDec { op: *op, target });
+                self.indices(target);
+            },
+            ast::ExprKind::Call { function, args } => {
+                let function = self.functions[function.name.as_str()];
+                self.tasks.push(Task::Call { function, n_args: args.len(), span: expr.span });
+                self.tasks.extend(args.iter().rev().map(Task::Expr));
+            },
+        }
+    }
+
+    fn binary(&self, op: ast::BinaryOp, left: Value, right: Value, span: Span) -> Result<Value, Box<Diagnostic>> {
+        let value = match (op, left, right) {
+            (ast::BinaryOp::Less, left, right) => Value::Boolean(left < right),
+            (ast::BinaryOp::Greater, left, right) => Value::Boolean(left > right),
+            (ast::BinaryOp::LessEqual, left, right) => Value::Boolean(left <= right),
+            (ast::BinaryOp::GreaterEqual, left, right) => Value::Boolean(left >= right),
+            (ast::BinaryOp::Equal, left, right) => Value::Boolean(left == right),
+            (ast::BinaryOp::NotEqual, left, right) => Value::Boolean(left != right),
+
+            (ast::BinaryOp::Add, Value::Integer(a), Value::Integer(b)) => Value::Integer(a.wrapping_add(b)),
+            (ast::BinaryOp::Sub, Value::Integer(a), Value::Integer(b)) => Value::Integer(a.wrapping_sub(b)),
+            (ast::BinaryOp::Mul, Value::Integer(a), Value::Integer(b)) => Value::Integer(a.wrapping_mul(b)),
+            (ast::BinaryOp::Div, Value::Integer(_), Value::Integer(0)) => {
+                return Err(self.error(ErrorCode::DivisionByZero, String::from("division by zero"), span));
+            },
+            (ast::BinaryOp::Div, Value::Integer(a), Value::Integer(b)) => Value::Integer(a.wrapping_div(b)),
+
+            (ast::BinaryOp::Add, Value::Real(a), Value::Real(b)) => Value::Real(a + b),
+            (ast::BinaryOp::Sub, Value::Real(a), Value::Real(b)) => Value::Real(a - b),
+            (ast::BinaryOp::Mul, Value::Real(a), Value::Real(b)) => Value::Real(a * b),
+            (ast::BinaryOp::Div, Value::Real(a), Value::Real(b)) => Value::Real(a / b),
+
+            (ast::BinaryOp::Add, Value::String(a), Value::String(b)) => Value::String(a + &b),
+            (op, left, right) => unreachable!("{:?} applied to {} and {}", op, left, right),
+        };
+        Ok(value)
+    }
+
+    // Evaluates the indices of a left value, from the variable outwards
+    fn indices(&mut self, lvalue: &'p ast::LValue) {
+        let mut lvalue = lvalue;
+        loop {
+            match &lvalue.kind {
+                ast::LValueKind::Variable(_) => break,
+                ast::LValueKind::Field { base, .. } => lvalue = base,
+                ast::LValueKind::Index { base, index } => {
+                    self.tasks.push(Task::Expr(index));
+                    lvalue = base;
+                },
+            }
+        }
+    }
+
+    // The steps from the variable of a left value to its part, taking the indices `indices` evaluated off the value stack
+    fn path(&mut self, lvalue: &ast::LValue) -> Vec<Step> {
+        let mut parts = vec![];
+        let mut lvalue = lvalue;
+        while let ast::LValueKind::Field { base, .. } | ast::LValueKind::Index { base, .. } = &lvalue.kind {
+            parts.push(lvalue);
+            lvalue = base;
+        }
+        let n_indices = parts.iter().filter(|part| matches!(part.kind, ast::LValueKind::Index { .. })).count();
+        let mut indices = self.values.split_off(self.values.len() - n_indices).into_iter();
+        parts.iter().rev()
+            .map(|part| match &part.kind {
+                ast::LValueKind::Field { field, .. } => Step::Field(field.name.clone()),
+                ast::LValueKind::Index { index, .. } => match indices.next() {
+                    Some(Value::Integer(value)) => Step::Index { index: value, span: index.span },
+                    value => unreachable!("array indexed by {:?}", value),
+                },
+                ast::LValueKind::Variable(_) => unreachable!(),
+            })
+            .collect()
+    }
+
+    // Where the value a left value stands for is, `path` holds its evaluated indices
+    fn place(&mut self, lvalue: &ast::LValue, path: &[Step]) -> Result<Place<'_>, Box<Diagnostic>> {
+        let mut variable = lvalue;
+        while let ast::LValueKind::Field { base, .. } | ast::LValueKind::Index { base, .. } = &variable.kind {
+            variable = base;
+        }
+        let name = match &variable.kind {
+            ast::LValueKind::Variable(name) => name.name.as_str(),
+            _ => unreachable!(),
+        };
+
+        // the error is built once the variable is no longer borrowed
+        let mut out_of_bounds = None;
+        let mut place = self.frames.last_mut().unwrap().scopes.iter_mut().rev().find_map(|scope| scope.get_mut(name));
+        for (i, step) in path.iter().enumerate() {
+            let value = place.take().unwrap();
+            place = match (step, value) {
+                (Step::Field(field), Value::Struct(fields)) => {
+                    fields.iter_mut().find(|(name, _)| name == field).map(|(_, value)| value)
+                },
+                (Step::Index { index, span }, Value::Array(elements)) => {
+                    let length = elements.len();
+                    let element = usize::try_from(*index).ok().and_then(|index| elements.get_mut(index));
+                    if element.is_none() {
+                        out_of_bounds = Some((format!("index {} is out of bounds for an array of {} elements", index, length), *span));
+                        break;
+                    }
+                    element
+                },
+                // the characters of a string have no parts, indexing one is the last step
+                (Step::Index { index, span }, Value::String(string)) if i == path.len() - 1 => {
+                    let length = string.chars().count();
+                    match usize::try_from(*index).ok().filter(|index| *index < length) {
+                        Some(index) => return Ok(Place::Char { string, index }),
+                        None => {
+                            out_of_bounds = Some((format!("index {} is out of bounds for a string of {} characters", index, length), *span));
+                            break;
+                        },
+                    }
+                },
+                _ => unreachable!("the semantic analysis checks fields and indexing"),
+            };
+        }
+        match (place, out_of_bounds) {
+            (Some(place), None) => Ok(Place::Value(place)),
+            (_, Some((message, span))) => Err(runtime_error(&self.calls, ErrorCode::IndexOutOfBounds, message, span)),
+            (None, None) => unreachable!("the semantic analysis checks variables and fields"),
+        }
+    }
+}
+
+// The error labels the innermost calls, so the path that led to it can be followed back
+pub(crate) fn runtime_error(calls: &[(&str, Span)], code: ErrorCode, message: String, span: Span) -> Box<Diagnostic> {
+    let mut diagnostic = Diagnostic::error(code, message, span);
+    let mut shown: Vec<Span> = vec![span];
+    for (function, call_site) in calls.iter().rev() {
+        if shown.len() > CALLS_SHOWN {
+            break;
+        }
+        // a recursive function calls itself from the same place over and over
+        if !shown.contains(call_site) {
+            shown.push(*call_site);
+            diagnostic = diagnostic.with_label(*call_site, format!("in this call to `{}`", function));
+        }
+    }
+    Box::new(diagnostic)
+}
@@ -0,0 +1,243 @@
+//! Front end of the Simplescript compiler.
+//!
+//! A program goes through [`lex`], [`parse`], [`check`] and [`lower`]. [`parse`] runs the
+//! semantic analysis along with the parser and stops with the [`Diagnostics`] they found,
+//! the stages after it can't fail. Checked
+//! programs can also be run directly with [`run`], or compiled to [`bytecode`] and run on its
+//! VM with [`run_bytecode`]:
+//!
+//! ```
+//! let source = "function f(n : integer) : integer { var x : integer; x = n + 1; return x; }";
+//! let ast = rulox::parse(source).unwrap();
+//! let typed = rulox::check(&ast);
+//! let module = rulox::lower(&typed);
+//! assert_eq!(module.functions[0].name, "f");
+//! assert_eq!(rulox::run(&typed, "f", vec![rulox::Value::Integer(41)]), Ok(rulox::Value::Integer(42)));
+//! let bytecode = rulox::compile(&typed);
+//! assert_eq!(rulox::run_bytecode(&bytecode, "f", vec![rulox::Value::Integer(41)]), Ok(rulox::Value::Integer(42)));
+//! ```
+
+mod syntatic_analyzer;
+mod intermediate_code;
+mod interpreter;
+pub mod bytecode;
+mod backend;
+
+#[cfg(test)]
+mod tests;
+
+use std::ops::Deref;
+
+use syntatic_analyzer::StateMachine;
+use syntatic_analyzer::report;
+use syntatic_analyzer::token_stream::TokenStream;
+
+pub use syntatic_analyzer::ast;
+pub use syntatic_analyzer::diagnostics::{Diagnostic, ErrorCode, Label, Phase, Severity, Subject};
+pub use syntatic_analyzer::nonterminals::{Kind, Object, Type};
+pub use syntatic_analyzer::span::{Position, Span};
+pub use syntatic_analyzer::terminals::{LexError, SpannedToken, Token};
+pub use syntatic_analyzer::trace::{Trace, TraceFormat};
+pub use interpreter::{Value, MAX_CALL_DEPTH};
+/// Three address code, the intermediate representation [`lower`] produces.
+pub use intermediate_code::tac as ir;
+
+/// The errors of a stage, in the order they were found. Never empty.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostics(Vec<Diagnostic>);
+
+impl Diagnostics {
+    /// The earliest stage that found an error.
+    pub fn phase(&self) -> Phase {
+        self.0.iter().map(|diagnostic| diagnostic.code.phase()).min().unwrap_or(Phase::Semantic)
+    }
+
+    /// Every diagnostic as the compiler prints it, pointing into `source`.
+    pub fn render(&self, file_name: &str, source: &str) -> String {
+        report::render_all(&self.0, file_name, source)
+    }
+
+    pub fn into_vec(self) -> Vec<Diagnostic> {
+        self.0
+    }
+}
+
+impl Deref for Diagnostics {
+    type Target = [Diagnostic];
+
+    fn deref(&self) -> &[Diagnostic] {
+        &self.0
+    }
+}
+
+impl IntoIterator for Diagnostics {
+    type Item = Diagnostic;
+    type IntoIter = std::vec::IntoIter<Diagnostic>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+/// A program that went through the parser and the semantic analysis without errors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ast {
+    program: ast::Program,
+    source: String,
+}
+
+impl Ast {
+    pub fn program(&self) -> &ast::Program {
+        &self.program
+    }
+}
+
+/// A program without errors, every expression in it has the type it was checked against.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypedAst {
+    program: ast::Program,
+    source: String,
+}
+
+impl TypedAst {
+    pub fn program(&self) -> &ast::Program {
+        &self.program
+    }
+
+    pub fn into_program(self) -> ast::Program {
+        self.program
+    }
+}
+
+/// Every token of `source` up to and including [`Token::DOLLAR`]. Lexical errors are
+/// [`Token::ERROR`] tokens, [`LexError::diagnostic`] turns them into diagnostics.
+pub fn lex(source: &str) -> Vec<SpannedToken<'_>> {
+    let mut token_stream = TokenStream::new(source.chars());
+    let mut tokens = vec![];
+    loop {
+        let spanned = token_stream.next_spanned();
+        let end = spanned.token == Token::DOLLAR;
+        tokens.push(spanned);
+        if end {
+            return tokens;
+        }
+    }
+}
+
+/// Parses `source` and runs the semantic analysis, which the reduction actions of the parser
+/// do along the way, failing on any error. A program with lexical errors is not parsed at all,
+/// while the parser recovers from syntax errors so all of them, and the semantic errors found
+/// around them, are reported at once.
+pub fn parse(source: &str) -> Result<Ast, Diagnostics> {
+    parse_with(source, None).0
+}
+
+/// [`parse`], logging every step of the parser in `trace`.
+pub fn parse_traced(source: &str, trace: Trace) -> (Result<Ast, Diagnostics>, Trace) {
+    let (parsed, trace) = parse_with(source, Some(trace));
+    (parsed, trace.unwrap())
+}
+
+fn parse_with(source: &str, trace: Option<Trace>) -> (Result<Ast, Diagnostics>, Option<Trace>) {
+    let lexical_errors: Vec<Diagnostic> = lex(source).into_iter()
+        .filter_map(|spanned| match spanned.token {
+            Token::ERROR(error) => Some(error.diagnostic(spanned.span)),
+            _ => None,
+        })
+        .collect();
+    if !lexical_errors.is_empty() {
+        return (Err(Diagnostics(lexical_errors)), trace);
+    }
+
+    let mut parser = StateMachine::new(source.to_string());
+    if let Some(trace) = trace {
+        parser.set_trace(trace);
+    }
+    let (program, diagnostics) = parser.parse_with_diagnostics();
+    let trace = parser.take_trace();
+    let parsed = match program {
+        Some(program) if diagnostics.is_empty() => Ok(Ast { program, source: source.to_string() }),
+        _ => Err(Diagnostics(diagnostics)),
+    };
+    (parsed, trace)
+}
+
+/// The program the later stages take. [`parse`] already checked that names are declared,
+/// types match and functions return a value, so this can't fail.
+pub fn check(ast: &Ast) -> TypedAst {
+    TypedAst { program: ast.program.clone(), source: ast.source.clone() }
+}
+
+/// Lowers a checked program to three address code.
+pub fn lower(ast: &TypedAst) -> ir::Module {
+    intermediate_code::generate(&ast.program)
+}
+
+/// Reads a call such as `f(1, 'a', "text", -2.5)` and checks that the program has the function
+/// and that the arguments match its parameters. Arguments are literals of the scalar types and
+/// strings, the error says what is wrong with the call.
+pub fn parse_call(ast: &TypedAst, call: &str) -> Result<(String, Vec<Value>), String> {
+    interpreter::parse_call(&ast.program, call)
+}
+
+/// Runs `function` of a checked program with `args` and returns the value it returns. Dividing
+/// an integer by zero, indexing out of the bounds of an array and nesting more than
+/// [`MAX_CALL_DEPTH`] calls are runtime errors, labelled with the calls that led to them. So is
+/// a call the program can't make, [`ErrorCode::InvalidCall`]: `function` doesn't exist or
+/// `args` don't match its parameters. The calls [`parse_call`] reads never are.
+pub fn run(ast: &TypedAst, function: &str, args: Vec<Value>) -> Result<Value, Diagnostics> {
+    check_call(function, interpreter::params(&ast.program, function), &args)?;
+    interpreter::run(&ast.program, function, args).map_err(|error| Diagnostics(vec![*error]))
+}
+
+/// Compiles a checked program to bytecode. The module keeps the source of the program, so it
+/// can be saved with [`bytecode::Module::to_ssbc`] and its runtime errors still rendered.
+pub fn compile(ast: &TypedAst) -> bytecode::Module {
+    bytecode::compile(&ast.program, &ast.source)
+}
+
+/// [`parse_call`] for a function of a bytecode module.
+pub fn parse_bytecode_call(module: &bytecode::Module, call: &str) -> Result<(String, Vec<Value>), String> {
+    interpreter::parse_call_with(call, |name| bytecode_params(module, name))
+}
+
+/// [`run`] on the VM: the same program gives the same value and the same runtime errors.
+pub fn run_bytecode(module: &bytecode::Module, function: &str, args: Vec<Value>) -> Result<Value, Diagnostics> {
+    check_call(function, bytecode_params(module, function), &args)?;
+    let index = module.function(function).expect("the call was checked");
+    bytecode::vm::run(module, index, args).map_err(|error| Diagnostics(vec![*error]))
+}
+
+// The types of the parameters of the function `name` of a module
+fn bytecode_params(module: &bytecode::Module, name: &str) -> Option<Vec<Type>> {
+    let function = &module.functions[module.function(name)?];
+    Some(function.locals[..function.n_params as usize].iter().map(|type_| module.type_(*type_)).collect())
+}
+
+// The error of a call to `function`, which has `params` if it exists, that can't be made
+fn check_call(function: &str, params: Option<Vec<Type>>, args: &[Value]) -> Result<(), Diagnostics> {
+    params.ok_or_else(|| format!("the program has no function `{}`", function))
+        .and_then(|params| interpreter::check_args(function, &params, args))
+        .map_err(|message| Diagnostics(vec![Diagnostic::error(ErrorCode::InvalidCall, message, Span::default())]))
+}
+
+/// Translates a checked program to C99, the runtime it needs included at the top. With an
+/// `entry` call, checked by [`parse_call`], the C program gets a `main` that makes the call
+/// and prints what it returns.
+pub fn emit_c(ast: &TypedAst, entry: Option<(&str, &[Value])>) -> String {
+    backend::c::generate(&ast.program, entry)
+}
+
+/// Translates a checked program to a textual LLVM module, the runtime it needs defined at the
+/// top. `entry` gives the module a `main` like it does for [`emit_c`].
+pub fn emit_llvm(ast: &TypedAst, entry: Option<(&str, &[Value])>) -> String {
+    backend::llvm::generate(&ast.program, entry)
+}
+
+/// Translates a checked program to a WebAssembly text module exporting its functions and its
+/// memory. The host provides the `ss` imports the runtime at the top of the module reports
+/// runtime errors with. Fails with [`ErrorCode::TypeTooLarge`] when the arrays and structs of
+/// the program don't fit in the 4 GiB of a WebAssembly memory.
+pub fn emit_wat(ast: &TypedAst) -> Result<String, Diagnostics> {
+    backend::wat::generate(&ast.program).map_err(Diagnostics)
+}
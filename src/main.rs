@@ -1,16 +1,376 @@
-pub mod syntatic_analyzer;
-
 #[cfg(test)]
-mod tests; 
+mod tests {
+    mod driver_tests;
+}
 
 use std::env;
 use std::fs;
+use std::io::{self, Read, Write};
 
-fn main() {
-    // read command line inputs
-    let args: Vec<String> = env::args().collect();
+use rulox::{ast, bytecode, Diagnostics, Phase, Token, Trace, TraceFormat};
+
+// Exit codes, a failing stage also stops the compilation of the file
+const EXIT_USAGE: i32 = 1;
+const EXIT_LEXICAL: i32 = 2;
+const EXIT_SYNTACTIC: i32 = 3;
+const EXIT_SEMANTIC: i32 = 4;
+const EXIT_RUNTIME: i32 = 5;
+
+const USAGE: &str = "\
+usage: rulox [--emit tokens|ast|symbols|ir|bytecode|ssbc|c|llvm|wat] [--run <call>] [--trace text|json [--trace-stack]] [-o <file>] <file.ss|file.ssbc>...
+
+Compiles each Simplescript file, `-` reads the program from the standard input. A .ssbc
+file, written by `--emit ssbc`, holds a compiled program: it can be run on the bytecode VM
+or disassembled with `--emit bytecode`.
+
+options:
+    --emit <stage>    stop after <stage> and write its result (default: ir), `ssbc` needs `-o`
+    --run <call>      run the checked program, e.g. `--run 'f(10, true)'`, and write what the call returns,
+                      with `--emit c` or `--emit llvm` the `main` of the program makes the call instead
+    --trace <format>  log every step of the parser to the standard error, as text or json lines
+    --trace-stack     also log the semantic stack after each step
+    -o <file>         write the result to <file> instead of the standard output
+    -h, --help        show this message
+
+exit codes:
+    0  success
+    1  bad usage, a file could not be read or written, or the call of `--run` is wrong
+    2  lexical error
+    3  syntax error
+    4  semantic error
+    5  runtime error
+";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Emit {
+    Tokens,
+    Ast,
+    Symbols,
+    Ir,
+    Bytecode,
+    Ssbc,
+    C,
+    Llvm,
+    Wat,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Options {
+    emit: Emit,
+    run: Option<String>,
+    trace: Option<TraceFormat>,
+    trace_stack: bool,
+    output: Option<String>,
+    inputs: Vec<String>,
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options { emit: Emit::Ir, run: None, trace: None, trace_stack: false, output: None, inputs: vec![] };
+    let mut emit = false;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--emit" => {
+                let stage = args.next().ok_or("`--emit` expects a stage")?;
+                options.emit = parse_emit(stage)?;
+                emit = true;
+            },
+            "--run" => {
+                let call = args.next().ok_or("`--run` expects a call")?;
+                options.run = Some(call.clone());
+            },
+            "--trace" => {
+                let format = args.next().ok_or("`--trace` expects a format")?;
+                options.trace = Some(parse_trace(format)?);
+            },
+            "--trace-stack" => options.trace_stack = true,
+            "-o" => {
+                let output = args.next().ok_or("`-o` expects a file name")?;
+                options.output = Some(output.clone());
+            },
+            "-" => options.inputs.push(arg.clone()),
+            _ if arg.starts_with("--emit=") => {
+                options.emit = parse_emit(&arg["--emit=".len()..])?;
+                emit = true;
+            },
+            _ if arg.starts_with("--run=") => options.run = Some(arg["--run=".len()..].to_string()),
+            _ if arg.starts_with("--trace=") => options.trace = Some(parse_trace(&arg["--trace=".len()..])?),
+            _ if arg.starts_with('-') => return Err(format!("unknown option `{}`", arg)),
+            _ => options.inputs.push(arg.clone()),
+        }
+    }
+
+    if options.inputs.is_empty() {
+        return Err(String::from("no input files"));
+    }
+    if emit && options.run.is_some() && options.emit != Emit::C && options.emit != Emit::Llvm {
+        return Err(String::from("`--run` can only be used with `--emit c` or `--emit llvm`"));
+    }
+    if options.emit == Emit::Ssbc && (options.output.is_none() || options.inputs.len() > 1) {
+        return Err(String::from("`--emit ssbc` writes one binary file, it needs `-o` and a single input"));
+    }
+    if options.trace_stack && options.trace.is_none() {
+        return Err(String::from("`--trace-stack` needs `--trace`"));
+    }
+    Ok(options)
+}
+
+fn parse_emit(stage: &str) -> Result<Emit, String> {
+    match stage {
+        "tokens" => Ok(Emit::Tokens),
+        "ast" => Ok(Emit::Ast),
+        "symbols" => Ok(Emit::Symbols),
+        "ir" => Ok(Emit::Ir),
+        "bytecode" => Ok(Emit::Bytecode),
+        "ssbc" => Ok(Emit::Ssbc),
+        "c" => Ok(Emit::C),
+        "llvm" => Ok(Emit::Llvm),
+        "wat" => Ok(Emit::Wat),
+        _ => Err(format!("unknown stage `{}`, expected tokens, ast, symbols, ir, bytecode, ssbc, c, llvm or wat", stage)),
+    }
+}
+
+fn parse_trace(format: &str) -> Result<TraceFormat, String> {
+    match format {
+        "text" => Ok(TraceFormat::Text),
+        "json" => Ok(TraceFormat::JsonLines),
+        _ => Err(format!("unknown trace format `{}`, expected text or json", format)),
+    }
+}
+
+fn exit_code(phase: Phase) -> i32 {
+    match phase {
+        Phase::Lexical => EXIT_LEXICAL,
+        Phase::Syntactic => EXIT_SYNTACTIC,
+        Phase::Semantic => EXIT_SEMANTIC,
+        Phase::Runtime => EXIT_RUNTIME,
+    }
+}
+
+// Why a file failed, the call of `--run` is only checked against the program it calls
+enum Failure {
+    Diagnostics(Diagnostics),
+    Usage(String),
+}
 
-    let contents = fs::read_to_string(args[1].clone()).unwrap();
-    
-    assert_eq!("\"Hello World\"", contents);
+impl From<Diagnostics> for Failure {
+    fn from(diagnostics: Diagnostics) -> Self {
+        Failure::Diagnostics(diagnostics)
+    }
+}
+
+/*
+ * Runs the compiler as the command line would, returning the exit code. With several
+ * inputs every file is compiled even if an earlier one fails, and the exit code is the one
+ * of the earliest stage that failed, a file that can't be loaded or a wrong call counting
+ * as bad usage.
+ */
+fn run(args: &[String], stdin: &mut dyn Read, stdout: &mut dyn Write, stderr: &mut dyn Write) -> i32 {
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        let _ = write!(stdout, "{}", USAGE);
+        return 0;
+    }
+    let options = match parse_args(args) {
+        Ok(options) => options,
+        Err(message) => {
+            let _ = write!(stderr, "error: {}\n\n{}", message, USAGE);
+            return EXIT_USAGE;
+        }
+    };
+
+    let mut output = vec![];
+    let mut failed: Option<i32> = None;
+    for input in options.inputs.iter() {
+        let name = if input == "-" { "<stdin>" } else { input.as_str() };
+        let (source, module) = match load(input, stdin) {
+            Ok(loaded) => loaded,
+            Err(message) => {
+                let _ = writeln!(stderr, "error: {}", message);
+                failed = Some(failed.map_or(EXIT_USAGE, |failed| failed.min(EXIT_USAGE)));
+                continue;
+            }
+        };
+
+        if options.inputs.len() > 1 {
+            if !output.is_empty() {
+                output.push(b'\n');
+            }
+            writeln!(output, "==> {} <==", name).unwrap();
+        }
+        let mut trace = String::new();
+        let compiled = match &module {
+            Some(module) => run_module(module, &options, &mut output),
+            None => compile(&source, &options, &mut output, &mut trace),
+        };
+        let _ = write!(stderr, "{}", trace);
+        let code = match compiled {
+            Ok(()) => continue,
+            Err(Failure::Diagnostics(diagnostics)) => {
+                let _ = write!(stderr, "{}", diagnostics.render(name, &source));
+                exit_code(diagnostics.phase())
+            },
+            Err(Failure::Usage(message)) => {
+                let _ = writeln!(stderr, "error: {}: {}", name, message);
+                EXIT_USAGE
+            }
+        };
+        failed = Some(failed.map_or(code, |failed| failed.min(code)));
+    }
+
+    let written = match &options.output {
+        Some(file) => fs::write(file, &output).map_err(|error| format!("could not write `{}`: {}", file, error)),
+        None => stdout.write_all(&output).map_err(|error| format!("could not write the output: {}", error)),
+    };
+    if let Err(message) = written {
+        let _ = writeln!(stderr, "error: {}", message);
+        return EXIT_USAGE;
+    }
+
+    failed.unwrap_or(0)
+}
+
+// Reads a source file, or a compiled program told apart by its magic number whatever the file is called
+fn load(input: &str, stdin: &mut dyn Read) -> Result<(String, Option<bytecode::Module>), String> {
+    let (name, bytes) = if input == "-" {
+        let mut bytes = vec![];
+        stdin.read_to_end(&mut bytes).map_err(|error| format!("could not read the standard input: {}", error))?;
+        ("<stdin>", bytes)
+    } else {
+        (input, fs::read(input).map_err(|error| format!("could not read `{}`: {}", input, error))?)
+    };
+    if bytes.starts_with(bytecode::MAGIC) {
+        let module = bytecode::Module::from_ssbc(&bytes).map_err(|message| format!("could not load `{}`: {}", name, message))?;
+        return Ok((module.source.clone(), Some(module)));
+    }
+    let source = String::from_utf8(bytes).map_err(|_| format!("could not read `{}`: stream did not contain valid UTF-8", name))?;
+    Ok((source, None))
+}
+
+// Runs the stages up to `emit` on one program, or the program itself with `--run`, appending
+// what the last one produced to `output` and the parser steps to `trace` if they are traced
+fn compile(source: &str, options: &Options, output: &mut Vec<u8>, trace: &mut String) -> Result<(), Failure> {
+    let emit = options.emit;
+    let tokens = rulox::lex(source);
+    if emit == Emit::Tokens {
+        for spanned in tokens.iter() {
+            writeln!(output, "{}:{} {:?}", spanned.span.start.line, spanned.span.start.column, spanned.token).unwrap();
+        }
+    }
+    let lexical_errors = tokens.iter().any(|spanned| matches!(spanned.token, Token::ERROR(_)));
+    if emit == Emit::Tokens && !lexical_errors {
+        return Ok(());
+    }
+
+    // parsing reports the lexical errors
+    let parsed = match options.trace {
+        Some(format) => {
+            let (parsed, parser_trace) = rulox::parse_traced(source, Trace::new(format, options.trace_stack));
+            trace.push_str(parser_trace.output());
+            parsed
+        },
+        None => rulox::parse(source),
+    };
+    let program = rulox::check(&parsed?);
+    if let Some(call) = &options.run {
+        let (function, args) = rulox::parse_call(&program, call).map_err(Failure::Usage)?;
+        match emit {
+            Emit::C => write!(output, "{}", rulox::emit_c(&program, Some((&function, &args)))).unwrap(),
+            Emit::Llvm => write!(output, "{}", rulox::emit_llvm(&program, Some((&function, &args)))).unwrap(),
+            _ => {
+                let value = rulox::run(&program, &function, args)?;
+                writeln!(output, "{}", value).unwrap();
+            },
+        }
+        return Ok(());
+    }
+    match emit {
+        Emit::Ast => writeln!(output, "{:#?}", program.program()).unwrap(),
+        Emit::Symbols => write_symbols(program.program(), output),
+        Emit::Ir => write!(output, "{}", rulox::lower(&program)).unwrap(),
+        Emit::Bytecode => write!(output, "{}", rulox::compile(&program)).unwrap(),
+        Emit::Ssbc => output.extend(rulox::compile(&program).to_ssbc()),
+        Emit::C => write!(output, "{}", rulox::emit_c(&program, None)).unwrap(),
+        Emit::Llvm => write!(output, "{}", rulox::emit_llvm(&program, None)).unwrap(),
+        Emit::Wat => write!(output, "{}", rulox::emit_wat(&program)?).unwrap(),
+        Emit::Tokens => ()
+    }
+    Ok(())
+}
+
+// What can be done with a program that is already compiled: run it on the VM or write it again
+fn run_module(module: &bytecode::Module, options: &Options, output: &mut Vec<u8>) -> Result<(), Failure> {
+    if let Some(call) = &options.run {
+        let (function, args) = rulox::parse_bytecode_call(module, call).map_err(Failure::Usage)?;
+        let value = rulox::run_bytecode(module, &function, args)?;
+        writeln!(output, "{}", value).unwrap();
+        return Ok(());
+    }
+    match options.emit {
+        Emit::Bytecode => write!(output, "{}", module).unwrap(),
+        Emit::Ssbc => output.extend(module.to_ssbc()),
+        _ => return Err(Failure::Usage(String::from("the file holds bytecode, which can only be run or emitted as bytecode or ssbc"))),
+    }
+    Ok(())
+}
+
+/*
+ * One line per declared name, indented by how deeply its scope is nested:
+ *
+ * 1:6 type Id = integer
+ * 2:10 function f : (integer) -> integer
+ *   2:12 param n : integer
+ */
+fn write_symbols(program: &ast::Program, output: &mut Vec<u8>) {
+    for declaration in program.declarations.iter() {
+        match declaration {
+            ast::Declaration::Type(type_decl) => {
+                let position = type_decl.name.span.start;
+                writeln!(output, "{}:{} type {} = {}", position.line, position.column, type_decl.name.name, type_decl.type_).unwrap();
+            },
+            ast::Declaration::Function(function) => {
+                let position = function.name.span.start;
+                let params: Vec<String> = function.params.iter().map(|param| param.type_.type_.to_string()).collect();
+                writeln!(output, "{}:{} function {} : ({}) -> {}", position.line, position.column, function.name.name,
+                         params.join(", "), function.return_type.type_).unwrap();
+                for param in function.params.iter() {
+                    let position = param.name.span.start;
+                    writeln!(output, "  {}:{} param {} : {}", position.line, position.column, param.name.name, param.type_.type_).unwrap();
+                }
+                write_block_symbols(&function.body, 1, output);
+            },
+        }
+    }
+}
+
+fn write_block_symbols(block: &ast::Block, depth: usize, output: &mut Vec<u8>) {
+    let indent = "  ".repeat(depth);
+    for var in block.vars.iter() {
+        for name in var.names.iter() {
+            let position = name.span.start;
+            writeln!(output, "{}{}:{} var {} : {}", indent, position.line, position.column, name.name, var.type_.type_).unwrap();
+        }
+    }
+    for stmt in block.stmts.iter() {
+        write_stmt_symbols(stmt, depth, output);
+    }
+}
+
+fn write_stmt_symbols(stmt: &ast::Stmt, depth: usize, output: &mut Vec<u8>) {
+    match &stmt.kind {
+        ast::StmtKind::Block(block) => write_block_symbols(block, depth + 1, output),
+        ast::StmtKind::If { then_branch, else_branch, .. } => {
+            write_stmt_symbols(then_branch, depth, output);
+            if let Some(else_branch) = else_branch {
+                write_stmt_symbols(else_branch, depth, output);
+            }
+        },
+        ast::StmtKind::While { body, .. } | ast::StmtKind::DoWhile { body, .. } => write_stmt_symbols(body, depth, output),
+        _ => ()
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let code = run(&args, &mut io::stdin(), &mut io::stdout(), &mut io::stderr());
+    std::process::exit(code);
 }
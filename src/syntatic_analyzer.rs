@@ -4,18 +4,57 @@ pub mod terminals;
 pub mod nonterminals;
 pub mod token_stream;
 pub mod scope_analyzer;
+pub mod diagnostics;
+pub mod span;
+pub mod report;
+pub mod ast;
+mod ast_builder;
+pub mod flow_analyzer;
+pub mod trace;
+#[cfg(test)]
+pub mod lalr;
 
 use token_stream::TokenStream;
 use rules::Rules;
 use scope_analyzer::ScopeAnalyzer;
 use nonterminals::{Object, Kind};
+use diagnostics::{Diagnostic, ErrorCode, Phase, Subject};
+use span::Span;
+use ast_builder::Node;
+use trace::{Trace, Step};
+use crate::intermediate_code::layout;
+
+// Below this many shifts after a recovery, new syntax errors are considered cascades and not reported
+const ERROR_SILENCE_SHIFTS: usize = 3;
+
+// How big the semantic stack, the scope and the loop nesting were when the matching state was pushed
+#[derive(Clone, Copy)]
+struct StackMark {
+    semantic_len: usize,
+    scope_depth: usize,
+    loop_depth: usize,
+}
 
 pub struct StateMachine {
     program: String,
     token_sec: String,
     syntatic_stack: Vec<usize>, 
+    // span_stack[i] is the source covered by the symbol that led to syntatic_stack[i]
+    span_stack: Vec<Span>,
+    // marks[i] lets error recovery put the other stacks back as they were when syntatic_stack[i] was pushed
+    marks: Vec<StackMark>,
+    // spans of the right hand side symbols of the rule being reduced
+    rhs_spans: Vec<Span>,
+    reduction_span: Span,
     semantic_stack: Vec<nonterminals::AttribToken>,
-    scope_analyzer: ScopeAnalyzer
+    // ast_stack[i] is the tree built for the symbol that led to syntatic_stack[i]
+    ast_stack: Vec<Node>,
+    scope_analyzer: ScopeAnalyzer,
+    // loops around the statement being parsed, innermost last
+    loops: Vec<ast::LoopId>,
+    n_loops: u32,
+    diagnostics: Vec<Diagnostic>,
+    trace: Option<Trace>
 }
 
 impl StateMachine {
@@ -24,46 +63,83 @@ impl StateMachine {
             program,
             token_sec: String::new(), 
             syntatic_stack: vec![0; 1],
+            span_stack: vec![Span::default(); 1],
+            marks: vec![StackMark { semantic_len: 0, scope_depth: 1, loop_depth: 0 }; 1],
+            rhs_spans: vec![],
+            reduction_span: Span::default(),
             semantic_stack: vec![],
-            scope_analyzer: ScopeAnalyzer::new()
+            ast_stack: vec![Node::Empty],
+            scope_analyzer: ScopeAnalyzer::new(),
+            loops: vec![],
+            n_loops: 0,
+            diagnostics: vec![],
+            trace: None
         }
     }
 
-    
-    pub fn parse(&mut self) -> bool {
+    // Logs every step of the next parse, see trace.rs
+    pub fn set_trace(&mut self, trace: Trace) {
+        self.trace = Some(trace);
+    }
+
+    pub fn take_trace(&mut self) -> Option<Trace> {
+        self.trace.take()
+    }
+
+    // The tree is only handed out for programs without errors
+    #[cfg(test)]
+    pub fn parse(&mut self) -> Result<ast::Program, Vec<Diagnostic>> {
+        match self.parse_with_diagnostics() {
+            (Some(program), diagnostics) if diagnostics.is_empty() => Ok(program),
+            (_, diagnostics) => Err(diagnostics),
+        }
+    }
+
+    /*
+     * The tree of the program along with every error found, semantic errors leave a tree behind
+     * and erroneous nodes get the universal type. There is no tree when the parser could not
+     * recover from a syntax error before the end of the input.
+     */
+    pub fn parse_with_diagnostics(&mut self) -> (Option<ast::Program>, Vec<Diagnostic>) {
         let temp = self.program.clone();
         let mut token_stream = TokenStream::new(temp.chars());
         let mut curr_tok: terminals::Token;
+        let mut curr_span: Span;
         let mut action: i32;
         let mut reduction_rule: usize;
+        let mut shifts_since_error = ERROR_SILENCE_SHIFTS;
+        let mut last_error_offset: Option<usize> = None;
 
 
-        curr_tok = token_stream.next().unwrap();
+        terminals::SpannedToken { token: curr_tok, span: curr_span } = self.next_token(&mut token_stream);
         
 
         loop{
-            action = constants::ACTIONTABLE[*self.syntatic_stack.last().unwrap()][usize::from(curr_tok.clone())];
-
-            match curr_tok {
-                terminals::Token::ID(id) => {
-                    self.token_sec = id.to_string();
-                },
-                terminals::Token::CHARACTER(ch) => {
-                    self.token_sec = String::from(ch);
-                },
-                terminals::Token::STRINGVAL(str_val) => {
-                    self.token_sec = str_val.to_string();
-                },
-                terminals::Token::NUMERAL(num) => {
-                    self.token_sec = num.to_string();
-                },
-                _ => ()
-            }
+            let state = *self.syntatic_stack.last().unwrap();
+            action = Self::action(state, &curr_tok);
 
-            //dbg!(&curr_tok);
             if action > 0 {
+                self.trace(state, &curr_tok, curr_span, Step::Shift { to: usize::try_from(action).unwrap() });
+                // the literal is recorded when shifted, the lookahead may be another literal by the time it is reduced
+                let literal = match curr_tok {
+                    terminals::Token::ID(id) => Some(id.to_string()),
+                    terminals::Token::CHARACTER(ch) => Some(String::from(ch)),
+                    terminals::Token::STRINGVAL(str_val) => Some(str_val.to_string()),
+                    terminals::Token::NUMERAL(num) => Some(num.to_string()),
+                    _ => None
+                };
+                match literal {
+                    Some(literal) => {
+                        self.token_sec = literal.clone();
+                        self.ast_stack.push(Node::Terminal(literal));
+                    },
+                    None => self.ast_stack.push(Node::Empty)
+                }
                 self.syntatic_stack.push(usize::try_from(action).unwrap());
-                curr_tok = token_stream.next().unwrap();
+                self.span_stack.push(curr_span);
+                self.push_mark();
+                shifts_since_error += 1;
+                terminals::SpannedToken { token: curr_tok, span: curr_span } = self.next_token(&mut token_stream);
             }
             else if action < 0 {
                 /* 
@@ -75,55 +151,313 @@ impl StateMachine {
 
                 action = -action;
                 reduction_rule = usize::try_from(action-1).unwrap();
-                //dbg!(Rules::from(reduction_rule));
-                self.semantics(Rules::from(reduction_rule));
                 let new_length = self.syntatic_stack.len() - constants::RULELEN[reduction_rule];
+                self.rhs_spans = self.span_stack[new_length..].to_vec();
+                self.reduction_span = match (self.rhs_spans.first(), self.rhs_spans.last()) {
+                    (Some(first), Some(last)) => first.to(*last),
+                    _ => Span::empty(curr_span.start)
+                };
+                let rule = Rules::from(reduction_rule);
+                self.semantics(rule);
+                let children = self.ast_stack.split_off(new_length);
+                let node = self.build(rule, children);
+                self.ast_stack.push(node);
                 self.syntatic_stack.truncate(new_length);
+                self.span_stack.truncate(new_length);
+                self.marks.truncate(new_length);
 
                 // pushing the state after the transition with the non terminal
                 self.syntatic_stack.push(usize::try_from(
-                                    constants::ACTIONTABLE
-                                    [*self.syntatic_stack.last().unwrap()]
-                                    [constants::RULELEFT[reduction_rule].into_usize()]).unwrap());
+                                    constants::action(*self.syntatic_stack.last().unwrap(),
+                                                      constants::RULELEFT[reduction_rule].into_usize())).unwrap());
+                self.span_stack.push(self.reduction_span);
+                self.push_mark();
+                let goto = *self.syntatic_stack.last().unwrap();
+                self.trace(state, &curr_tok, curr_span, Step::Reduce { rule, length: constants::RULELEN[reduction_rule], goto });
             }
             else {
-                panic!("SYNTAX ERROR\n");
+                self.trace(state, &curr_tok, curr_span, Step::Error);
+                if shifts_since_error >= ERROR_SILENCE_SHIFTS {
+                    let diagnostic = self.syntax_error(&curr_tok, curr_span);
+                    self.diagnostics.push(diagnostic);
+                }
+                // Failing again on the token we resynchronized on means it can't be used, so it is dropped
+                if last_error_offset == Some(curr_span.start.offset) {
+                    if curr_tok == terminals::Token::DOLLAR {
+                        return (None, std::mem::take(&mut self.diagnostics));
+                    }
+                    self.trace(state, &curr_tok, curr_span, Step::Skip);
+                    terminals::SpannedToken { token: curr_tok, span: curr_span } = self.next_token(&mut token_stream);
+                }
+
+                /*
+                 * Panic mode recovery: tokens are skipped until one that usually ends a construct,
+                 * then states are popped until one that has an action for it.
+                 */
+                shifts_since_error = 0;
+                loop {
+                    if Self::is_synchronizing(&curr_tok) {
+                        if let Some(depth) = self.recovery_depth(usize::from(curr_tok.clone())) {
+                            self.restore(depth + 1);
+                            self.trace(self.syntatic_stack[depth], &curr_tok, curr_span, Step::Recover);
+                            break;
+                        }
+                        // A `;` nothing can take ends a broken statement. It is parsed as if there was
+                        // an `S -> error ;` rule so the enclosing block still has its statement.
+                        if curr_tok == terminals::Token::SEMICOLON {
+                            let statement = nonterminals::Token::S.into_usize();
+                            if let Some(depth) = self.recovery_depth(statement) {
+                                self.restore(depth + 1);
+                                let goto = constants::action(*self.syntatic_stack.last().unwrap(), statement);
+                                self.syntatic_stack.push(usize::try_from(goto).unwrap());
+                                self.span_stack.push(curr_span);
+                                self.semantic_stack.push(nonterminals::AttribToken::S);
+                                self.ast_stack.push(Node::Stmt(ast::Stmt { kind: ast::StmtKind::Error, span: curr_span }));
+                                self.push_mark();
+                                self.trace(self.syntatic_stack[depth], &curr_tok, curr_span, Step::ErrorStatement { goto: usize::try_from(goto).unwrap() });
+                                terminals::SpannedToken { token: curr_tok, span: curr_span } = self.next_token(&mut token_stream);
+                                // the broken statement is over, an error in the next one is not a cascade
+                                shifts_since_error = ERROR_SILENCE_SHIFTS;
+                                break;
+                            }
+                        }
+                    }
+                    if curr_tok == terminals::Token::DOLLAR {
+                        return (None, std::mem::take(&mut self.diagnostics));
+                    }
+                    self.trace(state, &curr_tok, curr_span, Step::Skip);
+                    terminals::SpannedToken { token: curr_tok, span: curr_span } = self.next_token(&mut token_stream);
+                }
+                last_error_offset = Some(curr_span.start.offset);
+                continue;
             }
             
-            if *self.syntatic_stack.last().unwrap() == 1 {
-                println!("FINISHED PARSING\n");
+            if *self.syntatic_stack.last().unwrap() == constants::ACCEPT_STATE {
+                self.trace(constants::ACCEPT_STATE, &curr_tok, curr_span, Step::Accept);
+                let program = match self.ast_stack.pop() {
+                    Some(Node::Program(program)) => Some(program),
+                    _ => None
+                };
+                return (program, std::mem::take(&mut self.diagnostics));
+            }
+        }
+    }
+
+    fn trace(&mut self, state: usize, lookahead: &terminals::Token, span: Span, step: Step) {
+        if let Some(trace) = &mut self.trace {
+            trace.step(state, lookahead, span, step, &self.semantic_stack);
+        }
+    }
+
+    fn action(state: usize, token: &terminals::Token) -> i32 {
+        constants::action(state, usize::from(token.clone()))
+    }
+
+    /*
+     * Whether the symbol in `column` would be shifted from the current stack. Reduce entries
+     * can't be trusted on their own because a state may be shared by several contexts and reduce
+     * on the union of their lookaheads, so the reductions are simulated on a copy of the stack.
+     */
+    fn would_shift(&self, column: usize) -> bool {
+        Self::shifts(&self.syntatic_stack, column)
+    }
+
+    // The deepest prefix of the stack that would shift the symbol in `column`, as the index of its top
+    fn recovery_depth(&self, column: usize) -> Option<usize> {
+        (0..self.syntatic_stack.len()).rev().find(|depth| Self::shifts(&self.syntatic_stack[..=*depth], column))
+    }
+
+    fn shifts(stack: &[usize], column: usize) -> bool {
+        let mut stack = stack.to_vec();
+        loop {
+            let action = constants::action(*stack.last().unwrap(), column);
+            if action > 0 {
+                return true;
+            }
+            if action == 0 {
+                return false;
+            }
+
+            let reduction_rule = usize::try_from(-action-1).unwrap();
+            let new_length = stack.len() - constants::RULELEN[reduction_rule];
+            stack.truncate(new_length);
+            let goto = constants::action(*stack.last().unwrap(), constants::RULELEFT[reduction_rule].into_usize());
+            stack.push(usize::try_from(goto).unwrap());
+            if *stack.last().unwrap() == constants::ACCEPT_STATE {
                 return true;
             }
         }
     }
 
+    // Terminals that would not have been a syntax error in place of the current token
+    fn expected_terminals(&self) -> Vec<&'static str> {
+        (terminals::FIRST_TERMINAL..constants::COLUMNS)
+            .filter(|column| self.would_shift(*column))
+            .map(terminals::terminal_name)
+            .collect()
+    }
+
+    fn syntax_error(&self, token: &terminals::Token, span: Span) -> Diagnostic {
+        let expected = self.expected_terminals();
+        let found = match token {
+            terminals::Token::DOLLAR => String::from("end of file"),
+            _ => format!("`{}`", &self.program[span.range()]),
+        };
+
+        let message = match expected.split_last() {
+            None => format!("unexpected {}", found),
+            Some((last, [])) => format!("expected {} but found {}", last, found),
+            Some((last, [first])) => format!("expected {} or {} but found {}", first, last, found),
+            Some((last, rest)) => format!("expected one of {} or {} but found {}", rest.join(", "), last, found),
+        };
+
+        Diagnostic::error(ErrorCode::SyntaxError, message, span)
+            .with_subject(Subject::UnexpectedToken {
+                expected: expected.iter().map(|name| name.to_string()).collect(),
+                found,
+            })
+    }
+
+    // The next token the grammar knows of, the lexical errors before it are reported and skipped
+    fn next_token<'a>(&mut self, token_stream: &mut TokenStream<'a>) -> terminals::SpannedToken<'a> {
+        loop {
+            let spanned = token_stream.next_spanned();
+            match spanned.token {
+                terminals::Token::ERROR(error) => self.diagnostics.push(error.diagnostic(spanned.span)),
+                _ => return spanned,
+            }
+        }
+    }
+
+    fn is_synchronizing(token: &terminals::Token) -> bool {
+        matches!(token, terminals::Token::SEMICOLON | terminals::Token::RIGHTBRACE |
+                        terminals::Token::FUNCTION | terminals::Token::TYPE |
+                        terminals::Token::DOLLAR)
+    }
+
+    fn push_mark(&mut self) {
+        self.marks.push(StackMark {
+            semantic_len: self.semantic_stack.len(),
+            scope_depth: self.scope_analyzer.depth(),
+            loop_depth: self.loops.len(),
+        });
+    }
+
+    // Pops every state above the first `len` ones, undoing what their reductions did
+    fn restore(&mut self, len: usize) {
+        let mark = self.marks[len - 1];
+        self.syntatic_stack.truncate(len);
+        self.span_stack.truncate(len);
+        self.ast_stack.truncate(len);
+        self.marks.truncate(len);
+        self.semantic_stack.truncate(mark.semantic_len);
+        self.scope_analyzer.truncate(mark.scope_depth);
+        self.loops.truncate(mark.loop_depth);
+    }
+
     fn check_types(&self, t1: nonterminals::Type, t2: nonterminals::Type) -> bool {
-        if t1 == t2 {
-            return true;
+        t1 == t2 || t1 == nonterminals::Type::Universal_ || t2 == nonterminals::Type::Universal_
+    }
+
+    // Span of the i-th symbol in the right hand side of the rule being reduced
+    fn rhs_span(&self, i: usize) -> Span {
+        self.rhs_spans.get(i).copied().unwrap_or(self.reduction_span)
+    }
+
+    fn error(&mut self, code: ErrorCode, message: String, subject: Subject, span: Span) {
+        self.diagnostics.push(Diagnostic::error(code, message, span).with_subject(subject));
+    }
+
+    // Reports the type `name` just declared if its values are too large to be laid out in memory
+    fn check_type_size(&mut self, name: &str) {
+        let type_ = match self.scope_analyzer.find(name) {
+            Some(Object(_, Kind::array(type_))) | Some(Object(_, Kind::struct_(type_))) => type_,
+            _ => return,
+        };
+        if layout::checked_size_of(&type_).is_none() {
+            self.error(ErrorCode::TypeTooLarge,
+                       format!("the values of type `{}` take more than {} bytes", name, layout::MAX_SIZE),
+                       Subject::Identifier(name.to_string()),
+                       self.rhs_span(1));
         }
+    }
+
+    // Returns false, after reporting the error, when the types don't match
+    fn expect_type(&mut self, found: nonterminals::Type, expected: nonterminals::Type, code: ErrorCode, context: &str, span: Span) -> bool {
+        if !self.check_types(found.clone(), expected.clone()) {
+            self.error(code,
+                       format!("{} expects {} but found {}", context, expected, found),
+                       Subject::Mismatch { expected, found },
+                       span);
+            return false;
+        }
+        true
+    }
+
+    // Reports an error unless `found` is one of the types arithmetic works on
+    fn expect_numeric(&mut self, found: nonterminals::Type, context: &str, span: Span) {
+        if !(self.check_types(found.clone(), nonterminals::Type::Int_) || self.check_types(found.clone(), nonterminals::Type::Real_)) {
+            self.error(ErrorCode::InvalidOperandType,
+                       format!("{} expects integer or real but found {}", context, found),
+                       Subject::Type(found),
+                       span);
+        }
+    }
+
+    // Adds a secondary label to the last reported error
+    fn label(&mut self, span: Span, message: String) {
+        if let Some(diagnostic) = self.diagnostics.pop() {
+            self.diagnostics.push(diagnostic.with_label(span, message));
+        }
+    }
 
-        return false;
+    fn label_param(&mut self, function: &str, index: usize, type_: &nonterminals::Type) {
+        let span = self.scope_analyzer.param_spans(function).and_then(|spans| spans.get(index)).copied();
+        if let Some(span) = span {
+            self.label(span, format!("parameter declared as {} here", type_));
+        }
+    }
+
+    // Name of the function being called; the stack holds `IDU MC` while its arguments are checked
+    fn callee_name(&self) -> String {
+        match self.semantic_stack.get(self.semantic_stack.len() - 2) {
+            Some(nonterminals::AttribToken::IDU(obj)) => obj.0.clone(),
+            _ => String::new()
+        }
+    }
+
+    fn callee_is_universal(&self) -> bool {
+        matches!(self.semantic_stack.last(), Some(nonterminals::AttribToken::MC(nonterminals::Type::Universal_, _)))
     }
 
     pub fn semantics(&mut self, reduction_rule: Rules) {
         match reduction_rule {
             Rules::IDD => {
                 let idd = nonterminals::AttribToken::IDD(Object(self.token_sec.clone(), nonterminals::Kind::no_kind_def));
-                if let Some(_) = self.scope_analyzer.search(self.token_sec.as_str()) {
-                    //dbg!("{:?}", &self.token_sec);
-                    panic!("IDENTIFIER REDECLARATION");
+                if self.scope_analyzer.search(self.token_sec.as_str()).is_some() {
+                    self.error(ErrorCode::IdentifierRedeclaration,
+                               format!("identifier `{}` is already declared in this scope", self.token_sec),
+                               Subject::Identifier(self.token_sec.clone()),
+                               self.reduction_span);
+                    if let Some(span) = self.scope_analyzer.declaration_span(self.token_sec.as_str()) {
+                        self.label(span, String::from("previously declared here"));
+                    }
                 } else {
-                    self.scope_analyzer.define(self.token_sec.clone());
-                    self.semantic_stack.push(idd);
+                    self.scope_analyzer.define(self.token_sec.clone(), self.reduction_span);
                 }
+                self.semantic_stack.push(idd);
             },
             Rules::IDU => {
                 if let Some(obj) = self.scope_analyzer.find(self.token_sec.as_str()) {
                     let idu = nonterminals::AttribToken::IDU(obj);
                     self.semantic_stack.push(idu);
                 } else {
-                    panic!("IDENTIFIER NOT DECLARED");
-                    //self.scope_analyzer.define(self.token_sec.clone());
+                    self.error(ErrorCode::IdentifierNotDeclared,
+                               format!("identifier `{}` is not declared", self.token_sec),
+                               Subject::Identifier(self.token_sec.clone()),
+                               self.reduction_span);
+                    let idu = nonterminals::AttribToken::IDU(Object(self.token_sec.clone(), Kind::universal));
+                    self.semantic_stack.push(idu);
                 }
             },
             Rules::ID => {
@@ -134,6 +468,10 @@ impl StateMachine {
                 self.scope_analyzer.new_block();
                 self.semantic_stack.push(nonterminals::AttribToken::NF);
             },
+            Rules::MW => {
+                self.loops.push(ast::LoopId(self.n_loops));
+                self.n_loops += 1;
+            },
             Rules::NB => {
                 self.scope_analyzer.new_block();
                 self.semantic_stack.push(nonterminals::AttribToken::NB);
@@ -154,6 +492,10 @@ impl StateMachine {
                 let t = nonterminals::AttribToken::T(nonterminals::Type::String_);
                 self.semantic_stack.push(t);
             },
+            Rules::T_REAL => {
+                let t = nonterminals::AttribToken::T(nonterminals::Type::Real_);
+                self.semantic_stack.push(t);
+            },
             Rules::T_IDU => {
                 let idu = self.semantic_stack.pop().unwrap();
                 if let nonterminals::AttribToken::IDU(obj) = idu {
@@ -174,8 +516,15 @@ impl StateMachine {
                             let t = nonterminals::AttribToken::T(type_);
                             self.semantic_stack.push(t);
                         }
+                        nonterminals::Kind::universal => {
+                            self.semantic_stack.push(nonterminals::AttribToken::T(nonterminals::Type::Universal_));
+                        }
                         _ => {
-                            panic!("EXPECTED IDENTIFIER WITH KIND TYPE");
+                            self.error(ErrorCode::ExpectedTypeIdentifier,
+                                       format!("`{}` is not a type", obj.0),
+                                       Subject::Identifier(obj.0.clone()),
+                                       self.reduction_span);
+                            self.semantic_stack.push(nonterminals::AttribToken::T(nonterminals::Type::Universal_));
                         }
                     }
                 }
@@ -229,7 +578,22 @@ impl StateMachine {
                 self.semantic_stack.push(string_);
             },
             Rules::NUM => {
-                let num_ = nonterminals::AttribToken::NUM(Object(String::new(), Kind::scalar(nonterminals::Type::Int_)), self.token_sec.parse::<i32>().unwrap());
+                // a numeral with a fractional part is a real, its value is only kept in the tree
+                let (type_, value) = if self.token_sec.contains('.') {
+                    (nonterminals::Type::Real_, terminals::real_value(&self.token_sec).is_finite().then_some(0))
+                } else {
+                    (nonterminals::Type::Int_, terminals::integer_value(&self.token_sec))
+                };
+                let num_ = match value {
+                    Some(value) => nonterminals::AttribToken::NUM(Object(String::new(), Kind::scalar(type_)), value),
+                    None => {
+                        self.error(ErrorCode::LiteralOutOfRange,
+                                   format!("{} literal `{}` is out of range", type_, self.token_sec),
+                                   Subject::Type(type_),
+                                   self.reduction_span);
+                        nonterminals::AttribToken::NUM(Object(String::new(), Kind::scalar(nonterminals::Type::Universal_)), 0)
+                    }
+                };
                 self.semantic_stack.push(num_);
             },
             Rules::DT_ARRAY => {
@@ -238,10 +602,26 @@ impl StateMachine {
                 let idd = self.semantic_stack.pop().unwrap();
 
                 if let nonterminals::AttribToken::T(type_) = t {
-                    if let nonterminals::AttribToken::NUM(_, n_elements) = num {
+                    if let nonterminals::AttribToken::NUM(num_obj, n_elements) = num {
                         if let nonterminals::AttribToken::IDD(obj) = idd {
                             let name = obj.0;
+                            if num_obj.1 == Kind::scalar(nonterminals::Type::Real_) {
+                                let size = &self.program[self.rhs_span(5).range()];
+                                self.error(ErrorCode::ArraySizeNotInteger,
+                                           format!("array `{}` must have an integer size, found {}", name, size),
+                                           Subject::Identifier(name.clone()),
+                                           self.rhs_span(5));
+                            }
+                            // a literal out of range was already reported and counts as 0
+                            else if num_obj.1 == Kind::scalar(nonterminals::Type::Int_) && n_elements <= 0 {
+                                self.error(ErrorCode::ArraySizeNotPositive,
+                                           format!("array `{}` must have a positive size, found {}", name, n_elements),
+                                           Subject::Identifier(name.clone()),
+                                           self.rhs_span(5));
+                            }
+                            let n_elements = u32::try_from(n_elements).unwrap_or(0);
                             self.scope_analyzer.array_type_decl(name.as_str(), n_elements, type_.clone());
+                            self.check_type_size(&name);
                             self.semantic_stack.push(nonterminals::AttribToken::DT);
                         }
                     }
@@ -313,6 +693,7 @@ impl StateMachine {
                         //    }
                         //}
                         self.scope_analyzer.struct_type_decl(name.as_str());
+                        self.check_type_size(&name);
                         self.semantic_stack.push(nonterminals::AttribToken::DT);
                     }
                 }
@@ -368,7 +749,9 @@ impl StateMachine {
                                 }
                             }
 
-                            self.scope_analyzer.func_type_decl(idd_obj.0.as_str(), type_, types_vec);
+                            // MF derives the empty string, the return type is the symbol right before it
+                            let return_type_span = *self.span_stack.last().unwrap();
+                            self.scope_analyzer.func_type_decl(idd_obj.0.as_str(), type_, return_type_span, types_vec);
 
                             self.semantic_stack.push(idd);
                             self.semantic_stack.push(_nb);
@@ -385,23 +768,113 @@ impl StateMachine {
                 let _t = self.semantic_stack.pop();
                 let _lp = self.semantic_stack.pop();
                 let _nb = self.semantic_stack.pop();
-                let _idd = self.semantic_stack.pop();
+                let idd = self.semantic_stack.pop();
+
+                // a statement lost to a syntax error may have been the return, the body is only checked without them
+                let function = self.reduction_span.range();
+                let broken = self.diagnostics.iter().any(|diagnostic| {
+                    matches!(diagnostic.code.phase(), Phase::Lexical | Phase::Syntactic) && function.contains(&diagnostic.span.start.offset)
+                });
+
+                // the tree of the body is still on top of the stack, the one of the function is built after this
+                if let (Some(nonterminals::AttribToken::IDD(obj)), Some(Node::Block(body))) = (idd, self.ast_stack.last()) {
+                    if let Some(Kind::function(return_type, _)) = self.scope_analyzer.find(obj.0.as_str()).map(|obj| obj.1) {
+                        if let Some(end) = flow_analyzer::missing_return(body).filter(|_| !broken) {
+                            self.error(ErrorCode::MissingReturn,
+                                       format!("function `{}` can reach the end of its body without returning {}", obj.0, return_type),
+                                       Subject::Identifier(obj.0.clone()),
+                                       end);
+                            self.label(self.rhs_span(1), format!("`{}` declared to return {} here", obj.0, return_type));
+                        }
+                    }
+                }
 
                 self.semantic_stack.push(nonterminals::AttribToken::DF);
                 self.scope_analyzer.end_block();
             },
             Rules::S_NB => {
+                let _b = self.semantic_stack.pop();
+                let _nb = self.semantic_stack.pop();
                 self.scope_analyzer.end_block();
                 self.semantic_stack.push(nonterminals::AttribToken::S);
             },
+            Rules::S_BREAK | Rules::S_CONTINUE => {
+                if self.loops.is_empty() {
+                    let (code, keyword) = match reduction_rule {
+                        Rules::S_BREAK => (ErrorCode::BreakOutsideLoop, "break"),
+                        _ => (ErrorCode::ContinueOutsideLoop, "continue"),
+                    };
+                    self.diagnostics.push(Diagnostic::error(code, format!("`{}` outside of a loop", keyword), self.reduction_span));
+                }
+                self.semantic_stack.push(nonterminals::AttribToken::S);
+            },
+            Rules::S_RETURN => {
+                let e = self.semantic_stack.pop().unwrap();
+
+                if let nonterminals::AttribToken::E(type_) = e {
+                    match self.scope_analyzer.enclosing_function().cloned() {
+                        Some(function) => {
+                            let context = format!("`return` in `{}`", function.name);
+                            if !self.expect_type(type_, function.return_type.clone(), ErrorCode::ReturnTypeMismatch, context.as_str(), self.rhs_span(1)) {
+                                self.label(function.return_type_span, format!("return type {} declared here", function.return_type));
+                            }
+                        },
+                        None => {
+                            self.error(ErrorCode::ReturnOutsideFunction,
+                                       String::from("`return` outside of a function"),
+                                       Subject::Type(type_),
+                                       self.reduction_span);
+                        }
+                    }
+                    self.semantic_stack.push(nonterminals::AttribToken::S);
+                }
+            },
+            Rules::B => {
+                let _ls = self.semantic_stack.pop();
+                let _ldv = self.semantic_stack.pop();
+                self.semantic_stack.push(nonterminals::AttribToken::B);
+            },
+            Rules::LDV_LDV => {
+                let _dv = self.semantic_stack.pop();
+                let _ldv = self.semantic_stack.pop();
+                self.semantic_stack.push(nonterminals::AttribToken::LDV);
+            },
+            Rules::LDV_DV => {
+                let _dv = self.semantic_stack.pop();
+                self.semantic_stack.push(nonterminals::AttribToken::LDV);
+            },
+            Rules::LS_LS => {
+                let _s = self.semantic_stack.pop();
+                let _ls = self.semantic_stack.pop();
+                self.semantic_stack.push(nonterminals::AttribToken::LS);
+            },
+            Rules::LS_S => {
+                let _s = self.semantic_stack.pop();
+                self.semantic_stack.push(nonterminals::AttribToken::LS);
+            },
+            Rules::DE_DF | Rules::DE_DT => {
+                let _d = self.semantic_stack.pop();
+                self.semantic_stack.push(nonterminals::AttribToken::DE);
+            },
+            Rules::LDE_LDE => {
+                let _de = self.semantic_stack.pop();
+                let _lde = self.semantic_stack.pop();
+                self.semantic_stack.push(nonterminals::AttribToken::LDE);
+            },
+            Rules::LDE_DE => {
+                let _de = self.semantic_stack.pop();
+                self.semantic_stack.push(nonterminals::AttribToken::LDE);
+            },
+            Rules::P => {
+                let _lde = self.semantic_stack.pop();
+                self.semantic_stack.push(nonterminals::AttribToken::P);
+            },
             Rules::S_IF => {
                 let _s = self.semantic_stack.pop();
                 let e = self.semantic_stack.pop().unwrap();
                 
                 if let nonterminals::AttribToken::E(type_) = e {
-                    if !self.check_types(type_, nonterminals::Type::Bool_) {
-                        panic!("IF STATEMENT EXPECTS A BOOLEAN TYPE");
-                    }
+                    self.expect_type(type_, nonterminals::Type::Bool_, ErrorCode::ConditionNotBoolean, "`if` condition", self.rhs_span(2));
                     self.semantic_stack.push(nonterminals::AttribToken::S);
                 }
             },
@@ -411,29 +884,25 @@ impl StateMachine {
                 let e = self.semantic_stack.pop().unwrap();
 
                 if let nonterminals::AttribToken::E(type_) = e {
-                    if !self.check_types(type_, nonterminals::Type::Bool_) {
-                        panic!("IF ELSE STATEMENT EXPECTS A BOOLEAN TYPE");
-                    }
+                    self.expect_type(type_, nonterminals::Type::Bool_, ErrorCode::ConditionNotBoolean, "`if` condition", self.rhs_span(2));
                     self.semantic_stack.push(nonterminals::AttribToken::S);
                 }
             },
             Rules::S_WHILE => {
+                self.loops.pop();
                 let _s = self.semantic_stack.pop();
                 let e = self.semantic_stack.pop().unwrap();
                 if let nonterminals::AttribToken::E(type_) = e {
-                    if !self.check_types(type_, nonterminals::Type::Bool_) {
-                        panic!("WHILE STATEMENT EXPECTS A BOOLEAN TYPE");
-                    }
+                    self.expect_type(type_, nonterminals::Type::Bool_, ErrorCode::ConditionNotBoolean, "`while` condition", self.rhs_span(3));
                     self.semantic_stack.push(nonterminals::AttribToken::S);
                 }
             },
             Rules::S_DO_WHILE => {
+                self.loops.pop();
                 let e = self.semantic_stack.pop().unwrap();
                 let _s = self.semantic_stack.pop();
                 if let nonterminals::AttribToken::E(type_) = e {
-                    if !self.check_types(type_, nonterminals::Type::Bool_) {
-                        panic!("WHILE STATEMENT EXPECTS A BOOLEAN TYPE");
-                    }
+                    self.expect_type(type_, nonterminals::Type::Bool_, ErrorCode::ConditionNotBoolean, "`while` condition", self.rhs_span(5));
                     self.semantic_stack.push(nonterminals::AttribToken::S);
                 }
             },
@@ -443,8 +912,8 @@ impl StateMachine {
 
                 if let nonterminals::AttribToken::E(type_e) = e {
                     if let nonterminals::AttribToken::LV(type_lv) = lv {
-                        if !self.check_types(type_e, type_lv) {
-                            panic!("ASSINGMENT TYPE MISMATCH");
+                        if !self.expect_type(type_e, type_lv.clone(), ErrorCode::AssignmentTypeMismatch, "assignment", self.rhs_span(2)) {
+                            self.label(self.rhs_span(0), format!("left value has type {}", type_lv));
                         }
                         self.semantic_stack.push(nonterminals::AttribToken::S);
                     }
@@ -456,12 +925,8 @@ impl StateMachine {
 
                 if let nonterminals::AttribToken::L(type_l) = l {
                     if let nonterminals::AttribToken::E(type_e) = e {
-                        if !self.check_types(type_l, nonterminals::Type::Bool_) {
-                            panic!("AND EXPRESSION FIRST ELEMENT SHOULD BE BOOL TYPE");
-                        }
-                        if !self.check_types(type_e, nonterminals::Type::Bool_) {
-                            panic!("AND EXPRESSION SECOND ELEMENT SHOULD BE BOOL TYPE");
-                        }
+                        self.expect_type(type_e, nonterminals::Type::Bool_, ErrorCode::InvalidOperandType, "`&&` left operand", self.rhs_span(0));
+                        self.expect_type(type_l, nonterminals::Type::Bool_, ErrorCode::InvalidOperandType, "`&&` right operand", self.rhs_span(2));
 
                         self.semantic_stack.push(nonterminals::AttribToken::E(nonterminals::Type::Bool_));
                     }
@@ -473,12 +938,8 @@ impl StateMachine {
 
                 if let nonterminals::AttribToken::L(type_l) = l {
                     if let nonterminals::AttribToken::E(type_e) = e {
-                        if !self.check_types(type_l, nonterminals::Type::Bool_) {
-                            panic!("OR EXPRESSION FIRST ELEMENT SHOULD BE BOOL TYPE");
-                        }
-                        if !self.check_types(type_e, nonterminals::Type::Bool_) {
-                            panic!("OR EXPRESSION SECOND ELEMENT SHOULD BE BOOL TYPE");
-                        }
+                        self.expect_type(type_e, nonterminals::Type::Bool_, ErrorCode::InvalidOperandType, "`||` left operand", self.rhs_span(0));
+                        self.expect_type(type_l, nonterminals::Type::Bool_, ErrorCode::InvalidOperandType, "`||` right operand", self.rhs_span(2));
 
                         self.semantic_stack.push(nonterminals::AttribToken::E(nonterminals::Type::Bool_));
                     }
@@ -496,8 +957,8 @@ impl StateMachine {
                 
                 if let nonterminals::AttribToken::R(type_r) = r {
                     if let nonterminals::AttribToken::L(type_l) = l {
-                        if !self.check_types(type_r, type_l) {
-                            panic!("LESS THAN COMPARISONS TYPE MISMATCH");
+                        if !self.expect_type(type_r, type_l.clone(), ErrorCode::OperandTypeMismatch, "`<` right operand", self.rhs_span(2)) {
+                            self.label(self.rhs_span(0), format!("left operand has type {}", type_l));
                         }
                         self.semantic_stack.push(nonterminals::AttribToken::L(nonterminals::Type::Bool_));
                     }
@@ -509,8 +970,8 @@ impl StateMachine {
                 
                 if let nonterminals::AttribToken::R(type_r) = r {
                     if let nonterminals::AttribToken::L(type_l) = l {
-                        if !self.check_types(type_r, type_l) {
-                            panic!("GREATER THAN COMPARISONS TYPE MISMATCH");
+                        if !self.expect_type(type_r, type_l.clone(), ErrorCode::OperandTypeMismatch, "`>` right operand", self.rhs_span(2)) {
+                            self.label(self.rhs_span(0), format!("left operand has type {}", type_l));
                         }
                         self.semantic_stack.push(nonterminals::AttribToken::L(nonterminals::Type::Bool_));
                     }
@@ -522,8 +983,8 @@ impl StateMachine {
                 
                 if let nonterminals::AttribToken::R(type_r) = r {
                     if let nonterminals::AttribToken::L(type_l) = l {
-                        if !self.check_types(type_r, type_l) {
-                            panic!("LESS OR EQUAL COMPARISONS TYPE MISMATCH");
+                        if !self.expect_type(type_r, type_l.clone(), ErrorCode::OperandTypeMismatch, "`<=` right operand", self.rhs_span(2)) {
+                            self.label(self.rhs_span(0), format!("left operand has type {}", type_l));
                         }
                         self.semantic_stack.push(nonterminals::AttribToken::L(nonterminals::Type::Bool_));
                     }
@@ -535,8 +996,8 @@ impl StateMachine {
                 
                 if let nonterminals::AttribToken::R(type_r) = r {
                     if let nonterminals::AttribToken::L(type_l) = l {
-                        if !self.check_types(type_r, type_l) {
-                            panic!("GREATER OR EQUAL COMPARISONS TYPE MISMATCH");
+                        if !self.expect_type(type_r, type_l.clone(), ErrorCode::OperandTypeMismatch, "`>=` right operand", self.rhs_span(2)) {
+                            self.label(self.rhs_span(0), format!("left operand has type {}", type_l));
                         }
                         self.semantic_stack.push(nonterminals::AttribToken::L(nonterminals::Type::Bool_));
                     }
@@ -548,8 +1009,8 @@ impl StateMachine {
                 
                 if let nonterminals::AttribToken::R(type_r) = r {
                     if let nonterminals::AttribToken::L(type_l) = l {
-                        if !self.check_types(type_r, type_l) {
-                            panic!("EQUAL EQUAL COMPARISONS TYPE MISMATCH");
+                        if !self.expect_type(type_r, type_l.clone(), ErrorCode::OperandTypeMismatch, "`==` right operand", self.rhs_span(2)) {
+                            self.label(self.rhs_span(0), format!("left operand has type {}", type_l));
                         }
                         self.semantic_stack.push(nonterminals::AttribToken::L(nonterminals::Type::Bool_));
                     }
@@ -561,8 +1022,8 @@ impl StateMachine {
                 
                 if let nonterminals::AttribToken::R(type_r) = r {
                     if let nonterminals::AttribToken::L(type_l) = l {
-                        if !self.check_types(type_r, type_l) {
-                            panic!("NOT EQUAL COMPARISONS TYPE MISMATCH");
+                        if !self.expect_type(type_r, type_l.clone(), ErrorCode::OperandTypeMismatch, "`!=` right operand", self.rhs_span(2)) {
+                            self.label(self.rhs_span(0), format!("left operand has type {}", type_l));
                         }
                         self.semantic_stack.push(nonterminals::AttribToken::L(nonterminals::Type::Bool_));
                     }
@@ -581,13 +1042,17 @@ impl StateMachine {
 
                 if let nonterminals::AttribToken::Y(type_y) = y {
                     if let nonterminals::AttribToken::R(type_r) = r {
-                        if !self.check_types(type_r.clone(), type_y) {
-                            panic!("ADDITION TYPE MISMATCH");
+                        if !self.expect_type(type_y, type_r.clone(), ErrorCode::OperandTypeMismatch, "`+` right operand", self.rhs_span(2)) {
+                            self.label(self.rhs_span(0), format!("left operand has type {}", type_r));
                         }
 
                         if !(self.check_types(type_r.clone(), nonterminals::Type::Int_) || 
+                             self.check_types(type_r.clone(), nonterminals::Type::Real_) ||
                              self.check_types(type_r.clone(), nonterminals::Type::String_)) {
-                            panic!("ADDITION ONLY TAKES INTEGER OR STRING ARGS");
+                            self.error(ErrorCode::InvalidOperandType,
+                                       format!("`+` expects integer, real or string operands but found {}", type_r),
+                                       Subject::Type(type_r.clone()),
+                                       self.rhs_span(0));
                         }
 
                         self.semantic_stack.push(nonterminals::AttribToken::R(type_r));
@@ -600,13 +1065,10 @@ impl StateMachine {
 
                 if let nonterminals::AttribToken::Y(type_y) = y {
                     if let nonterminals::AttribToken::R(type_r) = r {
-                        if !self.check_types(type_r.clone(), type_y) {
-                            panic!("SUBTRACTION TYPE MISMATCH");
-                        }
-
-                        if !self.check_types(type_r.clone(), nonterminals::Type::Int_) {
-                            panic!("SUBTRACTION ONLY TAKES INTEGER ARGS");
+                        if !self.expect_type(type_y, type_r.clone(), ErrorCode::OperandTypeMismatch, "`-` right operand", self.rhs_span(2)) {
+                            self.label(self.rhs_span(0), format!("left operand has type {}", type_r));
                         }
+                        self.expect_numeric(type_r.clone(), "`-`", self.rhs_span(0));
 
                         self.semantic_stack.push(nonterminals::AttribToken::R(type_r));
                     }
@@ -624,13 +1086,10 @@ impl StateMachine {
                 
                 if let nonterminals::AttribToken::F(type_f) = f {
                     if let nonterminals::AttribToken::Y(type_y) = y {
-                        if !self.check_types(type_f, type_y.clone()) {
-                            panic!("MULTIPLICATION TYPE MISMATCH");
-                        }
-
-                        if !self.check_types(type_y.clone(), nonterminals::Type::Int_) {
-                            panic!("MULTIPLICATION ONLY TAKES INT TYPES");
+                        if !self.expect_type(type_f, type_y.clone(), ErrorCode::OperandTypeMismatch, "`*` right operand", self.rhs_span(2)) {
+                            self.label(self.rhs_span(0), format!("left operand has type {}", type_y));
                         }
+                        self.expect_numeric(type_y.clone(), "`*`", self.rhs_span(0));
 
                         self.semantic_stack.push(nonterminals::AttribToken::Y(type_y));
                     }
@@ -642,13 +1101,10 @@ impl StateMachine {
                 
                 if let nonterminals::AttribToken::F(type_f) = f {
                     if let nonterminals::AttribToken::Y(type_y) = y{
-                        if !self.check_types(type_f, type_y.clone()) {
-                            panic!("DIVISION TYPE MISMATCH");
-                        }
-
-                        if !self.check_types(type_y.clone(), nonterminals::Type::Int_) {
-                            panic!("DIVISION ONLY TAKES INT TYPES");
+                        if !self.expect_type(type_f, type_y.clone(), ErrorCode::OperandTypeMismatch, "`/` right operand", self.rhs_span(2)) {
+                            self.label(self.rhs_span(0), format!("left operand has type {}", type_y));
                         }
+                        self.expect_numeric(type_y.clone(), "`/`", self.rhs_span(0));
 
                         self.semantic_stack.push(nonterminals::AttribToken::Y(type_y));
                     }
@@ -669,36 +1125,28 @@ impl StateMachine {
             Rules::F_PLUSPLUS => {
                 let lv = self.semantic_stack.pop().unwrap();
                 if let nonterminals::AttribToken::LV(type_) = lv {
-                    if !self.check_types(type_, nonterminals::Type::Int_) {
-                        panic!("LEFT INCREMENT INVALID TYPE");
-                    }
+                    self.expect_type(type_, nonterminals::Type::Int_, ErrorCode::InvalidOperandType, "prefix `++`", self.rhs_span(1));
                     self.semantic_stack.push(nonterminals::AttribToken::F(nonterminals::Type::Int_));
                 }
             },
             Rules::F_MINUSMINUS => {
                 let lv = self.semantic_stack.pop().unwrap();
                 if let nonterminals::AttribToken::LV(type_) = lv {
-                    if !self.check_types(type_, nonterminals::Type::Int_) {
-                        panic!("LEFT DECREMENT INVALID TYPE");
-                    }
+                    self.expect_type(type_, nonterminals::Type::Int_, ErrorCode::InvalidOperandType, "prefix `--`", self.rhs_span(1));
                     self.semantic_stack.push(nonterminals::AttribToken::F(nonterminals::Type::Int_));
                 }
             },
             Rules::F_LV_PLUSPLUS => {
                 let lv = self.semantic_stack.pop().unwrap();
                 if let nonterminals::AttribToken::LV(type_) = lv {
-                    if !self.check_types(type_, nonterminals::Type::Int_) {
-                        panic!("RIGHT INCREMENT INVALID TYPE");
-                    }
+                    self.expect_type(type_, nonterminals::Type::Int_, ErrorCode::InvalidOperandType, "postfix `++`", self.rhs_span(0));
                     self.semantic_stack.push(nonterminals::AttribToken::F(nonterminals::Type::Int_));
                 }
             },
             Rules::F_LV_MINUSMINUS => {
                 let lv = self.semantic_stack.pop().unwrap();
                 if let nonterminals::AttribToken::LV(type_) = lv {
-                    if !self.check_types(type_, nonterminals::Type::Int_) {
-                        panic!("RIGHT DECREMENT INVALID TYPE");
-                    }
+                    self.expect_type(type_, nonterminals::Type::Int_, ErrorCode::InvalidOperandType, "postfix `--`", self.rhs_span(0));
                     self.semantic_stack.push(nonterminals::AttribToken::F(nonterminals::Type::Int_));
                 }
             },
@@ -711,18 +1159,15 @@ impl StateMachine {
             Rules::F_NEGATIVE => {
                 let f = self.semantic_stack.pop().unwrap();
                 if let nonterminals::AttribToken::F(type_) = f {
-                    if !self.check_types(type_, nonterminals::Type::Int_) {
-                        panic!("NEGATIVE OPERATOR ONLY TAKES INT TYPE");
-                    }
-                    self.semantic_stack.push(nonterminals::AttribToken::F(nonterminals::Type::Int_));
+                    self.expect_numeric(type_.clone(), "unary `-`", self.rhs_span(1));
+                    let type_ = if type_ == nonterminals::Type::Real_ { type_ } else { nonterminals::Type::Int_ };
+                    self.semantic_stack.push(nonterminals::AttribToken::F(type_));
                 }
             },
             Rules::F_NOT => {
                 let f = self.semantic_stack.pop().unwrap();
                 if let nonterminals::AttribToken::F(type_) = f {
-                    if !self.check_types(type_, nonterminals::Type::Bool_) {
-                        panic!("NOT OPERATOR ONLY TAKES BOOL TYPE");
-                    }
+                    self.expect_type(type_, nonterminals::Type::Bool_, ErrorCode::InvalidOperandType, "`!`", self.rhs_span(1));
                     self.semantic_stack.push(nonterminals::AttribToken::F(nonterminals::Type::Bool_));
                 }
             },
@@ -743,8 +1188,20 @@ impl StateMachine {
                 self.semantic_stack.push(nonterminals::AttribToken::F(nonterminals::Type::String_));
             },
             Rules::F_NUM => {
-                self.semantic_stack.pop();
-                self.semantic_stack.push(nonterminals::AttribToken::F(nonterminals::Type::Int_));
+                if let Some(nonterminals::AttribToken::NUM(Object(_, Kind::scalar(type_)), _)) = self.semantic_stack.pop() {
+                    self.semantic_stack.push(nonterminals::AttribToken::F(type_));
+                }
+            },
+            Rules::F_TO_INTEGER | Rules::F_TO_REAL => {
+                let e = self.semantic_stack.pop().unwrap();
+                if let nonterminals::AttribToken::E(type_) = e {
+                    let (context, target) = match reduction_rule {
+                        Rules::F_TO_INTEGER => ("`integer` conversion", nonterminals::Type::Int_),
+                        _ => ("`real` conversion", nonterminals::Type::Real_),
+                    };
+                    self.expect_numeric(type_, context, self.rhs_span(2));
+                    self.semantic_stack.push(nonterminals::AttribToken::F(target));
+                }
             },
             Rules::LV_STRUCT => {
                 let id = self.semantic_stack.pop().unwrap();
@@ -758,21 +1215,35 @@ impl StateMachine {
                             // other types later.
                             let mut found_field = false;
                             let field_name_target = id_obj.0;
+                            let struct_type = nonterminals::Type::Struct_type_(boxed_obj_vec.clone());
                             for obj in boxed_obj_vec.iter() {
                                  if field_name_target == obj.0 {
-                                    if let Kind::field(field_type) = obj.1.clone() {
-                                        self.semantic_stack.push(nonterminals::AttribToken::LV(field_type));    
-                                        found_field = true;
-                                        break;
-                                    }
+                                    // a field whose type failed to parse has no kind, the syntax error was reported
+                                    let field_type = match obj.1.clone() {
+                                        Kind::field(field_type) => field_type,
+                                        _ => nonterminals::Type::Universal_,
+                                    };
+                                    self.semantic_stack.push(nonterminals::AttribToken::LV(field_type));
+                                    found_field = true;
+                                    break;
                                  }
                             }
                             if !found_field {
-                                panic!("STRUCT FIELD NOT DECLARED");
+                                self.error(ErrorCode::StructFieldNotDeclared,
+                                           format!("no field `{}` in {}", field_name_target, struct_type),
+                                           Subject::Identifier(field_name_target),
+                                           self.rhs_span(2));
+                                self.semantic_stack.push(nonterminals::AttribToken::LV(nonterminals::Type::Universal_));
                             }
                         }
                         else {
-                            panic!("ERROR KIND IS NOT STRUCT");
+                            if type_ != nonterminals::Type::Universal_ {
+                                self.error(ErrorCode::NotAStruct,
+                                           format!("cannot access field `{}` of non-struct type {}", id_obj.0, type_),
+                                           Subject::Type(type_),
+                                           self.rhs_span(0));
+                            }
+                            self.semantic_stack.push(nonterminals::AttribToken::LV(nonterminals::Type::Universal_));
                         }
                     }
                 }
@@ -791,12 +1262,16 @@ impl StateMachine {
                             lv0 = nonterminals::AttribToken::LV(*type_);
                         }
                         else {
-                            panic!("INDEXED ACCESS ONLY TAKES STRING OR ARRAY ELEMENTS");
+                            if type_lv != nonterminals::Type::Universal_ {
+                                self.error(ErrorCode::NotIndexable,
+                                           format!("cannot index into a value of type {}", type_lv),
+                                           Subject::Type(type_lv),
+                                           self.rhs_span(0));
+                            }
+                            lv0 = nonterminals::AttribToken::LV(nonterminals::Type::Universal_);
                         }
 
-                        if !self.check_types(type_e, nonterminals::Type::Int_) {
-                            panic!("INDEXES MUST HAVE INTEGER TYPE");
-                        }
+                        self.expect_type(type_e, nonterminals::Type::Int_, ErrorCode::IndexNotInteger, "array index", self.rhs_span(2));
 
                         self.semantic_stack.push(lv0);
                     }
@@ -811,12 +1286,18 @@ impl StateMachine {
                         self.semantic_stack.push(lv);
                     }
                     else {
-                        panic!("ERROR LEFT VALUE KIND IS NOT VAR OR PARAM");
+                        if obj.1 != Kind::universal {
+                            self.error(ErrorCode::NotAVariable,
+                                       format!("`{}` is not a variable or parameter", obj.0),
+                                       Subject::Identifier(obj.0.clone()),
+                                       self.reduction_span);
+                        }
+                        self.semantic_stack.push(nonterminals::AttribToken::LV(nonterminals::Type::Universal_));
                     }
                 }
             },
             Rules::MC => {
-                let idu = self.semantic_stack.last().unwrap();
+                let idu = self.semantic_stack.last().unwrap().clone();
 
                 if let nonterminals::AttribToken::IDU(obj) = idu {
                     if let nonterminals::Kind::function(ret_type, params_types_vec) = obj.1.clone() {
@@ -824,7 +1305,15 @@ impl StateMachine {
                         self.semantic_stack.push(mc);
                     }
                     else {
-                        panic!("ERROR KIND IS NOT FUNCTION");
+                        if obj.1 != Kind::universal {
+                            // MC derives the empty string; the callee is the symbol right before it
+                            let span = *self.span_stack.last().unwrap();
+                            self.error(ErrorCode::NotAFunction,
+                                       format!("`{}` is not a function", obj.0),
+                                       Subject::Identifier(obj.0.clone()),
+                                       span);
+                        }
+                        self.semantic_stack.push(nonterminals::AttribToken::MC(nonterminals::Type::Universal_, vec![]));
                     }
                 }
             },
@@ -832,19 +1321,29 @@ impl StateMachine {
                 let e = self.semantic_stack.pop().unwrap();
                 
                 if let nonterminals::AttribToken::E(type_) = e {
-                    let mc = self.semantic_stack.last().unwrap();
+                    let mc = self.semantic_stack.last().unwrap().clone();
                     if let nonterminals::AttribToken::MC(_, params_type_vec) = mc {
                         // if we there is no such first parameter, we must error out
-                        let first_type = params_type_vec.get(0);
-                        if first_type.is_none() {
-                            panic!("EXPECTED NO ARGUMENTS");
-                        }
-
-                        if !self.check_types(type_, first_type.unwrap().clone()) {
-                            panic!("ERROR TYPE PARAM 1");
+                        if !self.callee_is_universal() {
+                            match params_type_vec.first() {
+                                None => {
+                                    let name = self.callee_name();
+                                    self.error(ErrorCode::TooManyArguments,
+                                               format!("function `{}` takes no arguments", name),
+                                               Subject::Identifier(name),
+                                               self.rhs_span(0));
+                                },
+                                Some(first_type) => {
+                                    let name = self.callee_name();
+                                    let context = format!("argument 1 of `{}`", name);
+                                    if !self.expect_type(type_, first_type.clone(), ErrorCode::ArgumentTypeMismatch, context.as_str(), self.rhs_span(0)) {
+                                        self.label_param(name.as_str(), 0, first_type);
+                                    }
+                                }
+                            }
                         }
 
-                        self.semantic_stack.push(nonterminals::AttribToken::LE(params_type_vec.clone(), 1));
+                        self.semantic_stack.push(nonterminals::AttribToken::LE(params_type_vec, 1));
                     }
                 }
             },
@@ -854,13 +1353,25 @@ impl StateMachine {
 
                 if let nonterminals::AttribToken::E(type_) = e {
                     if let nonterminals::AttribToken::LE(params_type_vec, cnt) = le1 {
-                        let nth_type = params_type_vec.get(cnt);
-                        if nth_type.is_none() {
-                            panic!("TOO MANY ARGUMENTS");
-                        }
-                        
-                        if !self.check_types(type_, nth_type.unwrap().clone()) {
-                            panic!("ERROR TYPE PARAM {:?}", cnt+1);
+                        if !self.callee_is_universal() {
+                            match params_type_vec.get(cnt) {
+                                None => {
+                                    let name = self.callee_name();
+                                    if cnt == params_type_vec.len() {
+                                        self.error(ErrorCode::TooManyArguments,
+                                                   format!("function `{}` takes {} argument(s)", name, params_type_vec.len()),
+                                                   Subject::Identifier(name),
+                                                   self.rhs_span(2));
+                                    }
+                                },
+                                Some(nth_type) => {
+                                    let name = self.callee_name();
+                                    let context = format!("argument {} of `{}`", cnt+1, name);
+                                    if !self.expect_type(type_, nth_type.clone(), ErrorCode::ArgumentTypeMismatch, context.as_str(), self.rhs_span(2)) {
+                                        self.label_param(name.as_str(), cnt, nth_type);
+                                    }
+                                }
+                            }
                         }
 
                         self.semantic_stack.push(nonterminals::AttribToken::LE(params_type_vec, cnt+1));
@@ -870,12 +1381,19 @@ impl StateMachine {
             Rules::F_FUNC_CALL => {
                 let le = self.semantic_stack.pop().unwrap();
                 let mc = self.semantic_stack.pop().unwrap();
-                let _idu = self.semantic_stack.pop().unwrap();
+                let idu = self.semantic_stack.pop().unwrap();
 
                 if let nonterminals::AttribToken::MC(ret_type, _) = mc {
                     if let nonterminals::AttribToken::LE(params_type_vec, cnt) = le {
-                        if params_type_vec.len() > cnt {
-                            panic!("ERROR TOO FEW ARGUMENTS");
+                        if params_type_vec.len() > cnt && ret_type != nonterminals::Type::Universal_ {
+                            let name = match &idu {
+                                nonterminals::AttribToken::IDU(obj) => obj.0.clone(),
+                                _ => String::new()
+                            };
+                            self.error(ErrorCode::TooFewArguments,
+                                       format!("function `{}` takes {} argument(s) but {} were supplied", name, params_type_vec.len(), cnt),
+                                       Subject::Identifier(name),
+                                       self.reduction_span);
                         }
 
                         self.semantic_stack.push(nonterminals::AttribToken::F(ret_type));
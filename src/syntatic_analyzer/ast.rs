@@ -0,0 +1,195 @@
+use crate::syntatic_analyzer::nonterminals::Type;
+use crate::syntatic_analyzer::span::Span;
+
+/*
+ * Abstract syntax tree built by the reduction actions of the parser.
+ *
+ * Expressions and left values carry the type the semantic analysis gave them, so passes
+ * that run after the parser don't have to redo the type checking. Constructs that were
+ * erroneous have type Type::Universal_.
+ */
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Program {
+    pub declarations: Vec<Declaration>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Declaration {
+    Type(TypeDecl),
+    Function(FunctionDecl),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ident {
+    pub name: String,
+    pub span: Span,
+}
+
+// A type as it was written in the source, together with the type it stands for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeExpr {
+    pub kind: TypeName,
+    pub type_: Type,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TypeName {
+    Integer,
+    Char,
+    Boolean,
+    String,
+    Real,
+    Named(Ident),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeDecl {
+    pub name: Ident,
+    pub kind: TypeDeclKind,
+    // the type being declared, e.g. the whole struct for `type P = struct { ... }`
+    pub type_: Type,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TypeDeclKind {
+    Array { size: u32, element: TypeExpr },
+    Struct { fields: Vec<FieldDecl> },
+    Alias(TypeExpr),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDecl {
+    pub names: Vec<Ident>,
+    pub type_: TypeExpr,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionDecl {
+    pub name: Ident,
+    pub params: Vec<Param>,
+    pub return_type: TypeExpr,
+    pub body: Block,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Param {
+    pub name: Ident,
+    pub type_: TypeExpr,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block {
+    pub vars: Vec<VarDecl>,
+    pub stmts: Vec<Stmt>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VarDecl {
+    pub names: Vec<Ident>,
+    pub type_: TypeExpr,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stmt {
+    pub kind: StmtKind,
+    pub span: Span,
+}
+
+// Numbers the loops of a program in the order they start
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LoopId(pub u32);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StmtKind {
+    If { condition: Expr, then_branch: Box<Stmt>, else_branch: Option<Box<Stmt>> },
+    While { id: LoopId, condition: Expr, body: Box<Stmt> },
+    DoWhile { id: LoopId, body: Box<Stmt>, condition: Expr },
+    Block(Block),
+    Assign { target: LValue, value: Expr },
+    // `break` and `continue` name the loop they leave
+    Break(LoopId),
+    Continue(LoopId),
+    Return(Expr),
+    // A statement the parser could not make sense of and skipped while recovering
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expr {
+    pub kind: ExprKind,
+    pub type_: Type,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExprKind {
+    Binary { op: BinaryOp, left: Box<Expr>, right: Box<Expr> },
+    Unary { op: UnaryOp, operand: Box<Expr> },
+    // `++x`, `x--`, ... change the variable, so their operand is a left value
+    IncDec { op: IncDecOp, target: LValue },
+    Call { function: Ident, args: Vec<Expr> },
+    // `integer(e)` and `real(e)`, the type converted to is the type of the expression
+    Convert(Box<Expr>),
+    LValue(LValue),
+    Integer(i32),
+    Real(f64),
+    Char(char),
+    Bool(bool),
+    String(String),
+}
+
+// Real literals are never NaN, so their equality is reflexive
+impl Eq for ExprKind {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    And,
+    Or,
+    Less,
+    Greater,
+    LessEqual,
+    GreaterEqual,
+    Equal,
+    NotEqual,
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOp {
+    Negate,
+    Not,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IncDecOp {
+    PreIncrement,
+    PreDecrement,
+    PostIncrement,
+    PostDecrement,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LValue {
+    pub kind: LValueKind,
+    pub type_: Type,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LValueKind {
+    Variable(Ident),
+    Field { base: Box<LValue>, field: Ident },
+    Index { base: Box<LValue>, index: Box<Expr> },
+}
@@ -0,0 +1,303 @@
+use crate::syntatic_analyzer::StateMachine;
+use crate::syntatic_analyzer::ast::*;
+use crate::syntatic_analyzer::nonterminals::{AttribToken, Kind, Object, Type};
+use crate::syntatic_analyzer::rules::Rules;
+use crate::syntatic_analyzer::terminals;
+
+/*
+ * Partially built tree kept in a stack parallel to the syntatic stack: node i is what was
+ * built for the symbol that led to syntatic_stack[i]. Terminals that carry a value push
+ * their text, MW pushes the loop it starts, the other terminals and markers push Empty.
+ */
+#[derive(Debug)]
+pub enum Node {
+    Empty,
+    Terminal(String),
+    Loop(LoopId),
+    Ident(Ident),
+    Idents(Vec<Ident>),
+    Type(TypeExpr),
+    Fields(Vec<FieldDecl>),
+    Params(Vec<Param>),
+    Var(VarDecl),
+    Vars(Vec<VarDecl>),
+    Block(Block),
+    Stmt(Stmt),
+    Stmts(Vec<Stmt>),
+    Expr(Expr),
+    Exprs(Vec<Expr>),
+    LValue(LValue),
+    Declaration(Declaration),
+    Declarations(Vec<Declaration>),
+    Program(Program),
+}
+
+// Moves the i-th right hand side node out, the grammar guarantees which variant it is
+macro_rules! take {
+    ($children:ident[$i:expr], $variant:ident) => {
+        match std::mem::replace(&mut $children[$i], Node::Empty) {
+            Node::$variant(value) => value,
+            node => unreachable!("expected {} node but found {:?}", stringify!($variant), node),
+        }
+    };
+}
+
+impl StateMachine {
+    // Type the semantic action of the rule being reduced gave to its left hand side
+    fn reduced_type(&self) -> Type {
+        match self.semantic_stack.last() {
+            Some(AttribToken::E(type_) | AttribToken::L(type_) | AttribToken::R(type_) |
+                 AttribToken::Y(type_) | AttribToken::F(type_) | AttribToken::LV(type_) |
+                 AttribToken::T(type_)) => type_.clone(),
+            _ => Type::Universal_
+        }
+    }
+
+    fn expr(&self, kind: ExprKind) -> Node {
+        Node::Expr(Expr { kind, type_: self.reduced_type(), span: self.reduction_span })
+    }
+
+    fn literal(&self, kind: ExprKind, type_: Type) -> Node {
+        Node::Expr(Expr { kind, type_, span: self.reduction_span })
+    }
+
+    fn lvalue(&self, kind: LValueKind) -> Node {
+        Node::LValue(LValue { kind, type_: self.reduced_type(), span: self.reduction_span })
+    }
+
+    fn stmt(&self, kind: StmtKind) -> Node {
+        Node::Stmt(Stmt { kind, span: self.reduction_span })
+    }
+
+    fn type_expr(&self, kind: TypeName, type_: Type) -> Node {
+        Node::Type(TypeExpr { kind, type_, span: self.reduction_span })
+    }
+
+    fn binary(&self, op: BinaryOp, mut children: Vec<Node>) -> Node {
+        let left = take!(children[0], Expr);
+        let right = take!(children[2], Expr);
+        self.expr(ExprKind::Binary { op, left: Box::new(left), right: Box::new(right) })
+    }
+
+    fn inc_dec(&self, op: IncDecOp, mut children: Vec<Node>, target: usize) -> Node {
+        let target = take!(children[target], LValue);
+        self.expr(ExprKind::IncDec { op, target })
+    }
+
+    fn unary(&self, op: UnaryOp, mut children: Vec<Node>) -> Node {
+        let operand = take!(children[1], Expr);
+        self.expr(ExprKind::Unary { op, operand: Box::new(operand) })
+    }
+
+    // Builds the node of the rule being reduced out of the nodes of its right hand side
+    pub(super) fn build(&self, rule: Rules, mut children: Vec<Node>) -> Node {
+        let span = self.reduction_span;
+        match rule {
+            Rules::P => Node::Program(Program { declarations: take!(children[0], Declarations), span }),
+            Rules::LDE_LDE => {
+                let mut declarations = take!(children[0], Declarations);
+                declarations.push(take!(children[1], Declaration));
+                Node::Declarations(declarations)
+            },
+            Rules::LDE_DE => Node::Declarations(vec![take!(children[0], Declaration)]),
+            Rules::DE_DF | Rules::DE_DT => children.swap_remove(0),
+            Rules::T_INT => self.type_expr(TypeName::Integer, Type::Int_),
+            Rules::T_CHAR => self.type_expr(TypeName::Char, Type::Char_),
+            Rules::T_BOOL => self.type_expr(TypeName::Boolean, Type::Bool_),
+            Rules::T_STRING => self.type_expr(TypeName::String, Type::String_),
+            Rules::T_REAL => self.type_expr(TypeName::Real, Type::Real_),
+            Rules::T_IDU => self.type_expr(TypeName::Named(take!(children[0], Ident)), self.reduced_type()),
+            Rules::DT_ARRAY => {
+                let element = take!(children[8], Type);
+                let size = match take!(children[5], Expr).kind {
+                    ExprKind::Integer(size) => u32::try_from(size).unwrap_or(0),
+                    _ => 0
+                };
+                Node::Declaration(Declaration::Type(TypeDecl {
+                    name: take!(children[1], Ident),
+                    type_: Type::Array_type_(size, Box::new(element.type_.clone())),
+                    kind: TypeDeclKind::Array { size, element },
+                    span,
+                }))
+            },
+            Rules::DT_STRUCT => {
+                let fields = take!(children[6], Fields);
+                let mut objects = vec![];
+                for field in fields.iter() {
+                    for name in field.names.iter() {
+                        objects.push(Object(name.name.clone(), Kind::field(field.type_.type_.clone())));
+                    }
+                }
+                Node::Declaration(Declaration::Type(TypeDecl {
+                    name: take!(children[1], Ident),
+                    type_: Type::Struct_type_(Box::new(objects)),
+                    kind: TypeDeclKind::Struct { fields },
+                    span,
+                }))
+            },
+            Rules::DT_ALIAS => {
+                let aliased = take!(children[3], Type);
+                Node::Declaration(Declaration::Type(TypeDecl {
+                    name: take!(children[1], Ident),
+                    type_: aliased.type_.clone(),
+                    kind: TypeDeclKind::Alias(aliased),
+                    span,
+                }))
+            },
+            Rules::DC_DC => {
+                let mut fields = take!(children[0], Fields);
+                fields.push(FieldDecl {
+                    names: take!(children[2], Idents),
+                    type_: take!(children[4], Type),
+                    span: self.rhs_span(2).to(self.rhs_span(4)),
+                });
+                Node::Fields(fields)
+            },
+            Rules::DC_LI => Node::Fields(vec![FieldDecl {
+                names: take!(children[0], Idents),
+                type_: take!(children[2], Type),
+                span,
+            }]),
+            Rules::DF => Node::Declaration(Declaration::Function(FunctionDecl {
+                name: take!(children[1], Ident),
+                params: take!(children[4], Params),
+                return_type: take!(children[7], Type),
+                body: take!(children[9], Block),
+                span,
+            })),
+            Rules::LP_LP => {
+                let mut params = take!(children[0], Params);
+                params.push(Param {
+                    name: take!(children[2], Ident),
+                    type_: take!(children[4], Type),
+                    span: self.rhs_span(2).to(self.rhs_span(4)),
+                });
+                Node::Params(params)
+            },
+            Rules::LP_IDD => Node::Params(vec![Param {
+                name: take!(children[0], Ident),
+                type_: take!(children[2], Type),
+                span,
+            }]),
+            Rules::B => Node::Block(Block {
+                vars: take!(children[1], Vars),
+                stmts: take!(children[2], Stmts),
+                span,
+            }),
+            Rules::LDV_LDV => {
+                let mut vars = take!(children[0], Vars);
+                vars.push(take!(children[1], Var));
+                Node::Vars(vars)
+            },
+            Rules::LDV_DV => Node::Vars(vec![take!(children[0], Var)]),
+            Rules::LS_LS => {
+                let mut stmts = take!(children[0], Stmts);
+                stmts.push(take!(children[1], Stmt));
+                Node::Stmts(stmts)
+            },
+            Rules::LS_S => Node::Stmts(vec![take!(children[0], Stmt)]),
+            Rules::DV => Node::Var(VarDecl {
+                names: take!(children[1], Idents),
+                type_: take!(children[3], Type),
+                span,
+            }),
+            Rules::LI_LI => {
+                let mut names = take!(children[0], Idents);
+                names.push(take!(children[2], Ident));
+                Node::Idents(names)
+            },
+            Rules::LI_IDD => Node::Idents(vec![take!(children[0], Ident)]),
+            Rules::S_IF_ELSE => self.stmt(StmtKind::If {
+                condition: take!(children[2], Expr),
+                then_branch: Box::new(take!(children[5], Stmt)),
+                else_branch: Some(Box::new(take!(children[8], Stmt))),
+            }),
+            Rules::S_IF => self.stmt(StmtKind::If {
+                condition: take!(children[2], Expr),
+                then_branch: Box::new(take!(children[5], Stmt)),
+                else_branch: None,
+            }),
+            Rules::S_WHILE => self.stmt(StmtKind::While {
+                id: take!(children[1], Loop),
+                condition: take!(children[3], Expr),
+                body: Box::new(take!(children[6], Stmt)),
+            }),
+            Rules::S_DO_WHILE => self.stmt(StmtKind::DoWhile {
+                id: take!(children[1], Loop),
+                body: Box::new(take!(children[2], Stmt)),
+                condition: take!(children[5], Expr),
+            }),
+            Rules::S_NB => self.stmt(StmtKind::Block(take!(children[1], Block))),
+            Rules::S_LV => self.stmt(StmtKind::Assign {
+                target: take!(children[0], LValue),
+                value: take!(children[2], Expr),
+            }),
+            // outside of a loop there is nothing to jump to, the error was already reported
+            Rules::S_BREAK => self.stmt(self.loops.last().map_or(StmtKind::Error, |id| StmtKind::Break(*id))),
+            Rules::S_CONTINUE => self.stmt(self.loops.last().map_or(StmtKind::Error, |id| StmtKind::Continue(*id))),
+            Rules::S_RETURN => self.stmt(StmtKind::Return(take!(children[1], Expr))),
+            Rules::E_AND => self.binary(BinaryOp::And, children),
+            Rules::E_OR => self.binary(BinaryOp::Or, children),
+            Rules::L_LESS_THAN => self.binary(BinaryOp::Less, children),
+            Rules::L_GREATER_THAN => self.binary(BinaryOp::Greater, children),
+            Rules::L_LESS_EQUAL => self.binary(BinaryOp::LessEqual, children),
+            Rules::L_GREATER_EQUAL => self.binary(BinaryOp::GreaterEqual, children),
+            Rules::L_EQUAL_EQUAL => self.binary(BinaryOp::Equal, children),
+            Rules::L_NOT_EQUAL => self.binary(BinaryOp::NotEqual, children),
+            Rules::R_PLUS => self.binary(BinaryOp::Add, children),
+            Rules::R_MINUS => self.binary(BinaryOp::Sub, children),
+            Rules::Y_TIMES => self.binary(BinaryOp::Mul, children),
+            Rules::Y_DIVIDE => self.binary(BinaryOp::Div, children),
+            // E -> L, L -> R, ... only change the precedence level, not the expression
+            Rules::E_L | Rules::L_R | Rules::R_Y | Rules::Y_F |
+            Rules::F_TRUE | Rules::F_FALSE | Rules::F_C | Rules::F_STR | Rules::F_NUM => children.swap_remove(0),
+            Rules::F_PAR_E_PAR => children.swap_remove(1),
+            Rules::F_LV => self.expr(ExprKind::LValue(take!(children[0], LValue))),
+            Rules::F_PLUSPLUS => self.inc_dec(IncDecOp::PreIncrement, children, 1),
+            Rules::F_MINUSMINUS => self.inc_dec(IncDecOp::PreDecrement, children, 1),
+            Rules::F_LV_PLUSPLUS => self.inc_dec(IncDecOp::PostIncrement, children, 0),
+            Rules::F_LV_MINUSMINUS => self.inc_dec(IncDecOp::PostDecrement, children, 0),
+            Rules::F_NEGATIVE => self.unary(UnaryOp::Negate, children),
+            Rules::F_NOT => self.unary(UnaryOp::Not, children),
+            Rules::F_FUNC_CALL => self.expr(ExprKind::Call {
+                function: take!(children[0], Ident),
+                args: take!(children[3], Exprs),
+            }),
+            Rules::F_TO_INTEGER | Rules::F_TO_REAL => self.expr(ExprKind::Convert(Box::new(take!(children[2], Expr)))),
+            Rules::LE_LE => {
+                let mut args = take!(children[0], Exprs);
+                args.push(take!(children[2], Expr));
+                Node::Exprs(args)
+            },
+            Rules::LE_E => Node::Exprs(vec![take!(children[0], Expr)]),
+            Rules::LV_STRUCT => self.lvalue(LValueKind::Field {
+                base: Box::new(take!(children[0], LValue)),
+                field: take!(children[2], Ident),
+            }),
+            Rules::LV_ARR => self.lvalue(LValueKind::Index {
+                base: Box::new(take!(children[0], LValue)),
+                index: Box::new(take!(children[2], Expr)),
+            }),
+            Rules::LV_IDU => self.lvalue(LValueKind::Variable(take!(children[0], Ident))),
+            Rules::TRUE => self.literal(ExprKind::Bool(true), Type::Bool_),
+            Rules::FALSE => self.literal(ExprKind::Bool(false), Type::Bool_),
+            Rules::C => {
+                let text = take!(children[0], Terminal);
+                self.literal(ExprKind::Char(text.chars().next().unwrap_or('\0')), Type::Char_)
+            },
+            Rules::STR => self.literal(ExprKind::String(take!(children[0], Terminal)), Type::String_),
+            Rules::NUM => {
+                let text = take!(children[0], Terminal);
+                if text.contains('.') {
+                    self.literal(ExprKind::Real(terminals::real_value(&text)), Type::Real_)
+                } else {
+                    self.literal(ExprKind::Integer(terminals::integer_value(&text).unwrap_or(0)), Type::Int_)
+                }
+            },
+            Rules::IDD | Rules::IDU | Rules::ID => Node::Ident(Ident { name: take!(children[0], Terminal), span }),
+            Rules::MW => Node::Loop(*self.loops.last().unwrap()),
+            Rules::NB | Rules::MF | Rules::MC | Rules::MT | Rules::ME | Rules::NF |
+            Rules::ERROR => Node::Empty,
+        }
+    }
+}
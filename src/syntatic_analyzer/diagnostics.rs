@@ -0,0 +1,168 @@
+use crate::syntatic_analyzer::nonterminals::Type;
+use crate::syntatic_analyzer::span::Span;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning
+}
+
+// The compiler stage that found the error, ordered as they run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Phase {
+    Lexical,
+    Syntactic,
+    Semantic,
+    // errors of a checked program while the interpreter runs it
+    Runtime
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCode {
+    SyntaxError,
+    UnexpectedCharacter,
+    UnterminatedComment,
+    InvalidEscape,
+    UnterminatedString,
+    UnterminatedChar,
+    EmptyChar,
+    MalformedNumber,
+    IdentifierRedeclaration,
+    IdentifierNotDeclared,
+    ExpectedTypeIdentifier,
+    ArraySizeNotPositive,
+    ArraySizeNotInteger,
+    TypeTooLarge,
+    ConditionNotBoolean,
+    AssignmentTypeMismatch,
+    OperandTypeMismatch,
+    InvalidOperandType,
+    StructFieldNotDeclared,
+    NotAStruct,
+    NotIndexable,
+    IndexNotInteger,
+    NotAVariable,
+    NotAFunction,
+    ArgumentTypeMismatch,
+    TooManyArguments,
+    TooFewArguments,
+    ReturnTypeMismatch,
+    ReturnOutsideFunction,
+    MissingReturn,
+    BreakOutsideLoop,
+    ContinueOutsideLoop,
+    LiteralOutOfRange,
+    IndexOutOfBounds,
+    DivisionByZero,
+    StackOverflow,
+    InvalidBytecode,
+    InvalidCall,
+}
+
+impl ErrorCode {
+    // The codes are stable: new errors get new numbers, old numbers are never reused
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCode::SyntaxError => "E0001",
+            ErrorCode::UnexpectedCharacter => "E0010",
+            ErrorCode::UnterminatedComment => "E0011",
+            ErrorCode::InvalidEscape => "E0012",
+            ErrorCode::UnterminatedString => "E0013",
+            ErrorCode::UnterminatedChar => "E0014",
+            ErrorCode::EmptyChar => "E0015",
+            ErrorCode::MalformedNumber => "E0016",
+            ErrorCode::IdentifierRedeclaration => "E0100",
+            ErrorCode::IdentifierNotDeclared => "E0101",
+            ErrorCode::ExpectedTypeIdentifier => "E0102",
+            ErrorCode::ArraySizeNotPositive => "E0103",
+            ErrorCode::ArraySizeNotInteger => "E0104",
+            ErrorCode::TypeTooLarge => "E0105",
+            ErrorCode::ConditionNotBoolean => "E0200",
+            ErrorCode::AssignmentTypeMismatch => "E0201",
+            ErrorCode::OperandTypeMismatch => "E0202",
+            ErrorCode::InvalidOperandType => "E0203",
+            ErrorCode::StructFieldNotDeclared => "E0204",
+            ErrorCode::NotAStruct => "E0205",
+            ErrorCode::NotIndexable => "E0206",
+            ErrorCode::IndexNotInteger => "E0207",
+            ErrorCode::NotAVariable => "E0208",
+            ErrorCode::NotAFunction => "E0209",
+            ErrorCode::ArgumentTypeMismatch => "E0210",
+            ErrorCode::TooManyArguments => "E0211",
+            ErrorCode::TooFewArguments => "E0212",
+            ErrorCode::ReturnTypeMismatch => "E0213",
+            ErrorCode::ReturnOutsideFunction => "E0214",
+            ErrorCode::MissingReturn => "E0215",
+            ErrorCode::BreakOutsideLoop => "E0216",
+            ErrorCode::ContinueOutsideLoop => "E0217",
+            ErrorCode::LiteralOutOfRange => "E0218",
+            ErrorCode::IndexOutOfBounds => "E0300",
+            ErrorCode::DivisionByZero => "E0301",
+            ErrorCode::StackOverflow => "E0302",
+            ErrorCode::InvalidBytecode => "E0303",
+            ErrorCode::InvalidCall => "E0304",
+        }
+    }
+
+    pub fn phase(&self) -> Phase {
+        match self {
+            ErrorCode::UnexpectedCharacter | ErrorCode::UnterminatedComment | ErrorCode::InvalidEscape |
+            ErrorCode::UnterminatedString | ErrorCode::UnterminatedChar | ErrorCode::EmptyChar |
+            ErrorCode::MalformedNumber => Phase::Lexical,
+            ErrorCode::SyntaxError => Phase::Syntactic,
+            ErrorCode::IndexOutOfBounds | ErrorCode::DivisionByZero | ErrorCode::StackOverflow |
+            ErrorCode::InvalidBytecode | ErrorCode::InvalidCall => Phase::Runtime,
+            _ => Phase::Semantic
+        }
+    }
+}
+
+// What the diagnostic is about, so tools don't have to dig it out of the message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Subject {
+    Identifier(String),
+    Type(Type),
+    Mismatch { expected: Type, found: Type },
+    // what the parser could have accepted instead of the source text it found
+    UnexpectedToken { expected: Vec<String>, found: String },
+}
+
+// Secondary location that helps explain the error, e.g. where a name was first declared
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Label {
+    pub span: Span,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub code: ErrorCode,
+    pub severity: Severity,
+    pub message: String,
+    pub subject: Option<Subject>,
+    pub span: Span,
+    pub labels: Vec<Label>,
+}
+
+impl Diagnostic {
+    pub fn error(code: ErrorCode, message: String, span: Span) -> Self {
+        Diagnostic {
+            code,
+            severity: Severity::Error,
+            message,
+            subject: None,
+            span,
+            labels: vec![],
+        }
+    }
+
+    pub fn with_subject(mut self, subject: Subject) -> Self {
+        self.subject = Some(subject);
+        self
+    }
+
+    pub fn with_label(mut self, span: Span, message: String) -> Self {
+        self.labels.push(Label { span, message });
+        self
+    }
+}
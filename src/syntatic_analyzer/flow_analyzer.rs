@@ -0,0 +1,78 @@
+use crate::syntatic_analyzer::ast::{Block, Expr, ExprKind, Stmt, StmtKind};
+use crate::syntatic_analyzer::span::{Position, Span};
+
+/*
+ * Finds a path through a function body that reaches its end without a return, so the
+ * function would give no value back. Returns where that path ends: the last statement it
+ * runs, or the closing brace when the body has no statements at all.
+ *
+ * A statement "completes" when running it can go on to the next statement. A loop whose
+ * condition is the literal `true` only completes through a `break`.
+ */
+pub fn missing_return(body: &Block) -> Option<Span> {
+    block_falls_off(body)
+}
+
+fn block_falls_off(block: &Block) -> Option<Span> {
+    if !block.stmts.iter().all(completes) {
+        return None;
+    }
+    match block.stmts.last() {
+        Some(last) => falls_off(last),
+        None => Some(closing_brace(block.span)),
+    }
+}
+
+// Where a path through `stmt`, which completes, ends
+fn falls_off(stmt: &Stmt) -> Option<Span> {
+    match &stmt.kind {
+        StmtKind::Block(block) => block_falls_off(block),
+        StmtKind::If { then_branch, else_branch: Some(else_branch), .. } => {
+            if completes(then_branch) { falls_off(then_branch) } else { falls_off(else_branch) }
+        },
+        _ => Some(stmt.span),
+    }
+}
+
+fn completes(stmt: &Stmt) -> bool {
+    match &stmt.kind {
+        // a statement that failed to parse may have been a return, assuming it was avoids cascading errors
+        StmtKind::Return(_) | StmtKind::Break(_) | StmtKind::Continue(_) | StmtKind::Error => false,
+        StmtKind::If { else_branch: None, .. } => true,
+        StmtKind::If { then_branch, else_branch: Some(else_branch), .. } => completes(then_branch) || completes(else_branch),
+        StmtKind::Block(block) => block.stmts.iter().all(completes),
+        StmtKind::While { id, condition, body } => !is_true(condition) || jumps(body, &StmtKind::Break(*id)),
+        StmtKind::DoWhile { id, body, condition } => {
+            let reaches_condition = completes(body) || jumps(body, &StmtKind::Continue(*id));
+            jumps(body, &StmtKind::Break(*id)) || (reaches_condition && !is_true(condition))
+        },
+        StmtKind::Assign { .. } => true,
+    }
+}
+
+// Whether `stmt` has the `break` or `continue` `jump`, which names the loop it leaves
+fn jumps(stmt: &Stmt, jump: &StmtKind) -> bool {
+    match &stmt.kind {
+        StmtKind::Break(_) | StmtKind::Continue(_) => stmt.kind == *jump,
+        StmtKind::If { then_branch, else_branch, .. } => {
+            jumps(then_branch, jump) || else_branch.as_ref().is_some_and(|else_branch| jumps(else_branch, jump))
+        },
+        StmtKind::Block(block) => block.stmts.iter().any(|stmt| jumps(stmt, jump)),
+        StmtKind::While { body, .. } | StmtKind::DoWhile { body, .. } => jumps(body, jump),
+        StmtKind::Return(_) | StmtKind::Assign { .. } | StmtKind::Error => false,
+    }
+}
+
+fn is_true(condition: &Expr) -> bool {
+    condition.kind == ExprKind::Bool(true)
+}
+
+// The `}` a block span ends with
+fn closing_brace(block: Span) -> Span {
+    let start = Position {
+        offset: block.end.offset - 1,
+        line: block.end.line,
+        column: block.end.column - 1,
+    };
+    Span::new(start, block.end)
+}
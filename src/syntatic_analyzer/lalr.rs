@@ -0,0 +1,547 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+
+/*
+ * LALR(1) table generator. build.rs runs it on simplescript.grammar to produce the parser
+ * constants, so it only uses std. The grammar has one declaration per line and `//` comments:
+ *
+ *   %token ID(_) "identifier"   a terminal, as its terminals::Token pattern, and how errors show it
+ *   %eof DOLLAR                 the terminal that ends the input
+ *   %expect 1                   how many shift/reduce conflicts there are, they are solved by shifting
+ *   LV_IDU: LV -> IDU           a rule named LV_IDU, terminals are quoted as in 'ID'
+ *
+ * The left side of the first rule is the start symbol. The states are the LR(0) ones, with the
+ * lookaheads computed by propagation (Dragon book, 4.7.5).
+ */
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Symbol {
+    Terminal(usize),
+    Nonterminal(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Terminal {
+    pub name: String,
+    pub pattern: String,
+    pub display: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    pub name: String,
+    pub left: usize,
+    pub right: Vec<Symbol>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grammar {
+    // in the order of their columns, after the non terminals ones
+    pub terminals: Vec<Terminal>,
+    // sorted by name, which is the order of their columns
+    pub nonterminals: Vec<String>,
+    pub rules: Vec<Rule>,
+    pub eof: usize,
+    pub expected_conflicts: usize,
+}
+
+impl Grammar {
+    pub fn parse(text: &str) -> Result<Grammar, String> {
+        let mut terminals: Vec<Terminal> = vec![];
+        let mut eof = None;
+        let mut expected_conflicts = 0;
+        // name, left side and right side of every rule, with the line they are in
+        let mut raw_rules: Vec<(usize, &str, &str, Vec<&str>)> = vec![];
+
+        for (i, line) in text.lines().enumerate() {
+            let line_number = i + 1;
+            let line = line.split("//").next().unwrap().trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(declaration) = line.strip_prefix("%token") {
+                let declaration = declaration.trim();
+                let (pattern, display) = declaration.split_once(char::is_whitespace)
+                    .ok_or_else(|| format!("line {}: expected `%token PATTERN \"display name\"`", line_number))?;
+                let display = display.trim();
+                if display.len() < 2 || !display.starts_with('"') || !display.ends_with('"') {
+                    return Err(format!("line {}: the display name of `{}` must be quoted", line_number, pattern));
+                }
+                let name = pattern.split('(').next().unwrap();
+                if terminals.iter().any(|terminal| terminal.name == name) {
+                    return Err(format!("line {}: terminal `{}` is declared twice", line_number, name));
+                }
+                terminals.push(Terminal {
+                    name: name.to_string(),
+                    pattern: pattern.to_string(),
+                    display: display[1..display.len() - 1].to_string(),
+                });
+            } else if let Some(name) = line.strip_prefix("%eof") {
+                eof = Some((line_number, name.trim()));
+            } else if let Some(count) = line.strip_prefix("%expect") {
+                expected_conflicts = count.trim().parse()
+                    .map_err(|_| format!("line {}: `%expect` takes the number of shift/reduce conflicts", line_number))?;
+            } else if line.starts_with('%') {
+                return Err(format!("line {}: unknown declaration `{}`", line_number, line));
+            } else {
+                let (name, rule) = line.split_once(':')
+                    .ok_or_else(|| format!("line {}: expected `NAME: LEFT -> RIGHT`", line_number))?;
+                let (left, right) = rule.split_once("->")
+                    .ok_or_else(|| format!("line {}: expected `NAME: LEFT -> RIGHT`", line_number))?;
+                raw_rules.push((line_number, name.trim(), left.trim(), right.split_whitespace().collect()));
+            }
+        }
+
+        if raw_rules.is_empty() {
+            return Err(String::from("the grammar has no rules"));
+        }
+        let nonterminals: Vec<String> = raw_rules.iter()
+            .map(|(_, _, left, _)| left.to_string())
+            .collect::<BTreeSet<String>>()
+            .into_iter()
+            .collect();
+
+        let terminal = |line_number: usize, name: &str| {
+            terminals.iter().position(|terminal| terminal.name == name)
+                .ok_or_else(|| format!("line {}: terminal `{}` is not declared", line_number, name))
+        };
+        let eof = match eof {
+            Some((line_number, name)) => terminal(line_number, name)?,
+            None => return Err(String::from("the grammar needs an `%eof` terminal")),
+        };
+
+        let mut rules: Vec<Rule> = vec![];
+        for (line_number, name, left, right) in raw_rules {
+            if rules.iter().any(|rule| rule.name == name) {
+                return Err(format!("line {}: rule `{}` is declared twice", line_number, name));
+            }
+            let mut symbols = vec![];
+            for symbol in right {
+                match symbol.strip_prefix('\'').and_then(|symbol| symbol.strip_suffix('\'')) {
+                    Some(name) => symbols.push(Symbol::Terminal(terminal(line_number, name)?)),
+                    None => match nonterminals.iter().position(|nonterminal| nonterminal == symbol) {
+                        Some(index) => symbols.push(Symbol::Nonterminal(index)),
+                        None => return Err(format!("line {}: `{}` is not the left side of any rule", line_number, symbol)),
+                    }
+                }
+            }
+            rules.push(Rule {
+                name: name.to_string(),
+                left: nonterminals.iter().position(|nonterminal| nonterminal == left).unwrap(),
+                right: symbols,
+            });
+        }
+
+        Ok(Grammar { terminals, nonterminals, rules, eof, expected_conflicts })
+    }
+
+    pub fn start(&self) -> usize {
+        self.rules[0].left
+    }
+
+    // Table column of a symbol
+    pub fn column(&self, symbol: Symbol) -> usize {
+        match symbol {
+            Symbol::Nonterminal(index) => index,
+            Symbol::Terminal(index) => self.nonterminals.len() + index,
+        }
+    }
+
+    fn symbol_name(&self, symbol: Symbol) -> String {
+        match symbol {
+            Symbol::Terminal(index) => format!("'{}'", self.terminals[index].name),
+            Symbol::Nonterminal(index) => self.nonterminals[index].clone(),
+        }
+    }
+
+    // `LV -> LV 'DOT' ID`
+    pub fn rule(&self, rule: usize) -> String {
+        self.text(rule, None)
+    }
+
+    // `LV -> LV . 'DOT' ID`
+    pub fn item(&self, rule: usize, dot: usize) -> String {
+        self.text(rule, Some(dot))
+    }
+
+    fn text(&self, rule: usize, dot: Option<usize>) -> String {
+        let rule = &self.rules[rule];
+        let mut text = format!("{} ->", self.nonterminals[rule.left]);
+        for (i, symbol) in rule.right.iter().enumerate() {
+            if dot == Some(i) {
+                text.push_str(" .");
+            }
+            text.push(' ');
+            text.push_str(&self.symbol_name(*symbol));
+        }
+        if dot == Some(rule.right.len()) {
+            text.push_str(" .");
+        }
+        text
+    }
+}
+
+// A terminal that some state could both shift and reduce, or reduce by several rules
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conflict {
+    pub state: usize,
+    pub terminal: usize,
+    pub shift: bool,
+    pub reductions: Vec<usize>,
+    // the items of the state behind each of the possible actions
+    pub items: Vec<(usize, usize)>,
+}
+
+impl Conflict {
+    pub fn report(&self, grammar: &Grammar) -> String {
+        let kind = if self.shift { "shift/reduce" } else { "reduce/reduce" };
+        let mut text = format!("{} conflict in state {} on {}:\n", kind, self.state, grammar.terminals[self.terminal].display);
+        let width = self.items.iter().map(|(rule, dot)| grammar.item(*rule, *dot).len()).max().unwrap_or(0);
+        for (rule, dot) in self.items.iter() {
+            let action = if *dot == grammar.rules[*rule].right.len() {
+                format!("reduce by {}", grammar.rules[*rule].name)
+            } else {
+                String::from("shift")
+            };
+            text.push_str(&format!("    {:<width$}    {}\n", grammar.item(*rule, *dot), action));
+        }
+        if self.shift {
+            text.push_str("  solved by shifting\n");
+        } else {
+            text.push_str(&format!("  solved by reducing by {}, the first of the rules\n", grammar.rules[self.reductions[0]].name));
+        }
+        text
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tables {
+    /*
+     * action[state][column], the non terminal columns come first. Positive entries shift or go
+     * to that state, negative ones reduce by the rule -entry - 1 and 0 is a syntax error.
+     */
+    pub action: Vec<Vec<i32>>,
+    // the state reached with the start symbol, where the input is accepted
+    pub accept_state: usize,
+    pub conflicts: Vec<Conflict>,
+}
+
+impl Tables {
+    // Reports every conflict if there are any the grammar does not expect
+    pub fn check(&self, grammar: &Grammar) -> Result<(), String> {
+        let shift_reduce = self.conflicts.iter().filter(|conflict| conflict.shift).count();
+        let reduce_reduce = self.conflicts.len() - shift_reduce;
+        if shift_reduce == grammar.expected_conflicts && reduce_reduce == 0 {
+            return Ok(());
+        }
+
+        let mut text = format!("{} shift/reduce conflicts (expected {}) and {} reduce/reduce conflicts\n\n",
+                               shift_reduce, grammar.expected_conflicts, reduce_reduce);
+        for conflict in self.conflicts.iter() {
+            text.push_str(&conflict.report(grammar));
+            text.push('\n');
+        }
+        Err(text)
+    }
+}
+
+/*
+ * The action table packed by row displacement. The entries of a state are stored at
+ * `base[state] + column` of vectors shared by every state, and `check` tells which state a slot
+ * belongs to, so rows can overlap as long as their entries don't. Missing entries are errors.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Compressed {
+    pub base: Vec<usize>,
+    // None for the slots no state uses
+    pub check: Vec<Option<usize>>,
+    pub value: Vec<i32>,
+}
+
+impl Compressed {
+    pub fn get(&self, state: usize, column: usize) -> i32 {
+        let slot = self.base[state] + column;
+        match self.check.get(slot) {
+            Some(Some(owner)) if *owner == state => self.value[slot],
+            _ => 0
+        }
+    }
+}
+
+impl Tables {
+    pub fn compress(&self) -> Compressed {
+        let mut base = vec![0; self.action.len()];
+        let mut check: Vec<Option<usize>> = vec![];
+        let mut value = vec![];
+
+        // the rows with the most entries are placed first, while the vectors still have room for them
+        let mut states: Vec<usize> = (0..self.action.len()).collect();
+        states.sort_by_key(|state| Reverse(self.action[*state].iter().filter(|entry| **entry != 0).count()));
+        for state in states {
+            let columns: Vec<usize> = (0..self.action[state].len()).filter(|column| self.action[state][*column] != 0).collect();
+            let Some(last) = columns.last() else { continue };
+
+            let mut offset = 0;
+            while columns.iter().any(|column| check.get(offset + column).is_some_and(|owner| owner.is_some())) {
+                offset += 1;
+            }
+            if check.len() <= offset + last {
+                check.resize(offset + last + 1, None);
+                value.resize(offset + last + 1, 0);
+            }
+            for column in columns {
+                check[offset + column] = Some(state);
+                value[offset + column] = self.action[state][column];
+            }
+            base[state] = offset;
+        }
+
+        Compressed { base, check, value }
+    }
+}
+
+// (rule, dot), the rule after the grammar ones is the augmented `start' -> start`
+type Item = (usize, usize);
+
+// Stands for the lookaheads that are propagated, not generated, while they are computed
+const PROPAGATED: usize = usize::MAX;
+
+struct Generator<'g> {
+    grammar: &'g Grammar,
+    // the grammar rules followed by the augmented one
+    rules: Vec<(usize, Vec<Symbol>)>,
+    rules_of: Vec<Vec<usize>>,
+    nullable: Vec<bool>,
+    first: Vec<BTreeSet<usize>>,
+    kernels: Vec<Vec<Item>>,
+    transitions: Vec<BTreeMap<Symbol, usize>>,
+}
+
+pub fn build(grammar: &Grammar) -> Tables {
+    let mut rules: Vec<(usize, Vec<Symbol>)> = grammar.rules.iter().map(|rule| (rule.left, rule.right.clone())).collect();
+    rules.push((grammar.nonterminals.len(), vec![Symbol::Nonterminal(grammar.start())]));
+
+    let mut rules_of = vec![vec![]; grammar.nonterminals.len()];
+    for (index, rule) in grammar.rules.iter().enumerate() {
+        rules_of[rule.left].push(index);
+    }
+
+    let mut generator = Generator {
+        grammar,
+        rules,
+        rules_of,
+        nullable: vec![false; grammar.nonterminals.len()],
+        first: vec![BTreeSet::new(); grammar.nonterminals.len()],
+        kernels: vec![],
+        transitions: vec![],
+    };
+    generator.first_sets();
+    generator.lr0_states();
+    let lookaheads = generator.lookaheads();
+    generator.tables(&lookaheads)
+}
+
+impl Generator<'_> {
+    fn first_sets(&mut self) {
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for rule in self.grammar.rules.iter() {
+                let (first, nullable) = self.first_of(&rule.right);
+                if nullable && !self.nullable[rule.left] {
+                    self.nullable[rule.left] = true;
+                    changed = true;
+                }
+                let before = self.first[rule.left].len();
+                self.first[rule.left].extend(first);
+                changed |= self.first[rule.left].len() != before;
+            }
+        }
+    }
+
+    // Terminals that can start `symbols`, and whether they can derive the empty string
+    fn first_of(&self, symbols: &[Symbol]) -> (BTreeSet<usize>, bool) {
+        let mut first = BTreeSet::new();
+        for symbol in symbols {
+            match *symbol {
+                Symbol::Terminal(terminal) => {
+                    first.insert(terminal);
+                    return (first, false);
+                },
+                Symbol::Nonterminal(nonterminal) => {
+                    first.extend(self.first[nonterminal].iter().copied());
+                    if !self.nullable[nonterminal] {
+                        return (first, false);
+                    }
+                }
+            }
+        }
+        (first, true)
+    }
+
+    fn next_symbol(&self, (rule, dot): Item) -> Option<Symbol> {
+        self.rules[rule].1.get(dot).copied()
+    }
+
+    fn closure(&self, kernel: &[Item]) -> Vec<Item> {
+        let mut items = kernel.to_vec();
+        let mut expanded = vec![false; self.grammar.nonterminals.len()];
+        let mut i = 0;
+        while i < items.len() {
+            if let Some(Symbol::Nonterminal(nonterminal)) = self.next_symbol(items[i]) {
+                if !expanded[nonterminal] {
+                    expanded[nonterminal] = true;
+                    items.extend(self.rules_of[nonterminal].iter().map(|rule| (*rule, 0)));
+                }
+            }
+            i += 1;
+        }
+        items
+    }
+
+    // Closure of items with a lookahead each, which may be PROPAGATED
+    fn closure1(&self, kernel: Vec<(usize, usize, usize)>) -> BTreeSet<(usize, usize, usize)> {
+        let mut items: BTreeSet<(usize, usize, usize)> = kernel.iter().copied().collect();
+        let mut pending = kernel;
+        while let Some((rule, dot, lookahead)) = pending.pop() {
+            if let Some(Symbol::Nonterminal(nonterminal)) = self.next_symbol((rule, dot)) {
+                let (mut first, nullable) = self.first_of(&self.rules[rule].1[dot + 1..]);
+                if nullable {
+                    first.insert(lookahead);
+                }
+                for next_rule in self.rules_of[nonterminal].iter() {
+                    for terminal in first.iter() {
+                        if items.insert((*next_rule, 0, *terminal)) {
+                            pending.push((*next_rule, 0, *terminal));
+                        }
+                    }
+                }
+            }
+        }
+        items
+    }
+
+    // The LR(0) states, numbered in the order they are found from the first one
+    fn lr0_states(&mut self) {
+        let mut index: HashMap<Vec<Item>, usize> = HashMap::new();
+        let initial = vec![(self.rules.len() - 1, 0)];
+        index.insert(initial.clone(), 0);
+        self.kernels.push(initial);
+        self.transitions.push(BTreeMap::new());
+
+        let mut pending = VecDeque::from([0]);
+        while let Some(state) = pending.pop_front() {
+            let mut successors: BTreeMap<Symbol, Vec<Item>> = BTreeMap::new();
+            for (rule, dot) in self.closure(&self.kernels[state]) {
+                if let Some(symbol) = self.next_symbol((rule, dot)) {
+                    successors.entry(symbol).or_default().push((rule, dot + 1));
+                }
+            }
+
+            for (symbol, mut kernel) in successors {
+                kernel.sort();
+                kernel.dedup();
+                let target = match index.get(&kernel) {
+                    Some(target) => *target,
+                    None => {
+                        let target = self.kernels.len();
+                        index.insert(kernel.clone(), target);
+                        self.kernels.push(kernel);
+                        self.transitions.push(BTreeMap::new());
+                        pending.push_back(target);
+                        target
+                    }
+                };
+                self.transitions[state].insert(symbol, target);
+            }
+        }
+    }
+
+    // Lookaheads of every kernel item, indexed by state and then by position in the kernel
+    fn lookaheads(&self) -> Vec<Vec<BTreeSet<usize>>> {
+        let mut lookaheads: Vec<Vec<BTreeSet<usize>>> = self.kernels.iter()
+            .map(|kernel| vec![BTreeSet::new(); kernel.len()])
+            .collect();
+        lookaheads[0][0].insert(self.grammar.eof);
+
+        let mut propagation: Vec<((usize, usize), (usize, usize))> = vec![];
+        for (state, kernel) in self.kernels.iter().enumerate() {
+            for (position, (rule, dot)) in kernel.iter().enumerate() {
+                for (rule, dot, lookahead) in self.closure1(vec![(*rule, *dot, PROPAGATED)]) {
+                    let Some(symbol) = self.next_symbol((rule, dot)) else { continue };
+                    let target = self.transitions[state][&symbol];
+                    let target_position = self.kernels[target].iter().position(|item| *item == (rule, dot + 1)).unwrap();
+                    if lookahead == PROPAGATED {
+                        propagation.push(((state, position), (target, target_position)));
+                    } else {
+                        lookaheads[target][target_position].insert(lookahead);
+                    }
+                }
+            }
+        }
+
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for ((state, position), (target, target_position)) in propagation.iter() {
+                let from = lookaheads[*state][*position].clone();
+                let to = &mut lookaheads[*target][*target_position];
+                let before = to.len();
+                to.extend(from);
+                changed |= to.len() != before;
+            }
+        }
+        lookaheads
+    }
+
+    fn tables(&self, lookaheads: &[Vec<BTreeSet<usize>>]) -> Tables {
+        let grammar = self.grammar;
+        let columns = grammar.nonterminals.len() + grammar.terminals.len();
+        let mut action = vec![vec![0; columns]; self.kernels.len()];
+        let mut conflicts = vec![];
+
+        for (state, transitions) in self.transitions.iter().enumerate() {
+            for (symbol, target) in transitions.iter() {
+                action[state][grammar.column(*symbol)] = i32::try_from(*target).unwrap();
+            }
+
+            let kernel: Vec<(usize, usize, usize)> = self.kernels[state].iter().zip(lookaheads[state].iter())
+                .flat_map(|((rule, dot), lookaheads)| lookaheads.iter().map(move |lookahead| (*rule, *dot, *lookahead)))
+                .collect();
+            let mut reductions: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+            for (rule, dot, lookahead) in self.closure1(kernel) {
+                // reducing the augmented rule is accepting, which the parser does on its own
+                if dot == self.rules[rule].1.len() && rule < grammar.rules.len() {
+                    let rules = reductions.entry(lookahead).or_default();
+                    if !rules.contains(&rule) {
+                        rules.push(rule);
+                    }
+                }
+            }
+
+            for (terminal, mut rules) in reductions {
+                rules.sort();
+                let column = grammar.column(Symbol::Terminal(terminal));
+                let shift = action[state][column] > 0;
+                if shift || rules.len() > 1 {
+                    let mut items: Vec<Item> = rules.iter().map(|rule| (*rule, grammar.rules[*rule].right.len())).collect();
+                    if shift {
+                        items.extend(self.closure(&self.kernels[state]).into_iter()
+                            .filter(|item| self.next_symbol(*item) == Some(Symbol::Terminal(terminal))));
+                    }
+                    conflicts.push(Conflict { state, terminal, shift, reductions: rules.clone(), items });
+                }
+                if !shift {
+                    action[state][column] = -i32::try_from(rules[0]).unwrap() - 1;
+                }
+            }
+        }
+
+        Tables {
+            action,
+            accept_state: self.transitions[0][&Symbol::Nonterminal(grammar.start())],
+            conflicts,
+        }
+    }
+}
@@ -1,51 +1,10 @@
-pub enum Token {
-B, C, DC, DE, DF, DT, DV, E, F, FALSE, ID, IDD, IDU, L, LDE, LDV, LE, LI, LP, LS, LV, MC, ME, MF, MT, MW, NB, NF, NUM, P, R, S, STR, T, TRUE, Y, 
-}
+use std::fmt;
 
-impl Token {
-    pub fn into_usize(&self) -> usize {
-        match self{
-            Token::B => 0,
-            Token::C => 1,
-            Token::DC => 2,
-            Token::DE => 3,
-            Token::DF => 4,
-            Token::DT => 5,
-            Token::DV => 6,
-            Token::E => 7,
-            Token::F => 8,
-            Token::FALSE => 9,
-            Token::ID => 10,
-            Token::IDD => 11,
-            Token::IDU => 12,
-            Token::L => 13,
-            Token::LDE => 14,
-            Token::LDV => 15,
-            Token::LE => 16,
-            Token::LI => 17,
-            Token::LP => 18,
-            Token::LS => 19,
-            Token::LV => 20,
-            Token::MC => 21,
-            Token::ME => 22,
-            Token::MF => 23,
-            Token::MT => 24,
-            Token::MW => 25,
-            Token::NB => 26,
-            Token::NF => 27,
-            Token::NUM => 28,
-            Token::P => 29,
-            Token::R => 30,
-            Token::S => 31,
-            Token::STR => 32,
-            Token::T => 33,
-            Token::TRUE => 34,
-            Token::Y => 35,
-        }
-    }
-}
+// The non terminals of simplescript.grammar and their ACTIONTABLE columns, generated by build.rs
+include!(concat!(env!("OUT_DIR"), "/nonterminals.rs"));
 
-#[derive(Clone, PartialEq, Eq)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum AttribToken {
     B,
     CHR(Object, char), 
@@ -68,10 +27,7 @@ pub enum AttribToken {
     LS,
     LV(Type),
     MC(Type, Vec<Type>),
-    ME,
     MF,
-    MT,
-    MW,
     NB,
     NF,
     NUM(Object, i32),
@@ -84,11 +40,11 @@ pub enum AttribToken {
     Y(Type) 
 }
 
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Object(pub String, pub Kind);
 
 #[allow(non_camel_case_types)]
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Kind{
     no_kind_def,
     var(Type),
@@ -102,17 +58,45 @@ pub enum Kind{
     universal
 }
 
-#[derive(Clone, PartialEq, Eq)]
-pub struct StructField(pub String, pub Type);
-
 #[allow(non_camel_case_types)]
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Type {
     Int_,
     Char_,
     Bool_,
     String_,
+    Real_,
     Array_type_(u32, Box<Type>),
     Alias_type_(Box<Type>),
-    Struct_type_(Box<Vec<Object>>)
+    Struct_type_(Box<Vec<Object>>),
+    // Type given to erroneous constructs so one error does not cascade into many
+    Universal_
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Type::Int_ => write!(f, "integer"),
+            Type::Char_ => write!(f, "char"),
+            Type::Bool_ => write!(f, "boolean"),
+            Type::String_ => write!(f, "string"),
+            Type::Real_ => write!(f, "real"),
+            Type::Array_type_(n_elements, type_) => write!(f, "array[{}] of {}", n_elements, type_),
+            Type::Alias_type_(type_) => write!(f, "{}", type_),
+            Type::Struct_type_(fields) => {
+                write!(f, "struct {{ ")?;
+                for (i, field) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "; ")?;
+                    }
+                    match &field.1 {
+                        Kind::field(type_) => write!(f, "{} : {}", field.0, type_)?,
+                        _ => write!(f, "{}", field.0)?,
+                    }
+                }
+                write!(f, " }}")
+            },
+            Type::Universal_ => write!(f, "{{unknown}}"),
+        }
+    }
 }
@@ -0,0 +1,124 @@
+use std::fmt::Write;
+
+use crate::syntatic_analyzer::diagnostics::{Diagnostic, Severity};
+use crate::syntatic_analyzer::span::Span;
+
+/*
+ * Renders a diagnostic the way rustc does:
+ *
+ * error[E0100]: identifier `n` is already declared in this scope
+ *  --> main.ss:3:9
+ *   |
+ * 1 | function main(n : integer) : integer {
+ *   |               - previously declared here
+ * 2 |     var s : string;
+ * 3 |     var n : integer;
+ *   |         ^
+ */
+
+struct Marker<'m> {
+    span: Span,
+    primary: bool,
+    message: &'m str,
+}
+
+pub fn render(diagnostic: &Diagnostic, file_name: &str, source: &str) -> String {
+    let lines: Vec<&str> = source.split('\n').collect();
+    let mut markers = vec![Marker { span: diagnostic.span, primary: true, message: "" }];
+    for label in diagnostic.labels.iter() {
+        markers.push(Marker { span: label.span, primary: false, message: label.message.as_str() });
+    }
+
+    // every marker is drawn under the last line it covers, multi-line spans also show their first line
+    let mut shown_lines: Vec<u32> = vec![];
+    for marker in markers.iter() {
+        shown_lines.push(marker.span.start.line);
+        shown_lines.push(marker.span.end.line);
+    }
+    shown_lines.sort();
+    shown_lines.dedup();
+
+    let width = shown_lines.last().unwrap().to_string().len();
+    let gutter = " ".repeat(width);
+    let mut out = String::new();
+
+    let severity = match diagnostic.severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+    };
+    writeln!(out, "{}[{}]: {}", severity, diagnostic.code.as_str(), diagnostic.message).unwrap();
+    writeln!(out, "{}--> {}:{}:{}", gutter, file_name, diagnostic.span.start.line, diagnostic.span.start.column).unwrap();
+    writeln!(out, "{} |", gutter).unwrap();
+
+    let mut previous: Option<u32> = None;
+    for line_number in shown_lines.iter().copied() {
+        if let Some(previous) = previous {
+            if line_number == previous + 2 {
+                write_source_line(&mut out, &lines, previous + 1, width);
+            }
+            else if line_number > previous + 2 {
+                writeln!(out, "...").unwrap();
+            }
+        }
+        write_source_line(&mut out, &lines, line_number, width);
+
+        let text = line_text(&lines, line_number);
+        for marker in markers.iter() {
+            let span = marker.span;
+            if span.start.line != line_number && span.end.line != line_number {
+                continue;
+            }
+            // a multi-line span is underlined from its start to the end of the first line, and
+            // from the first non blank character to its end on the last line
+            let start = if span.start.line == line_number {
+                span.start.column as usize
+            } else {
+                text.chars().take_while(|ch| ch.is_whitespace()).count() + 1
+            };
+            let end = if span.end.line == line_number {
+                span.end.column as usize
+            } else {
+                text.chars().count() + 1
+            };
+            let message = if span.end.line == line_number { marker.message } else { "" };
+
+            let mut underline = String::new();
+            for ch in text.chars().take(start.saturating_sub(1)) {
+                underline.push(if ch == '\t' { '\t' } else { ' ' });
+            }
+            let length = std::cmp::max(end.saturating_sub(start), 1);
+            underline.push_str(&(if marker.primary { "^" } else { "-" }).repeat(length));
+            if !message.is_empty() {
+                underline.push(' ');
+                underline.push_str(message);
+            }
+            writeln!(out, "{} | {}", gutter, underline).unwrap();
+        }
+        previous = Some(line_number);
+    }
+
+    out
+}
+
+pub fn render_all(diagnostics: &[Diagnostic], file_name: &str, source: &str) -> String {
+    let mut out = String::new();
+    for diagnostic in diagnostics.iter() {
+        out.push_str(render(diagnostic, file_name, source).as_str());
+        out.push('\n');
+    }
+    out
+}
+
+fn line_text<'s>(lines: &[&'s str], line_number: u32) -> &'s str {
+    // spans read from a .ssbc file may point at line 0 or past the end of the source
+    (line_number as usize).checked_sub(1).and_then(|index| lines.get(index)).map_or("", |line| line.trim_end_matches('\r'))
+}
+
+fn write_source_line(out: &mut String, lines: &[&str], line_number: u32, width: usize) {
+    let text = line_text(lines, line_number);
+    if text.is_empty() {
+        writeln!(out, "{:>width$} |", line_number, width = width).unwrap();
+    } else {
+        writeln!(out, "{:>width$} | {}", line_number, text, width = width).unwrap();
+    }
+}
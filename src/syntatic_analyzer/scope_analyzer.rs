@@ -1,26 +1,86 @@
+use std::collections::HashMap;
+
 use crate::syntatic_analyzer::nonterminals;
+use crate::syntatic_analyzer::span::Span;
+
+// The function whose body is being analyzed
+#[derive(Debug, Clone)]
+pub struct EnclosingFunction {
+    pub name: String,
+    pub return_type: nonterminals::Type,
+    pub return_type_span: Span,
+}
 
 pub struct ScopeAnalyzer {
-    contexts: Vec<Vec<nonterminals::Object>>
+    contexts: Vec<Vec<nonterminals::Object>>,
+    // spans[i][j] is where contexts[i][j] was declared
+    spans: Vec<Vec<Span>>,
+    // where the parameters of each function were declared, in order
+    param_spans: HashMap<String, Vec<Span>>,
+    enclosing_function: Option<EnclosingFunction>
+}
+
+impl Default for ScopeAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl ScopeAnalyzer {
     pub fn new() -> Self {
         ScopeAnalyzer {
-            contexts: vec![vec![]]
+            contexts: vec![vec![]],
+            spans: vec![vec![]],
+            param_spans: HashMap::new(),
+            enclosing_function: None
         }
     }
     pub fn new_block(&mut self) {
         self.contexts.push(vec![]);
+        self.spans.push(vec![]);
     }
 
     pub fn end_block(&mut self) {
         let new_size = self.contexts.len() - 1;
-        self.contexts.truncate(new_size);
+        self.truncate(new_size);
+    }
+
+    pub fn depth(&self) -> usize {
+        self.contexts.len()
+    }
+
+    // Closes every block opened after the scope had `depth` levels
+    pub fn truncate(&mut self, depth: usize) {
+        self.contexts.truncate(depth);
+        self.spans.truncate(depth);
+        // back in the global scope, so no longer inside any function
+        if depth <= 1 {
+            self.enclosing_function = None;
+        }
     }
 
-    pub fn define(&mut self, new_identifier: String) {
+    pub fn enclosing_function(&self) -> Option<&EnclosingFunction> {
+        self.enclosing_function.as_ref()
+    }
+
+    pub fn define(&mut self, new_identifier: String, span: Span) {
         self.contexts.last_mut().unwrap().push(nonterminals::Object(new_identifier, nonterminals::Kind::no_kind_def));
+        self.spans.last_mut().unwrap().push(span);
+    }
+
+    pub fn declaration_span(&self, target: &str) -> Option<Span> {
+        for (context, spans) in self.contexts.iter().zip(self.spans.iter()).rev() {
+            for (element, span) in context.iter().zip(spans.iter()) {
+                if element.0.as_str() == target {
+                    return Some(*span);
+                }
+            }
+        }
+        None
+    }
+
+    pub fn param_spans(&self, function: &str) -> Option<&Vec<Span>> {
+        self.param_spans.get(function)
     }
 
     pub fn find(&self, target: &str) -> Option<nonterminals::Object>{
@@ -31,7 +91,7 @@ impl ScopeAnalyzer {
                 }
             }
         }
-        return None;
+        None
     }
 
     pub fn search(&self, target: &str) -> Option<nonterminals::Object>{
@@ -40,7 +100,7 @@ impl ScopeAnalyzer {
                 return Some(element.clone());
             }
         }
-        return None;
+        None
     }
 
     pub fn var_decl(&mut self, target: &str, var_type: nonterminals::Type) {
@@ -51,15 +111,11 @@ impl ScopeAnalyzer {
         }
     }
 
-    pub fn array_type_decl(&mut self, target: &str, n_elements: i32, elements_type: nonterminals::Type) {
-        if n_elements < 0 {
-            panic!("ARRAY SIZE MUST BE POSITIVE");
-        }
+    pub fn array_type_decl(&mut self, target: &str, n_elements: u32, elements_type: nonterminals::Type) {
         for element in self.contexts.last_mut().unwrap().iter_mut() {
             if element.0.as_str() == target {
-                let u32_n_elements = u32::try_from(n_elements).ok().unwrap();
                 *element = nonterminals::Object(element.0.clone(), 
-                                                nonterminals::Kind::array(nonterminals::Type::Array_type_(u32_n_elements, Box::new(elements_type))));
+                                                nonterminals::Kind::array(nonterminals::Type::Array_type_(n_elements, Box::new(elements_type))));
                 break;
             }
         }
@@ -84,6 +140,7 @@ impl ScopeAnalyzer {
     pub fn struct_type_decl(&mut self, target: &str) {
         // the top context is the one with the objects representing the struct fields
         let struct_fields = Box::new(self.contexts.pop().unwrap());
+        self.spans.pop();
         for element in self.contexts.last_mut().unwrap().iter_mut() {
             if element.0.as_str() == target {
                 *element = nonterminals::Object(element.0.clone(), nonterminals::Kind::struct_(nonterminals::Type::Struct_type_(struct_fields))); 
@@ -100,7 +157,9 @@ impl ScopeAnalyzer {
         }
     }
 
-    pub fn func_type_decl(&mut self, target: &str, return_type: nonterminals::Type, param_types: Vec<nonterminals::Type>) {
+    pub fn func_type_decl(&mut self, target: &str, return_type: nonterminals::Type, return_type_span: Span, param_types: Vec<nonterminals::Type>) {
+        // right after the parameter list the function context holds exactly the parameters
+        self.param_spans.insert(target.to_string(), self.spans.last().unwrap().clone());
         let func_context = self.contexts.pop().unwrap();
         for element in self.contexts.last_mut().unwrap().iter_mut() {
             if element.0.as_str() == target {
@@ -108,6 +167,11 @@ impl ScopeAnalyzer {
             }
         }
         self.contexts.push(func_context);
+        self.enclosing_function = Some(EnclosingFunction {
+            name: target.to_string(),
+            return_type,
+            return_type_span,
+        });
     }
 }
 
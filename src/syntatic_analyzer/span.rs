@@ -0,0 +1,42 @@
+use std::ops::Range;
+
+// Lines and columns start at 1, columns count chars; offset is a byte index into the source
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Position {
+    pub offset: usize,
+    pub line: u32,
+    pub column: u32,
+}
+
+// The end position is exclusive, it points just past the last character of the span
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Span {
+    pub start: Position,
+    pub end: Position,
+}
+
+impl Span {
+    pub fn new(start: Position, end: Position) -> Self {
+        Span { start, end }
+    }
+
+    // Empty span right before `position`, used for productions that derive the empty string
+    pub fn empty(position: Position) -> Self {
+        Span { start: position, end: position }
+    }
+
+    // Smallest span covering both `self` and `other`
+    pub fn to(&self, other: Span) -> Span {
+        let start = if other.start.offset < self.start.offset { other.start } else { self.start };
+        let end = if other.end.offset > self.end.offset { other.end } else { self.end };
+        Span { start, end }
+    }
+
+    pub fn range(&self) -> Range<usize> {
+        self.start.offset..self.end.offset
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start.offset == self.end.offset
+    }
+}
@@ -1,3 +1,50 @@
+use std::borrow::Cow;
+
+use crate::syntatic_analyzer::span::Span;
+use crate::syntatic_analyzer::diagnostics::{Diagnostic, ErrorCode};
+
+// Source text the lexer could not turn into a token
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LexError {
+    UnexpectedChar(char),
+    UnterminatedString,
+    UnterminatedChar,
+    EmptyChar,
+    MalformedNumber,
+    UnterminatedComment,
+    InvalidEscape,
+}
+
+impl LexError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            LexError::UnexpectedChar(_) => ErrorCode::UnexpectedCharacter,
+            LexError::UnterminatedString => ErrorCode::UnterminatedString,
+            LexError::UnterminatedChar => ErrorCode::UnterminatedChar,
+            LexError::EmptyChar => ErrorCode::EmptyChar,
+            LexError::MalformedNumber => ErrorCode::MalformedNumber,
+            LexError::UnterminatedComment => ErrorCode::UnterminatedComment,
+            LexError::InvalidEscape => ErrorCode::InvalidEscape,
+        }
+    }
+
+    fn message(&self) -> String {
+        match self {
+            LexError::UnexpectedChar(ch) => format!("unexpected character `{}`", ch),
+            LexError::UnterminatedString => String::from("unterminated string literal"),
+            LexError::UnterminatedChar => String::from("unterminated character literal"),
+            LexError::EmptyChar => String::from("empty character literal"),
+            LexError::MalformedNumber => String::from("malformed number literal"),
+            LexError::UnterminatedComment => String::from("unterminated block comment"),
+            LexError::InvalidEscape => String::from("invalid escape sequence"),
+        }
+    }
+
+    pub fn diagnostic(&self, span: Span) -> Diagnostic {
+        Diagnostic::error(self.code(), self.message(), span)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Token<'a>
 {
@@ -11,71 +58,41 @@ pub enum Token<'a>
     // Reserved words
     ARRAY, BOOLEAN, BREAK, CHAR, CONTINUE, DO, FUNCTION,
     IF, ELSE, INTEGER, OF, RETURN, STRING, STRUCT, TRUE, FALSE,
-    TYPE, VAR, WHILE,
+    TYPE, VAR, WHILE, REAL,
 
     // Mark the end of program
     DOLLAR,
     
     // Literals and identifiers
-    ID(&'a str), CHARACTER(char), STRINGVAL(&'a str), NUMERAL(&'a str),
+    // string and character literals carry their value, with the escape sequences decoded
+    ID(&'a str), CHARACTER(char), STRINGVAL(Cow<'a, str>), NUMERAL(&'a str),
 
-    UNKNOWN(&'a str)
+    // Its span is where the error is, e.g. the opening of an unterminated comment or the bad escape
+    ERROR(LexError)
 }
 
-impl<'a> From<Token<'a>> for usize {
-    fn from(t: Token<'a>) -> Self {
-        match t {
-            Token::ARRAY => 36,
-            Token::BOOLEAN => 37,
-            Token::BREAK => 38,
-            Token::CHAR => 39,
-            Token::CONTINUE => 40,
-            Token::DO => 41,
-            Token::ELSE => 42,
-            Token::FALSE => 43,
-            Token::FUNCTION => 44,
-            Token::IF => 45,
-            Token::INTEGER => 46,
-            Token::OF => 47,
-            Token::RETURN => 48,
-            Token::STRING => 49,
-            Token::STRUCT => 50,
-            Token::TRUE => 51,
-            Token::TYPE => 52,
-            Token::VAR => 53,
-            Token::WHILE => 54,
-            Token::COLON => 55,
-            Token::SEMICOLON => 56,
-            Token::COMMA => 57,
-            Token::EQUAL => 58,
-            Token::LEFTSQUARE => 59,
-            Token::RIGHTSQUARE => 60,
-            Token::LEFTBRACE => 61,
-            Token::RIGHTBRACE => 62,
-            Token::LEFTPARENTHESIS => 63,
-            Token::RIGHTPARENTHESIS => 64,
-            Token::AND => 65,
-            Token::OR => 66,
-            Token::LESSTHAN => 67,
-            Token::GREATERTHAN => 68,
-            Token::LESSOREQUAL => 69,
-            Token::GREATEROREQUAL => 70,
-            Token::NOTEQUAL => 71,
-            Token::EQUALEQUAL => 72,
-            Token::PLUS => 73,
-            Token::PLUSPLUS => 74,
-            Token::MINUS => 75,
-            Token::MINUSMINUS => 76,
-            Token::TIMES => 77,
-            Token::DIVIDE => 78,
-            Token::DOT => 79,
-            Token::NOT => 80,
-            Token::CHARACTER(_) => 81,
-            Token::NUMERAL(_) => 82,
-            Token::STRINGVAL(_) => 83,
-            Token::ID(_) => 84,
-            Token::DOLLAR => 85,
-            Token::UNKNOWN(_) => 1000,
-        }
-    }
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpannedToken<'a> {
+    pub token: Token<'a>,
+    pub span: Span,
 }
+
+// Value of an integer numeral, `None` when it does not fit in an `integer`
+pub fn integer_value(numeral: &str) -> Option<i32> {
+    let digits = numeral.replace('_', "");
+    let (radix, digits) = match digits.get(..2) {
+        Some("0x") => (16, &digits[2..]),
+        Some("0b") => (2, &digits[2..]),
+        Some("0o") => (8, &digits[2..]),
+        _ => (10, &digits[..])
+    };
+    i32::from_str_radix(digits, radix).ok()
+}
+
+// Value of a numeral with a fractional part, infinite when it is too large for a `real`
+pub fn real_value(numeral: &str) -> f64 {
+    numeral.replace('_', "").parse().unwrap_or(f64::INFINITY)
+}
+
+// The ACTIONTABLE column of every terminal, FIRST_TERMINAL and terminal_name, generated by build.rs
+include!(concat!(env!("OUT_DIR"), "/terminals.rs"));
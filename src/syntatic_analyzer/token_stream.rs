@@ -1,243 +1,450 @@
-use crate::syntatic_analyzer::terminals::Token;
+use std::borrow::Cow;
+
+use crate::syntatic_analyzer::terminals::{Token, SpannedToken, LexError};
+use crate::syntatic_analyzer::span::{Position, Span};
 
 pub struct TokenStream<'a> {
     it: std::str::Chars<'a>,
+    source_len: usize,
     line: u32,
+    column: u32,
 }
 
 impl<'a> TokenStream<'a>{ pub fn new(it: std::str::Chars<'a>) -> Self {
         TokenStream{
+            source_len: it.as_str().len(),
             it, 
             line: 1,
+            column: 1,
         }
     }
-}
 
-impl<'a> Iterator for TokenStream<'a> {
-    type Item = Token<'a>;
+    pub fn current_position(&self) -> Position {
+        Position {
+            offset: self.source_len - self.it.as_str().len(),
+            line: self.line,
+            column: self.column,
+        }
+    }
 
-    fn next(&mut self) -> Option<Token<'a>> {
-        while self.it.clone().next().map_or(false, |ch| ch.is_whitespace()){
-            if let Some('\n') = self.it.next(){
+    fn peek(&self) -> Option<char> {
+        self.it.clone().next()
+    }
+
+    fn peek_second(&self) -> Option<char> {
+        self.it.clone().nth(1)
+    }
+
+    // Every character is consumed through here so the position is always up to date
+    fn bump(&mut self) -> Option<char> {
+        let ch = self.it.next();
+        match ch {
+            Some('\n') => {
                 self.line += 1;
+                self.column = 1;
+            },
+            Some(_) => {
+                self.column += 1;
+            },
+            None => ()
+        }
+        ch
+    }
+
+    pub fn next_spanned(&mut self) -> SpannedToken<'a> {
+        if let Err(opening) = self.skip_trivia() {
+            return SpannedToken {
+                token: Token::ERROR(LexError::UnterminatedComment),
+                span: opening,
+            };
+        }
+
+        let start = self.current_position();
+        // the end of the input is Token::DOLLAR
+        match self.scan() {
+            Ok(token) => SpannedToken {
+                token,
+                span: Span::new(start, self.current_position()),
+            },
+            Err((error, span)) => SpannedToken {
+                token: Token::ERROR(error),
+                span,
+            },
+        }
+    }
+
+    // Skips whitespace and comments, failing with the opening of a block comment that is never closed
+    fn skip_trivia(&mut self) -> Result<(), Span> {
+        loop {
+            match (self.peek(), self.peek_second()) {
+                (Some(ch), _) if ch.is_whitespace() => {
+                    self.bump();
+                },
+                (Some('/'), Some('/')) => {
+                    while self.peek().is_some_and(|ch| ch != '\n') {
+                        self.bump();
+                    }
+                },
+                (Some('/'), Some('*')) => self.skip_block_comment()?,
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    // Block comments nest, so `/* a /* b */ c */` is a single comment
+    fn skip_block_comment(&mut self) -> Result<(), Span> {
+        let start = self.current_position();
+        self.bump();
+        self.bump();
+        let opening = Span::new(start, self.current_position());
+
+        let mut depth = 1;
+        while depth > 0 {
+            match (self.bump(), self.peek()) {
+                (Some('/'), Some('*')) => {
+                    self.bump();
+                    depth += 1;
+                },
+                (Some('*'), Some('/')) => {
+                    self.bump();
+                    depth -= 1;
+                },
+                (Some(_), _) => (),
+                (None, _) => return Err(opening),
             }
         }
+        Ok(())
+    }
 
-        match self.it.clone().next() {
+    /*
+     * Decodes the escape sequence starting at the `\`: \n, \t, \\, \", \', \0 or \u{...}
+     * with up to six hexadecimal digits. Fails with the span of what was read of an invalid one.
+     */
+    fn escape(&mut self) -> Result<char, Span> {
+        let start = self.current_position();
+        self.bump();
+        let ch = match self.bump() {
+            Some('n') => Some('\n'),
+            Some('t') => Some('\t'),
+            Some('\\') => Some('\\'),
+            Some('"') => Some('"'),
+            Some('\'') => Some('\''),
+            Some('0') => Some('\0'),
+            Some('u') if self.peek() == Some('{') => {
+                self.bump();
+                let digits = self.it.as_str();
+                while self.peek().is_some_and(|ch| ch.is_ascii_hexdigit()) {
+                    self.bump();
+                }
+                let digits = &digits[..digits.len() - self.it.as_str().len()];
+                if self.peek() == Some('}') {
+                    self.bump();
+                    Some(digits).filter(|digits| (1..=6).contains(&digits.len()))
+                        .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+                        .and_then(char::from_u32)
+                } else {
+                    None
+                }
+            },
+            _ => None,
+        };
+        ch.ok_or_else(|| Span::new(start, self.current_position()))
+    }
+
+    // Where the token being scanned started up to the current position
+    fn span_from(&self, start: Position) -> Span {
+        Span::new(start, self.current_position())
+    }
+
+    // Consumes digits in the given base, which may be separated by single underscores as in `1_000`.
+    // Returns false if there was not a digit.
+    fn digits(&mut self, radix: u32) -> bool {
+        let mut any = false;
+        while let Some(ch) = self.peek() {
+            if ch.is_digit(radix) {
+                any = true;
+            } else if !(ch == '_' && any && self.peek_second().is_some_and(|next| next.is_digit(radix))) {
+                break;
+            }
+            self.bump();
+        }
+        any
+    }
+
+    fn scan(&mut self) -> Result<Token<'a>, (LexError, Span)> {
+        let start = self.current_position();
+        match self.peek() {
             Some(ch) => {
                 match ch { 
                     ':' => {
-                        self.it.next();
-                        Some(Token::COLON)
+                        self.bump();
+                        Ok(Token::COLON)
                     },
                     ';' => {
-                        self.it.next();
-                        Some(Token::SEMICOLON)
+                        self.bump();
+                        Ok(Token::SEMICOLON)
                     },
                     ',' => {
-                        self.it.next();
-                        Some(Token::COMMA)
+                        self.bump();
+                        Ok(Token::COMMA)
                     },
                     '=' => {
-                        self.it.next();
-                        if let Some('=') = self.it.clone().next() {
-                            self.it.next();
-                            Some(Token::EQUALEQUAL)
+                        self.bump();
+                        if let Some('=') = self.peek() {
+                            self.bump();
+                            Ok(Token::EQUALEQUAL)
                         }
                         else {
-                            Some(Token::EQUAL)
+                            Ok(Token::EQUAL)
                         }
                     },
                     '[' => {
-                        self.it.next();
-                        Some(Token::LEFTSQUARE)
+                        self.bump();
+                        Ok(Token::LEFTSQUARE)
                     },
                     ']' => {
-                        self.it.next();
-                        Some(Token::RIGHTSQUARE)
+                        self.bump();
+                        Ok(Token::RIGHTSQUARE)
                     },
                     '{' => {
-                        self.it.next();
-                        Some(Token::LEFTBRACE)
+                        self.bump();
+                        Ok(Token::LEFTBRACE)
                     },
                     '}' => {
-                        self.it.next();
-                        Some(Token::RIGHTBRACE)
+                        self.bump();
+                        Ok(Token::RIGHTBRACE)
                     },
                     '(' => {
-                        self.it.next();
-                        Some(Token::LEFTPARENTHESIS)
+                        self.bump();
+                        Ok(Token::LEFTPARENTHESIS)
                     },
                     ')' => {
-                        self.it.next();
-                        Some(Token::RIGHTPARENTHESIS)
+                        self.bump();
+                        Ok(Token::RIGHTPARENTHESIS)
                     },
                     '&' => {
-                        self.it.next();
-                        if let Some('&') = self.it.next() {
-                            Some(Token::AND)
+                        self.bump();
+                        if let Some('&') = self.peek() {
+                            self.bump();
+                            Ok(Token::AND)
                         }
                         else {
-                            Some(Token::UNKNOWN("&"))
+                            Err((LexError::UnexpectedChar('&'), self.span_from(start)))
                         }
                     }
                     '|' => {
-                        self.it.next();
-                        if let Some('|') = self.it.next() {
-                            Some(Token::OR)
+                        self.bump();
+                        if let Some('|') = self.peek() {
+                            self.bump();
+                            Ok(Token::OR)
                         }
                         else {
-                            Some(Token::UNKNOWN("|"))
+                            Err((LexError::UnexpectedChar('|'), self.span_from(start)))
                         }
                     }
                     '<' => {
-                        self.it.next();
-                        if let Some('=') = self.it.clone().next() {
-                            self.it.next();
-                            Some(Token::LESSOREQUAL)
+                        self.bump();
+                        if let Some('=') = self.peek() {
+                            self.bump();
+                            Ok(Token::LESSOREQUAL)
                         }
                         else {
-                            Some(Token::LESSTHAN)
+                            Ok(Token::LESSTHAN)
                         }
                     },
                     '>' => {
-                        self.it.next();
-                        if let Some('=') = self.it.clone().next() {
-                            self.it.next();
-                            Some(Token::GREATEROREQUAL)
+                        self.bump();
+                        if let Some('=') = self.peek() {
+                            self.bump();
+                            Ok(Token::GREATEROREQUAL)
                         }
                         else {
-                            Some(Token::GREATERTHAN)
+                            Ok(Token::GREATERTHAN)
                         }
                     },
                     '!' => {
-                        self.it.next();
-                        if let Some('=') = self.it.clone().next() {
-                            self.it.next();
-                            Some(Token::NOTEQUAL)
+                        self.bump();
+                        if let Some('=') = self.peek() {
+                            self.bump();
+                            Ok(Token::NOTEQUAL)
                         }
                         else {
-                            Some(Token::NOT)
+                            Ok(Token::NOT)
                         }
                     },
                     '+' => {
-                        self.it.next();
-                        if let Some('+') = self.it.clone().next() {
-                            self.it.next();
-                            Some(Token::PLUSPLUS)
+                        self.bump();
+                        if let Some('+') = self.peek() {
+                            self.bump();
+                            Ok(Token::PLUSPLUS)
                         } 
                         else {
-                            Some(Token::PLUS)
+                            Ok(Token::PLUS)
                         }
                     },
                     '-' => {
-                        self.it.next();
-                        if let Some('-') = self.it.clone().next() {
-                            self.it.next();
-                            Some(Token::MINUSMINUS)
+                        self.bump();
+                        if let Some('-') = self.peek() {
+                            self.bump();
+                            Ok(Token::MINUSMINUS)
                         }
                         else {
-                            Some(Token::MINUS)
+                            Ok(Token::MINUS)
                         }
                     },
                     '*' => {
-                        self.it.next();
-                        Some(Token::TIMES)
+                        self.bump();
+                        Ok(Token::TIMES)
                     },
                     '/' => {
-                        self.it.next();
-                        Some(Token::DIVIDE)
+                        self.bump();
+                        Ok(Token::DIVIDE)
                     },
                     '.' => {
-                        self.it.next();
-                        Some(Token::DOT)
-                    },
-                    '$' => {
-                        self.it.next();
-                        Some(Token::DOLLAR)
+                        self.bump();
+                        Ok(Token::DOT)
                     },
                     'a'..='z' | 'A'..='Z' | '_' => {
                         let str = self.it.as_str();
-                        while self.it.clone().next().map_or(false, |ch| ch.is_alphanumeric()) {
-                            self.it.next();
+                        while self.peek().is_some_and(|ch| ch.is_alphanumeric() || ch == '_') {
+                            self.bump();
                         }
 
                         // check if the identifier is a reserved word
                         let identifier: &str = &str[..str.len() - self.it.as_str().len()];
                         match identifier {
-                            "array" => Some(Token::ARRAY),
-                            "boolean" => Some(Token::BOOLEAN),
-                            "break" => Some(Token::BREAK),
-                            "char" => Some(Token::CHAR),
-                            "continue" => Some(Token::CONTINUE),
-                            "do" => Some(Token::DO),
-                            "function" => Some(Token::FUNCTION),
-                            "if" => Some(Token::IF),
-                            "else" => Some(Token::ELSE),
-                            "integer" => Some(Token::INTEGER),
-                            "of" => Some(Token::OF),
-                            "string" => Some(Token::STRING),
-                            "struct" => Some(Token::STRUCT),
-                            "true" => Some(Token::TRUE),
-                            "false" => Some(Token::FALSE),
-                            "type" => Some(Token::TYPE),
-                            "var" => Some(Token::VAR),
-                            "while" => Some(Token::WHILE),
-                            _ => Some(Token::ID(identifier)),
+                            "array" => Ok(Token::ARRAY),
+                            "boolean" => Ok(Token::BOOLEAN),
+                            "break" => Ok(Token::BREAK),
+                            "char" => Ok(Token::CHAR),
+                            "continue" => Ok(Token::CONTINUE),
+                            "do" => Ok(Token::DO),
+                            "function" => Ok(Token::FUNCTION),
+                            "if" => Ok(Token::IF),
+                            "else" => Ok(Token::ELSE),
+                            "integer" => Ok(Token::INTEGER),
+                            "of" => Ok(Token::OF),
+                            "real" => Ok(Token::REAL),
+                            "return" => Ok(Token::RETURN),
+                            "string" => Ok(Token::STRING),
+                            "struct" => Ok(Token::STRUCT),
+                            "true" => Ok(Token::TRUE),
+                            "false" => Ok(Token::FALSE),
+                            "type" => Ok(Token::TYPE),
+                            "var" => Ok(Token::VAR),
+                            "while" => Ok(Token::WHILE),
+                            _ => Ok(Token::ID(identifier)),
                         }
                     },
                     '\'' => {
-                        self.it.next();
-                        let temp = self.it.next();
-                        if let Some('\'') = self.it.next() {
-                            Some(Token::CHARACTER(temp.expect("Invalid character\n")))
+                        self.bump();
+                        let value = match self.peek() {
+                            Some('\'') => {
+                                self.bump();
+                                return Err((LexError::EmptyChar, self.span_from(start)));
+                            },
+                            Some('\\') => self.escape(),
+                            Some(ch) if ch != '\n' => Ok(self.bump().unwrap()),
+                            _ => return Err((LexError::UnterminatedChar, self.span_from(start))),
+                        };
+                        if let Some('\'') = self.peek() {
+                            self.bump();
+                            value.map(Token::CHARACTER).map_err(|span| (LexError::InvalidEscape, span))
                         } else {
-                            Some(Token::UNKNOWN("'"))
+                            Err((LexError::UnterminatedChar, self.span_from(start)))
                         }
                     },
                     '"' => {
-                       self.it.next();
-                       let mut ret_token: Token = Token::UNKNOWN("AAAAAAAA");
-                       let str = self.it.as_str();
-                       while let Some(ch) = self.it.next() {
-                            match ch {
-                                '\n' => {
-                                    self.line+=1;
+                        self.bump();
+                        let opening = self.span_from(start);
+                        let str = self.it.as_str();
+                        // the value is only copied out of the source when an escape changes it
+                        let mut value: Option<String> = None;
+                        let mut invalid_escape: Option<Span> = None;
+                        loop {
+                            let raw = &str[..str.len() - self.it.as_str().len()];
+                            match self.peek() {
+                                Some('"') => {
+                                    self.bump();
+                                    break;
+                                },
+                                Some('\\') => {
+                                    let value = value.get_or_insert_with(|| raw.to_string());
+                                    match self.escape() {
+                                        Ok(ch) => value.push(ch),
+                                        Err(span) => invalid_escape = invalid_escape.or(Some(span)),
+                                    }
                                 },
-                                '"' => {
-                                    ret_token = Token::STRINGVAL(&str[..str.len() - self.it.as_str().len()-1]);
-                                }
-                                _ => ()
+                                Some(ch) => {
+                                    self.bump();
+                                    if let Some(value) = value.as_mut() {
+                                        value.push(ch);
+                                    }
+                                },
+                                // strings can span lines, so only the opening quote is pointed at
+                                None => return Err((LexError::UnterminatedString, opening)),
                             }
-                       }
+                        }
 
-                       Some(ret_token)
+                        match (invalid_escape, value) {
+                            (Some(span), _) => Err((LexError::InvalidEscape, span)),
+                            (None, Some(value)) => Ok(Token::STRINGVAL(Cow::Owned(value))),
+                            (None, None) => Ok(Token::STRINGVAL(Cow::Borrowed(&str[..str.len() - self.it.as_str().len() - 1]))),
+                        }
                     },
                     '0'..='9' => {
                         let str = self.it.as_str();
-                        while self.it.clone().next().map_or(false, |ch| ch.is_numeric()) {
-                            self.it.next();
-                        }
+                        // `0x`, `0b` and `0o` start integers in base 16, 2 and 8
+                        let radix = match (ch, self.peek_second()) {
+                            ('0', Some('x')) => 16,
+                            ('0', Some('b')) => 2,
+                            ('0', Some('o')) => 8,
+                            _ => 10
+                        };
+
+                        let mut malformed = if radix != 10 {
+                            self.bump();
+                            self.bump();
+                            !self.digits(radix)
+                        } else {
+                            self.digits(10);
+                            // In case it is a floating point number
+                            if let Some('.') = self.peek() {
+                                self.bump();
+                                !self.digits(10)
+                            } else {
+                                false
+                            }
+                        };
 
-                        // In case it is a floating point number
-                        if let Some('.') = self.it.clone().next() {
-                            self.it.next();
-                            while self.it.clone().next().map_or(false, |ch| ch.is_numeric()) {
-                                self.it.next();
-                            }   
+                        // letters right after the digits, as in `12ab`, make the whole word a bad number
+                        while self.peek().is_some_and(|ch| ch.is_alphanumeric() || ch == '_') {
+                            self.bump();
+                            malformed = true;
+                        }
+                        if malformed {
+                            return Err((LexError::MalformedNumber, self.span_from(start)));
                         }
 
-                        Some(Token::NUMERAL(&str[..str.len() - self.it.as_str().len()]))
+                        Ok(Token::NUMERAL(&str[..str.len() - self.it.as_str().len()]))
                     },
                     _ => {
-                        let str = self.it.as_str();
-                        Some(Token::UNKNOWN(str))
+                        self.bump();
+                        Err((LexError::UnexpectedChar(ch), self.span_from(start)))
                     }
                 }
             }
-            None => Some(Token::DOLLAR),
+            None => Ok(Token::DOLLAR),
         }
     }
 }
 
+impl<'a> Iterator for TokenStream<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Token<'a>> {
+        Some(self.next_spanned().token)
+    }
+}
+
 
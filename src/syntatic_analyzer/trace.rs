@@ -0,0 +1,110 @@
+use std::fmt::Write;
+
+use crate::syntatic_analyzer::nonterminals::AttribToken;
+use crate::syntatic_analyzer::rules::Rules;
+use crate::syntatic_analyzer::span::Span;
+use crate::syntatic_analyzer::terminals::Token;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceFormat {
+    // one line per step, for people
+    Text,
+    // one JSON object per line, for tools
+    JsonLines,
+}
+
+// What the parser did in a state with a lookahead
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    Shift { to: usize },
+    Reduce { rule: Rules, length: usize, goto: usize },
+    Accept,
+    Error,
+    // a token thrown away while recovering from a syntax error
+    Skip,
+    // recovery popped the stack down to the state of the step
+    Recover,
+    // a `;` taken as the end of a broken statement, see StateMachine::parse
+    ErrorStatement { goto: usize },
+}
+
+/*
+ * Log of the steps of a parse:
+ *
+ * state 0, FUNCTION at 1:1: shift to state 4
+ * state 9, LEFTPARENTHESIS at 1:11: reduce by IDD (1 symbol), goto state 12
+ */
+pub struct Trace {
+    format: TraceFormat,
+    semantic_stack: bool,
+    output: String,
+}
+
+impl Trace {
+    // With `semantic_stack` every step is followed by the semantic stack as it is after the step
+    pub fn new(format: TraceFormat, semantic_stack: bool) -> Self {
+        Trace { format, semantic_stack, output: String::new() }
+    }
+
+    pub fn output(&self) -> &str {
+        &self.output
+    }
+
+    pub(crate) fn step(&mut self, state: usize, lookahead: &Token, span: Span, step: Step, semantic_stack: &[AttribToken]) {
+        let lookahead = format!("{:?}", lookahead);
+        match self.format {
+            TraceFormat::Text => {
+                let action = match step {
+                    Step::Shift { to } => format!("shift to state {}", to),
+                    Step::Reduce { rule, length, goto } => {
+                        let plural = if length == 1 { "" } else { "s" };
+                        format!("reduce by {:?} ({} symbol{}), goto state {}", rule, length, plural, goto)
+                    },
+                    Step::Accept => String::from("accept"),
+                    Step::Error => String::from("syntax error"),
+                    Step::Skip => String::from("skip"),
+                    Step::Recover => String::from("recover"),
+                    Step::ErrorStatement { goto } => format!("end of a broken statement, goto state {}", goto),
+                };
+                writeln!(self.output, "state {}, {} at {}:{}: {}", state, lookahead, span.start.line, span.start.column, action).unwrap();
+                if self.semantic_stack {
+                    writeln!(self.output, "    semantic stack: {:?}", semantic_stack).unwrap();
+                }
+            },
+            TraceFormat::JsonLines => {
+                write!(self.output, "{{\"state\":{},\"lookahead\":{},\"line\":{},\"column\":{},",
+                       state, json_string(&lookahead), span.start.line, span.start.column).unwrap();
+                match step {
+                    Step::Shift { to } => write!(self.output, "\"action\":\"shift\",\"to\":{}", to),
+                    Step::Reduce { rule, length, goto } => write!(self.output, "\"action\":\"reduce\",\"rule\":\"{:?}\",\"length\":{},\"goto\":{}", rule, length, goto),
+                    Step::Accept => write!(self.output, "\"action\":\"accept\""),
+                    Step::Error => write!(self.output, "\"action\":\"error\""),
+                    Step::Skip => write!(self.output, "\"action\":\"skip\""),
+                    Step::Recover => write!(self.output, "\"action\":\"recover\""),
+                    Step::ErrorStatement { goto } => write!(self.output, "\"action\":\"error_statement\",\"goto\":{}", goto),
+                }.unwrap();
+                if self.semantic_stack {
+                    let entries: Vec<String> = semantic_stack.iter().map(|entry| json_string(&format!("{:?}", entry))).collect();
+                    write!(self.output, ",\"semantic_stack\":[{}]", entries.join(",")).unwrap();
+                }
+                self.output.push_str("}\n");
+            }
+        }
+    }
+}
+
+fn json_string(text: &str) -> String {
+    let mut json = String::from("\"");
+    for ch in text.chars() {
+        match ch {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\t' => json.push_str("\\t"),
+            ch if ch.is_control() => write!(json, "\\u{:04x}", u32::from(ch)).unwrap(),
+            ch => json.push(ch),
+        }
+    }
+    json.push('"');
+    json
+}
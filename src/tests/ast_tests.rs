@@ -0,0 +1,230 @@
+use crate::syntatic_analyzer::StateMachine;
+use crate::syntatic_analyzer::ast::*;
+use crate::syntatic_analyzer::nonterminals::Type;
+
+fn parse(code: &str) -> Program {
+    let mut parser = StateMachine::new(String::from(code));
+    parser.parse().unwrap()
+}
+
+fn function(program: &Program, index: usize) -> &FunctionDecl {
+    match &program.declarations[index] {
+        Declaration::Function(function) => function,
+        declaration => panic!("expected a function but found {:?}", declaration),
+    }
+}
+
+#[test]
+fn test_declarations() {
+    let program = parse("
+        type Vec = array[3] of integer
+        type Pessoa = struct {
+          nome : string;
+          x, y : integer
+        }
+        type Id = integer
+        function main(n : integer, p : Pessoa) : integer {
+          var v : Vec;
+          v[0] = n;
+          return n;
+        }
+    ");
+
+    assert_eq!(program.declarations.len(), 4);
+    match &program.declarations[0] {
+        Declaration::Type(TypeDecl { name, kind: TypeDeclKind::Array { size, element }, type_, .. }) => {
+            assert_eq!(name.name, "Vec");
+            assert_eq!(*size, 3);
+            assert_eq!(element.kind, TypeName::Integer);
+            assert_eq!(*type_, Type::Array_type_(3, Box::new(Type::Int_)));
+        },
+        declaration => panic!("unexpected {:?}", declaration),
+    }
+    match &program.declarations[1] {
+        Declaration::Type(TypeDecl { kind: TypeDeclKind::Struct { fields }, .. }) => {
+            let names: Vec<Vec<&str>> = fields.iter()
+                .map(|field| field.names.iter().map(|name| name.name.as_str()).collect())
+                .collect();
+            assert_eq!(names, vec![vec!["nome"], vec!["x", "y"]]);
+            assert_eq!(fields[1].type_.type_, Type::Int_);
+        },
+        declaration => panic!("unexpected {:?}", declaration),
+    }
+    match &program.declarations[2] {
+        Declaration::Type(TypeDecl { kind: TypeDeclKind::Alias(aliased), type_, .. }) => {
+            assert_eq!(aliased.kind, TypeName::Integer);
+            assert_eq!(*type_, Type::Int_);
+        },
+        declaration => panic!("unexpected {:?}", declaration),
+    }
+
+    let main = function(&program, 3);
+    assert_eq!(main.name.name, "main");
+    assert_eq!(main.params.len(), 2);
+    assert_eq!(main.params[1].name.name, "p");
+    assert!(matches!(&main.params[1].type_.kind, TypeName::Named(ident) if ident.name == "Pessoa"));
+    assert!(matches!(main.params[1].type_.type_, Type::Struct_type_(_)));
+    assert_eq!(main.return_type.type_, Type::Int_);
+    assert_eq!(main.body.vars.len(), 1);
+    assert_eq!(main.body.stmts.len(), 2);
+}
+
+#[test]
+fn test_expression_precedence_and_types() {
+    let program = parse("
+        function main(a : integer, b : integer) : integer {
+          var c : boolean;
+          c = a + b * 2 < 10 && !c;
+          return a;
+        }
+    ");
+
+    let main = function(&program, 0);
+    let value = match &main.body.stmts[0].kind {
+        StmtKind::Assign { target, value } => {
+            assert_eq!(target.kind, LValueKind::Variable(Ident { name: String::from("c"), span: target.span }));
+            assert_eq!(target.type_, Type::Bool_);
+            value
+        },
+        stmt => panic!("unexpected {:?}", stmt),
+    };
+
+    // ((a + (b * 2)) < 10) && (!c)
+    let ExprKind::Binary { op: BinaryOp::And, left, right } = &value.kind else { panic!("unexpected {:?}", value) };
+    assert_eq!(value.type_, Type::Bool_);
+    assert!(matches!(right.kind, ExprKind::Unary { op: UnaryOp::Not, .. }));
+    let ExprKind::Binary { op: BinaryOp::Less, left: sum, right: ten } = &left.kind else { panic!("unexpected {:?}", left) };
+    assert_eq!(ten.kind, ExprKind::Integer(10));
+    assert_eq!(sum.type_, Type::Int_);
+    let ExprKind::Binary { op: BinaryOp::Add, right: product, .. } = &sum.kind else { panic!("unexpected {:?}", sum) };
+    assert!(matches!(product.kind, ExprKind::Binary { op: BinaryOp::Mul, .. }));
+}
+
+#[test]
+fn test_statement_kinds() {
+    let program = parse("
+        type Arr = array[4] of integer
+        type P = struct { v : Arr }
+        function f(x : integer) : integer {
+          var p : P;
+          var i : integer;
+          if (x > 0) i = 1; else { var t : integer; t = f(x - 1); }
+          while (i < 4) {
+            var t : integer;
+            p.v[i] = i++;
+            if (i == 2) break;
+            continue;
+          }
+          do i = --i; while (i > 0);
+          return i;
+        }
+    ");
+
+    let f = function(&program, 2);
+    let stmts = &f.body.stmts;
+    assert_eq!(stmts.len(), 4);
+
+    let StmtKind::If { else_branch: Some(else_branch), .. } = &stmts[0].kind else { panic!("unexpected {:?}", stmts[0]) };
+    let StmtKind::Block(block) = &else_branch.kind else { panic!("unexpected {:?}", else_branch) };
+    let StmtKind::Assign { value, .. } = &block.stmts[0].kind else { panic!("unexpected {:?}", block.stmts[0]) };
+    let ExprKind::Call { function, args } = &value.kind else { panic!("unexpected {:?}", value) };
+    assert_eq!(function.name, "f");
+    assert_eq!(args.len(), 1);
+    assert_eq!(value.type_, Type::Int_);
+
+    let StmtKind::While { id, body, .. } = &stmts[1].kind else { panic!("unexpected {:?}", stmts[1]) };
+    let StmtKind::Block(block) = &body.kind else { panic!("unexpected {:?}", body) };
+    let StmtKind::Assign { target, value } = &block.stmts[0].kind else { panic!("unexpected {:?}", block.stmts[0]) };
+    let LValueKind::Index { base, index } = &target.kind else { panic!("unexpected {:?}", target) };
+    assert_eq!(target.type_, Type::Int_);
+    assert!(matches!(&base.kind, LValueKind::Field { field, .. } if field.name == "v"));
+    assert!(matches!(base.type_, Type::Array_type_(4, _)));
+    assert!(matches!(index.kind, ExprKind::LValue(_)));
+    assert!(matches!(value.kind, ExprKind::IncDec { op: IncDecOp::PostIncrement, .. }));
+    assert!(matches!(block.stmts[1].kind, StmtKind::If { else_branch: None, .. }));
+    assert_eq!(block.stmts[2].kind, StmtKind::Continue(*id));
+
+    let StmtKind::DoWhile { id: do_id, body, .. } = &stmts[2].kind else { panic!("unexpected {:?}", stmts[2]) };
+    assert_ne!(do_id, id);
+    assert!(matches!(&body.kind, StmtKind::Assign { value, .. } if matches!(value.kind, ExprKind::IncDec { op: IncDecOp::PreDecrement, .. })));
+    assert!(matches!(&stmts[3].kind, StmtKind::Return(value) if value.type_ == Type::Int_));
+}
+
+#[test]
+fn test_literals_and_spans() {
+    let code = "function f(x : integer) : integer {
+  var s : string;
+  var c : char;
+  s = \"abc\";
+  c = 'z';
+  x = -(x + 1);
+  return x;
+}";
+    let program = parse(code);
+    let f = function(&program, 0);
+
+    let StmtKind::Assign { value, .. } = &f.body.stmts[0].kind else { panic!() };
+    assert_eq!(value.kind, ExprKind::String(String::from("abc")));
+    assert_eq!(&code[value.span.range()], "\"abc\"");
+
+    let StmtKind::Assign { value, .. } = &f.body.stmts[1].kind else { panic!() };
+    assert_eq!(value.kind, ExprKind::Char('z'));
+
+    let StmtKind::Assign { value, .. } = &f.body.stmts[2].kind else { panic!() };
+    assert_eq!(&code[value.span.range()], "-(x + 1)");
+    assert!(matches!(value.kind, ExprKind::Unary { op: UnaryOp::Negate, .. }));
+    assert_eq!(&code[f.body.stmts[2].span.range()], "x = -(x + 1);");
+    assert_eq!(&code[f.span.range()], code);
+}
+
+#[test]
+fn test_escaped_literals() {
+    let code = r#"function f(x : integer) : integer {
+  var s : string;
+  var c : char;
+  s = "say \"hi\"\n";
+  c = '\0';
+  return x;
+}"#;
+    let program = parse(code);
+    let f = function(&program, 0);
+
+    let StmtKind::Assign { value, .. } = &f.body.stmts[0].kind else { panic!() };
+    assert_eq!(value.kind, ExprKind::String(String::from("say \"hi\"\n")));
+    assert_eq!(&code[value.span.range()], r#""say \"hi\"\n""#);
+
+    let StmtKind::Assign { value, .. } = &f.body.stmts[1].kind else { panic!() };
+    assert_eq!(value.kind, ExprKind::Char('\0'));
+}
+
+#[test]
+fn test_reals() {
+    let code = "function f(n : integer) : real {
+  var x : real;
+  x = real(n) / 0.25;
+  return x;
+}";
+    let program = parse(code);
+    let f = function(&program, 0);
+    assert_eq!(f.return_type.kind, TypeName::Real);
+
+    let StmtKind::Assign { value, .. } = &f.body.stmts[0].kind else { panic!() };
+    let ExprKind::Binary { left, right, .. } = &value.kind else { panic!("unexpected {:?}", value) };
+    assert_eq!(value.type_, Type::Real_);
+    assert_eq!(right.kind, ExprKind::Real(0.25));
+    let ExprKind::Convert(operand) = &left.kind else { panic!("unexpected {:?}", left) };
+    assert_eq!((&left.type_, &operand.type_), (&Type::Real_, &Type::Int_));
+    assert_eq!(&code[left.span.range()], "real(n)");
+}
+
+#[test]
+fn test_no_tree_for_invalid_programs() {
+    let mut parser = StateMachine::new(String::from("
+        function f(x : integer) : integer {
+          var b : boolean;
+          b = y;
+        }
+    "));
+    // the tree is only handed out for valid programs
+    assert!(parser.parse().is_err());
+}
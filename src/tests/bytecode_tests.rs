@@ -0,0 +1,325 @@
+use super::common::checked;
+use crate::bytecode::{Instruction, Layout, Module};
+use crate::{compile, parse_bytecode_call, parse_call, run, run_bytecode, ErrorCode, Value};
+
+// Runs `call` on the VM, checking that the interpreter gives the same result
+fn call(code: &str, call: &str) -> Result<Value, String> {
+    let program = checked(code);
+    let module = compile(&program);
+    let (function, args) = parse_bytecode_call(&module, call).unwrap();
+    assert_eq!(parse_call(&program, call), Ok((function.clone(), args.clone())));
+    let value = run_bytecode(&module, &function, args.clone()).map_err(|errors| errors.render("main.ss", code));
+    let expected = run(&program, &function, args).map_err(|errors| errors.render("main.ss", code));
+    assert_eq!(value, expected);
+    value
+}
+
+const PROGRAMS: &str = "type Row = array[3] of integer
+type Grid = array[2] of Row
+type Point = struct { x, y : integer; name : string }
+
+function fact(n : integer) : integer {
+    var r : integer;
+    if (n <= 1) return 1;
+    return n * fact(n - 1);
+}
+
+function sum(n : integer) : integer {
+    var i, s, t : integer;
+    i = 0;
+    while (true) {
+        var skip : boolean;
+        t = ++i;
+        skip = i == 2;
+        if (skip) continue;
+        if (i > n) break;
+        s = s + i;
+    }
+    do {
+        var i : integer;
+        i = 5;
+        t = t - 1;
+        if (t == 3) continue;
+        s = s + i;
+    } while (t > 0);
+    return s * 1000 + i;
+}
+
+function grid(k : integer) : integer {
+    var g : Grid;
+    var r : Row;
+    var p : Point;
+    var i, j : integer;
+    i = 0;
+    while (i < 2) {
+        var t : integer;
+        j = 0;
+        while (j < 3) { var u : integer; g[i][j] = i * 3 + j; u = g[i][j]++; j = j + 1; }
+        i = i + 1;
+    }
+    r = g[1];
+    r[0] = 100;
+    p.x = g[1][0];
+    p.y = r[0];
+    return p.x * 1000 + p.y + g[k][2];
+}
+
+function point(x : integer) : Point {
+    var p : Point;
+    p.x = x;
+    p.name = \"p\" + \"1\";
+    return p;
+}
+
+function f(x : real, c : char, s : string, b : boolean) : string {
+    var n : integer;
+    n = integer(x * 2.0);
+    if (c < 'm' && !b || s == \"\") return \"first\";
+    if (real(n) > x) return s + \"!\";
+    return \"last\";
+}
+
+function wrap(n : integer) : integer {
+    var x : integer;
+    return -n * 2;
+}";
+
+#[test]
+fn test_same_results_as_the_interpreter() {
+    assert_eq!(call(PROGRAMS, "fact(10)"), Ok(Value::Integer(3628800)));
+    assert_eq!(call(PROGRAMS, "sum(4)"), Ok(Value::Integer(28005)));
+    assert_eq!(call(PROGRAMS, "sum(0)"), Ok(Value::Integer(5001)));
+    assert_eq!(call(PROGRAMS, "grid(0)"), Ok(Value::Integer(4103)));
+    assert_eq!(call(PROGRAMS, "grid(1)").unwrap(), Value::Integer(4106));
+    assert_eq!(call(PROGRAMS, "point(7)").unwrap().to_string(), "{ x = 7, y = 0, name = \"p1\" }");
+    assert_eq!(call(PROGRAMS, "f(1.5, 'a', \"x\", false)"), Ok(Value::String(String::from("first"))));
+    assert_eq!(call(PROGRAMS, "f(1.5, 'z', \"x\", false)"), Ok(Value::String(String::from("x!"))));
+    assert_eq!(call(PROGRAMS, "f(-1.5, 'z', \"x\", true)"), Ok(Value::String(String::from("last"))));
+    assert_eq!(call(PROGRAMS, "wrap(-2147483647)"), Ok(Value::Integer(-2)));
+}
+
+#[test]
+fn test_disassembly() {
+    let module = compile(&checked("type P = struct { x : integer; b : boolean }
+function f(n : integer) : integer {
+    var p : P;
+    p.b = n > 0 || false;
+    if (p.b) {
+        var n : integer;
+        n = 1;
+        p.x = n;
+    }
+    return p.x;
+}"));
+    assert_eq!(module.to_string(), "\
+constants:
+    #0 = 1
+    #1 = 0
+    #2 = true
+    #3 = false
+types:
+    %0 = integer
+    %1 = boolean
+    %2 = struct P { x : %0; b : %1 }
+function f(n : %0) : %0
+    local p : %2
+    local n.1 : %0
+    0000  const #0            ; 1
+    0001  load n
+    0002  const #1            ; 0
+    0003  greater
+    0004  jump_if_false +2    ; to 0007
+    0005  const #2            ; true
+    0006  jump +1             ; to 0008
+    0007  const #3            ; false
+    0008  store p 1
+    0009  const #0            ; 1
+    0010  load p 1
+    0011  jump_if_false +5    ; to 0017
+    0012  const #0            ; 1
+    0013  store n.1
+    0014  const #1            ; 0
+    0015  load n.1
+    0016  store p 1
+    0017  const #1            ; 0
+    0018  load p 1
+    0019  return
+");
+}
+
+#[test]
+fn test_runtime_errors() {
+    let code = "type A = array[4] of integer
+
+function get(a : A, i : integer) : integer {
+    var x : integer;
+    return a[i];
+}
+
+function f(n : integer) : integer {
+    var a : A;
+    if (n == 0) return 1 / n;
+    return get(a, n);
+}
+
+function down(n : integer) : integer {
+    var x : integer;
+    return down(n + 1);
+}
+
+function late(n : integer) : integer {
+    var a : A;
+    var i : integer;
+    i = n;
+    a[i] = 10 / (n - 4);
+    return a[i++] * 10 + i;
+}";
+    // indices are checked after the value assigned is computed, as the interpreter does
+    assert!(call(code, "late(4)").unwrap_err().starts_with("error[E0301]: division by zero"));
+    assert!(call(code, "late(5)").unwrap_err().starts_with("error[E0300]: index 5 is out of bounds"));
+    assert_eq!(call(code, "late(1)"), Ok(Value::Integer(-28)));
+    assert!(call(code, "f(0)").unwrap_err().starts_with("error[E0301]: division by zero\n  --> main.ss:10:24\n"));
+    assert_eq!(call(code, "f(3)"), Ok(Value::Integer(0)));
+    assert!(call(code, "f(4)").unwrap_err().contains("in this call to `get`"));
+    assert!(call(code, "f(-1)").unwrap_err().starts_with("error[E0300]: index -1 is out of bounds"));
+
+    let module = compile(&checked(code));
+    let errors = run_bytecode(&module, "down", vec![Value::Integer(0)]).unwrap_err();
+    assert_eq!(errors[0].code, ErrorCode::StackOverflow);
+    assert!(errors[0].labels.is_empty());
+}
+
+#[test]
+fn test_string_indexing() {
+    let code = "type Names = array[2] of string
+
+function swap(s : string, i : integer) : string {
+    var c : char;
+    var names : Names;
+    names[1] = s;
+    c = names[1][i];
+    names[1][i] = names[1][0];
+    names[1][0] = c;
+    return names[1];
+}";
+    assert_eq!(call(code, "swap(\"abc\", 2)"), Ok(Value::String(String::from("cba"))));
+    assert_eq!(call(code, "swap(\"\u{e9}t\u{e9}!\", 3)"), Ok(Value::String(String::from("!t\u{e9}\u{e9}"))));
+    assert!(call(code, "swap(\"abc\", 3)").unwrap_err().starts_with("\
+error[E0300]: index 3 is out of bounds for a string of 3 characters
+ --> main.ss:7:18
+"));
+    assert!(call(code, "swap(\"\", -1)").unwrap_err().starts_with("error[E0300]: index -1 is out of bounds for a string of 0 characters"));
+
+    let module = compile(&checked("function f(s : string) : char {
+    var c : char;
+    s[1] = 'x';
+    c = s[0];
+    return c;
+}"));
+    assert!(module.to_string().ends_with("\
+    0000  const #0            ; 1
+    0001  const #1            ; 'x'
+    0002  store_char s 1
+    0003  const #2            ; 0
+    0004  load_char s 1
+    0005  store c
+    0006  load c
+    0007  return
+"));
+    assert_eq!(Module::from_ssbc(&module.to_ssbc()).unwrap(), module);
+}
+
+#[test]
+fn test_ssbc() {
+    let module = compile(&checked(PROGRAMS));
+    let bytes = module.to_ssbc();
+    assert!(bytes.starts_with(b"SSBC\x01\x00"));
+    let loaded = Module::from_ssbc(&bytes).unwrap();
+    assert_eq!(loaded, module);
+    assert_eq!(run_bytecode(&loaded, "fact", vec![Value::Integer(5)]), Ok(Value::Integer(120)));
+
+    assert_eq!(Module::from_ssbc(b"\x7fELF").unwrap_err(), "not a Simplescript bytecode file");
+    assert_eq!(Module::from_ssbc(&bytes[..bytes.len() - 1]).unwrap_err(), "the file is truncated");
+    let mut longer = bytes.clone();
+    longer.push(0);
+    assert_eq!(Module::from_ssbc(&longer).unwrap_err(), "unexpected bytes after the last function");
+
+    // everything an instruction refers to must exist
+    let mut broken = module.clone();
+    broken.functions[0].code[0] = Instruction::Call(99);
+    assert_eq!(Module::from_ssbc(&broken.to_ssbc()).unwrap_err(), "in function `fact`: instruction 0000 refers to nothing");
+    let mut broken = module.clone();
+    broken.functions[0].code.push(Instruction::Jump(-100));
+    assert_eq!(Module::from_ssbc(&broken.to_ssbc()).unwrap_err(), format!("in function `fact`: instruction {:04} refers to nothing",
+                                                                          broken.functions[0].code.len() - 1));
+    let mut broken = module.clone();
+    broken.functions[1].locals[0] = 99;
+    assert_eq!(Module::from_ssbc(&broken.to_ssbc()).unwrap_err(), "in function `sum`: invalid type %99");
+
+    // the VM makes the values of a type when a function starts, their size is bounded before it runs
+    let mut broken = module.clone();
+    broken.types.push(Layout::Array { length: u32::MAX, element: 0 });
+    assert_eq!(Module::from_ssbc(&broken.to_ssbc()).unwrap_err(),
+               format!("the values of type %{} take more than 2147483647 bytes", broken.types.len() - 1));
+    let mut broken = module;
+    for _ in 0..300 {
+        let element = broken.types.len() as u16 - 1;
+        broken.types.push(Layout::Array { length: 1, element });
+    }
+    assert!(Module::from_ssbc(&broken.to_ssbc()).unwrap_err().ends_with("is nested more than 256 deep"));
+}
+
+// Loads `f` of a small module with its code replaced, as a corrupted .ssbc file would have it, and calls it
+fn run_corrupted(code: Vec<Instruction>) -> String {
+    let mut module = compile(&checked("type Pair = array[2] of integer
+
+function f(n : integer) : integer {
+    var s : string;
+    var r : Pair;
+    s = \"a\";
+    return n;
+}"));
+    module.functions[0].code = code;
+    let loaded = Module::from_ssbc(&module.to_ssbc()).unwrap();
+    let errors = run_bytecode(&loaded, "f", vec![Value::Integer(1)]).unwrap_err();
+    assert_eq!(errors[0].code, ErrorCode::InvalidBytecode);
+    errors[0].message.clone()
+}
+
+#[test]
+fn test_corrupted_ssbc() {
+    assert_eq!(run_corrupted(vec![Instruction::Add]), "invalid bytecode: the operand stack is empty");
+    assert_eq!(run_corrupted(vec![Instruction::Load { local: 0, depth: 3 }]), "invalid bytecode: fewer than 3 keys on the operand stack");
+    assert_eq!(run_corrupted(vec![Instruction::Const(0), Instruction::Load { local: 2, depth: 1 }]), "invalid bytecode: \"a\" is not a key");
+    assert_eq!(run_corrupted(vec![Instruction::Load { local: 0, depth: 0 }, Instruction::Load { local: 0, depth: 1 }]),
+               "invalid bytecode: 1 has no part 1");
+    assert_eq!(run_corrupted(vec![Instruction::Load { local: 0, depth: 0 }, Instruction::Load { local: 0, depth: 0 }, Instruction::Add,
+                                  Instruction::Load { local: 2, depth: 1 }]),
+               "invalid bytecode: [0, 0] has no part 2");
+    assert_eq!(run_corrupted(vec![Instruction::IncDec { local: 1, depth: 0, delta: 1, prefix: true }]),
+               "invalid bytecode: `++` or `--` applied to \"\"");
+    assert_eq!(run_corrupted(vec![Instruction::CheckIndex { length: 2, depth: 0 }]), "invalid bytecode: no integer to check as an index");
+    assert_eq!(run_corrupted(vec![Instruction::Load { local: 0, depth: 0 }, Instruction::Const(0), Instruction::Add]),
+               "invalid bytecode: Add applied to 1 and \"a\"");
+    assert_eq!(run_corrupted(vec![Instruction::Load { local: 0, depth: 0 }, Instruction::Not]), "invalid bytecode: `!` applied to 1");
+    assert_eq!(run_corrupted(vec![Instruction::Load { local: 0, depth: 0 }, Instruction::JumpIfFalse(0)]),
+               "invalid bytecode: condition evaluated to 1");
+    assert_eq!(run_corrupted(vec![Instruction::Load { local: 0, depth: 0 }, Instruction::LoadChar { local: 2, depth: 1 }]),
+               "invalid bytecode: [0, 0] is not a string");
+    assert_eq!(run_corrupted(vec![Instruction::Load { local: 0, depth: 0 }, Instruction::LoadChar { local: 1, depth: 0 }]),
+               "invalid bytecode: no key leads to a string");
+    assert_eq!(run_corrupted(vec![Instruction::Load { local: 0, depth: 0 }, Instruction::Load { local: 0, depth: 0 },
+                                  Instruction::StoreChar { local: 1, depth: 1 }]),
+               "invalid bytecode: 1 stored in a string");
+    assert_eq!(run_corrupted(vec![Instruction::Call(0)]), "invalid bytecode: `f` is called with fewer than 1 arguments");
+    assert_eq!(run_corrupted(vec![Instruction::Jump(0)]), "invalid bytecode: the code of `f` ends without returning");
+    assert_eq!(run_corrupted(vec![Instruction::Return]), "invalid bytecode: the operand stack is empty");
+}
+
+#[test]
+fn test_parse_bytecode_call() {
+    let module = compile(&checked("function f(n : integer, x : real) : integer { var y : integer; return n; }"));
+    assert_eq!(parse_bytecode_call(&module, "f(1, 2.5)"), Ok((String::from("f"), vec![Value::Integer(1), Value::Real(2.5)])));
+    assert_eq!(parse_bytecode_call(&module, "g(1)").unwrap_err(), "the program has no function `g`");
+    assert_eq!(parse_bytecode_call(&module, "f(1, 2)").unwrap_err(), "argument 2 of `f` must be real but `2` was given");
+}
@@ -0,0 +1,135 @@
+use std::path::Path;
+use std::process::Command;
+
+use super::common::{same_as_the_interpreter, CALLS, PROGRAMS, STRINGS, STRING_CALLS};
+use crate::backend::c::RUNTIME;
+use crate::{emit_c, TypedAst, Value};
+
+// The generated C without the runtime it starts with
+fn generated(code: &str, call: Option<&str>) -> String {
+    super::common::generated(code, call, emit_c, RUNTIME)
+}
+
+const PROGRAM: &str = "type Row = array[2] of integer
+type int = struct { main : Row; name : string }
+
+function main(n : integer) : int {
+    var r : int;
+    var i : integer;
+    r.main[i++] = n / (i - 2);
+    r.name = \"a\\\"b\" + \"?\";
+    if (r.main[0] < 0 || r.name != \"\") i = 0; else i = 1;
+    return r;
+}";
+
+#[test]
+fn test_generated_code() {
+    assert_eq!(generated(PROGRAM, Some("main(3)")), "
+struct Row {
+    int32_t items[2];
+};
+
+struct int_ {
+    struct Row main_;
+    ss_string name;
+};
+
+static void ss_print_Row(struct Row value) {
+    uint32_t i;
+    putchar('[');
+    for (i = 0; i < 2; i++) {
+        if (i > 0) {
+            fputs(\", \", stdout);
+        }
+        ss_print_integer(value.items[i]);
+    }
+    putchar(']');
+}
+
+static void ss_print_int_(struct int_ value) {
+    fputs(\"{ main = \", stdout);
+    ss_print_Row(value.main_);
+    fputs(\", name = \", stdout);
+    ss_print_string(value.name);
+    fputs(\" }\", stdout);
+}
+
+struct int_ main_(int32_t);
+
+struct int_ main_(int32_t n) {
+    int32_t ss_t0;
+    int32_t ss_t1;
+    struct int_ r = {0};
+    int32_t i = 0;
+    ss_t0 = ss_step(&i, 1, false);
+    ss_t1 = ss_div(n, ss_sub(i, 2), 7, 19);
+    ss_index(ss_t0, 2, 7, 12);
+    r.main_.items[ss_t0] = ss_t1;
+    r.name = ss_concat(SS_STRING(\"a\\\"b\"), SS_STRING(\"?\"));
+    if ((r.main_.items[ss_index(0, 2, 9, 16)] < 0) || (ss_compare_string(r.name, SS_STRING(\"\")) != 0)) {
+        i = 0;
+    } else {
+        i = 1;
+    }
+    return r;
+}
+
+int main(void) {
+    ss_print_int_(main_(3));
+    putchar('\\n');
+    return 0;
+}
+");
+}
+
+#[test]
+fn test_string_indexing() {
+    // chars are whole Unicode characters, the runtime finds them in the UTF-8 of the strings
+    let code = "function f(s : string, i : integer) : char {
+    var c : char;
+    c = s[i];
+    s[i] = '\u{e9}';
+    if (c < 'a') return s[i + 1];
+    return c;
+}";
+    assert_eq!(generated(code, None), "
+uint32_t f(ss_string, int32_t);
+
+uint32_t f(ss_string s, int32_t i) {
+    uint32_t c = 0;
+    c = ss_char_at(s, i, 3, 11);
+    s = ss_with_char(s, i, 0xe9, 4, 7);
+    if (ss_compare_char(c, 'a') < 0) {
+        return ss_char_at(s, ss_add(i, 1), 5, 27);
+    }
+    return c;
+}
+");
+}
+
+#[test]
+fn test_without_entry() {
+    let c = generated(PROGRAM, None);
+    assert!(!c.contains("int main(void)"));
+    // nothing prints the struct, so its printer is left out
+    assert!(!c.contains("ss_print_int_"));
+    assert!(c.ends_with("    return r;\n}\n"));
+}
+
+/*
+ * Compiles the programs with `cc`, checking that they print what the interpreter returns.
+ * Needs a C compiler, run with
+ * cargo test c_backend -- --ignored
+ */
+#[test]
+#[ignore]
+fn test_same_results_as_the_interpreter() {
+    let run_c = |dir: &Path, program: &TypedAst, function: &str, args: &[Value]| {
+        std::fs::write(dir.join("main.c"), emit_c(program, Some((function, args)))).unwrap();
+        let status = Command::new("cc").args(["-std=c99", "-o", "main", "main.c"]).current_dir(dir).status().unwrap();
+        assert!(status.success());
+        Command::new(dir.join("main")).output().unwrap()
+    };
+    same_as_the_interpreter("c", PROGRAMS, &CALLS, |value| value.to_string(), run_c);
+    same_as_the_interpreter("c", STRINGS, &STRING_CALLS, |value| value.to_string(), run_c);
+}
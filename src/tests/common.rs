@@ -0,0 +1,148 @@
+use std::path::Path;
+use std::process::Output;
+
+use crate::{check, parse, parse_call, run, TypedAst, Value};
+
+pub fn checked(code: &str) -> TypedAst {
+    check(&parse(code).unwrap())
+}
+
+// emit_c or emit_llvm
+type Emit = fn(&TypedAst, Option<(&str, &[Value])>) -> String;
+
+// The code `emit` generates for the program, making `call` from its entry point, without the runtime it starts with
+pub fn generated(code: &str, call: Option<&str>, emit: Emit, runtime: &str) -> String {
+    let program = checked(code);
+    let entry = call.map(|call| parse_call(&program, call).unwrap());
+    let generated = emit(&program, entry.as_ref().map(|(function, args)| (function.as_str(), args.as_slice())));
+    generated.strip_prefix(runtime).expect("the runtime comes first").to_string()
+}
+
+// Programs the backends run to compare them with the interpreter
+pub const PROGRAMS: &str = "type A = array[4] of integer
+type P = struct { x : integer; s : string; a : A }
+
+function fact(n : integer) : integer {
+    var r : integer;
+    if (n <= 1) return 1;
+    return n * fact(n - 1);
+}
+
+function sum(n : integer) : integer {
+    var i, s : integer;
+    while (true) {
+        var skip : boolean;
+        skip = ++i == 2;
+        if (skip) continue;
+        if (i > n) break;
+        s = s + i;
+    }
+    do { var i : integer; i = 5; s = s + i; } while (s < 100);
+    return s;
+}
+
+function late(n : integer) : integer {
+    var a : A;
+    var i : integer;
+    i = n;
+    a[i] = 10 / (n - 4);
+    return a[i++] * 10 + i;
+}
+
+function cmp(n : integer) : boolean {
+    var p, q : P;
+    var c : char;
+    p.s = \"ab\";
+    q.s = \"a\" + \"b\";
+    q.a[3] = n;
+    c = 'x';
+    return p < q && p != q && c > 'a' && \"ab\" < \"abc\";
+}
+
+function point(x : real) : P {
+    var p : P;
+    p.x = integer(x * 2.5) / 0 + 1;
+    return p;
+}
+
+function show(x : real) : string {
+    var s : string;
+    s = \"tab\\t\" + \"'quote'\";
+    if (x < 0.0) return s;
+    return \"\";
+}
+
+function wrap(n : integer) : integer {
+    var x : integer;
+    return -n * 2;
+}";
+
+// The calls of PROGRAMS, some end with a runtime error
+pub const CALLS: [&str; 10] = ["fact(10)", "sum(4)", "late(1)", "late(4)", "late(5)", "cmp(0)", "cmp(-1)", "point(1.0)",
+                               "show(-1.0)", "wrap(-2147483647)"];
+
+// Programs indexing strings, non-ASCII characters included, their calls only pass integers
+pub const STRINGS: &str = "type Names = array[2] of string
+
+function swap(i : integer) : string {
+    var s : string;
+    var c : char;
+    s = \"\u{e9}t\u{e9}!\";
+    c = s[i];
+    s[i] = s[0];
+    s[0] = c;
+    return s;
+}
+
+function at(i : integer) : char {
+    var s : string;
+    s = \"d\u{e9}j\u{e0}\\t'\";
+    return s[i];
+}
+
+function empty(i : integer) : char {
+    var s : string;
+    return s[i];
+}
+
+function names(i : integer) : string {
+    var n : Names;
+    n[0] = \"ab\";
+    n[1] = \"\u{e9}t\u{e9}\";
+    n[i][i] = n[0][1];
+    return n[i];
+}";
+
+// The calls of STRINGS
+pub const STRING_CALLS: [&str; 9] = ["swap(3)", "swap(4)", "at(3)", "at(4)", "at(5)", "at(-1)", "empty(0)", "names(1)", "names(2)"];
+
+/*
+ * Makes each call of `calls` to `code` with a backend: `run_generated` writes the program
+ * generated for the call in an empty directory, builds it there and runs it. It must print what
+ * the interpreter returns, written by `printed`, or stop with exit code 5 and the runtime error
+ * it reports.
+ */
+pub fn same_as_the_interpreter(backend: &str, code: &str, calls: &[&str], printed: impl Fn(&Value) -> String,
+                               run_generated: impl Fn(&Path, &TypedAst, &str, &[Value]) -> Output) {
+    let program = checked(code);
+    for call in calls {
+        let (function, args) = parse_call(&program, call).unwrap();
+        let dir = std::env::temp_dir().join(format!("rulox-{}-backend-{}-{}", backend, std::process::id(), function));
+        std::fs::create_dir_all(&dir).unwrap();
+        let output = run_generated(&dir, &program, &function, &args);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        match run(&program, &function, args) {
+            Ok(value) => {
+                assert_eq!(output.status.code(), Some(0), "{}", call);
+                assert_eq!(String::from_utf8(output.stdout).unwrap(), format!("{}\n", printed(&value)));
+            },
+            Err(errors) => {
+                let error = &errors[0];
+                assert_eq!(output.status.code(), Some(5), "{}", call);
+                assert_eq!(String::from_utf8(output.stderr).unwrap(),
+                           format!("error[{}]: {} at {}:{}\n", error.code.as_str(), error.message, error.span.start.line, error.span.start.column));
+            },
+        }
+    }
+}
@@ -0,0 +1,296 @@
+use crate::run;
+
+// Runs the driver with `stdin` as the standard input, returning the exit code, stdout and stderr
+fn rulox(args: &[&str], stdin: &str) -> (i32, String, String) {
+    let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+    let mut stdout = vec![];
+    let mut stderr = vec![];
+    let code = run(&args, &mut stdin.as_bytes(), &mut stdout, &mut stderr);
+    (code, String::from_utf8(stdout).unwrap(), String::from_utf8(stderr).unwrap())
+}
+
+const PROGRAM: &str = "function f(n : integer) : integer {
+    var x : integer;
+    x = n + 1;
+    return x;
+}";
+
+#[test]
+fn test_emit_ir_by_default() {
+    let (code, stdout, stderr) = rulox(&["-"], PROGRAM);
+    assert_eq!(code, 0);
+    assert_eq!(stderr, "");
+    assert_eq!(stdout, "\
+function f(n : 4) : 4
+    local x : 4
+    t0 = n + 1
+    x = t0
+    return x
+end
+");
+}
+
+#[test]
+fn test_emit_tokens() {
+    let (code, stdout, _) = rulox(&["--emit", "tokens", "-"], "var x : integer;");
+    assert_eq!(code, 0);
+    assert_eq!(stdout, "1:1 VAR\n1:5 ID(\"x\")\n1:7 COLON\n1:9 INTEGER\n1:16 SEMICOLON\n1:17 DOLLAR\n");
+}
+
+#[test]
+fn test_emit_symbols() {
+    let code = "type Id = integer
+function f(n : Id) : integer {
+    var x, y : integer;
+    {
+        var c : char;
+        c = 'a';
+    }
+    x = n;
+    return x;
+}";
+    let (code, stdout, _) = rulox(&["--emit=symbols", "-"], code);
+    assert_eq!(code, 0);
+    assert_eq!(stdout, "\
+1:6 type Id = integer
+2:10 function f : (integer) -> integer
+  2:12 param n : integer
+  3:9 var x : integer
+  3:12 var y : integer
+    5:13 var c : char
+");
+}
+
+#[test]
+fn test_emit_ast() {
+    let (code, stdout, _) = rulox(&["--emit", "ast", "-"], PROGRAM);
+    assert_eq!(code, 0);
+    assert!(stdout.starts_with("Program {"));
+}
+
+#[test]
+fn test_exit_codes() {
+    let (code, stdout, stderr) = rulox(&["-"], "function f(n : integer) : integer { var x : integer; x = # ; }");
+    assert_eq!(code, 2);
+    assert_eq!(stdout, "");
+    assert!(stderr.starts_with("error[E0010]: unexpected character `#`\n --> <stdin>:1:58\n"));
+
+    let (code, _, stderr) = rulox(&["-"], "function f(n : integer) : integer { /* var x : integer; }");
+    assert_eq!(code, 2);
+    assert_eq!(stderr, "error[E0011]: unterminated block comment\n --> <stdin>:1:37\n  |\n1 | function f(n : integer) : integer { /* var x : integer; }\n  |                                     ^^\n\n");
+
+    let (code, _, stderr) = rulox(&["-"], "function f(n : integer) : string { return \"a\\qb\"; }");
+    assert_eq!(code, 2);
+    assert!(stderr.starts_with("error[E0012]: invalid escape sequence\n --> <stdin>:1:45\n"));
+
+    let (code, _, stderr) = rulox(&["-"], "function f(n : integer) : integer { var x : integer; x = ; }");
+    assert_eq!(code, 3);
+    assert!(stderr.starts_with("error[E0001]"));
+
+    let (code, _, stderr) = rulox(&["-"], "function f(n : integer) : integer { var x : integer; x = true; }");
+    assert_eq!(code, 4);
+    assert!(stderr.starts_with("error[E0201]"));
+}
+
+#[test]
+fn test_lexical_errors() {
+    let (code, stdout, stderr) = rulox(&["-"], "function f(c : char) : string {\n    c = '';\n    return 12ab | \"abc;\n}");
+    assert_eq!(code, 2);
+    assert_eq!(stdout, "");
+    // every lexical error of the file is reported, none of them stops the lexer
+    let headers: Vec<&str> = stderr.lines().filter(|line| line.starts_with("error") || line.starts_with(" -->")).collect();
+    assert_eq!(headers, vec![
+        "error[E0015]: empty character literal", " --> <stdin>:2:9",
+        "error[E0016]: malformed number literal", " --> <stdin>:3:12",
+        "error[E0010]: unexpected character `|`", " --> <stdin>:3:17",
+        "error[E0013]: unterminated string literal", " --> <stdin>:3:19",
+    ]);
+}
+
+#[test]
+fn test_several_inputs_and_output_file() {
+    let dir = std::env::temp_dir().join(format!("rulox-driver-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let input = dir.join("ok.ss");
+    let output = dir.join("out.ir");
+    std::fs::write(&input, PROGRAM).unwrap();
+
+    let (code, stdout, stderr) = rulox(&["-o", output.to_str().unwrap(), "-", input.to_str().unwrap()],
+                                       "function g(n : integer) : integer { var x : integer; x = true; }");
+    // the semantic error in the first file does not stop the second one
+    assert_eq!(code, 4);
+    assert_eq!(stdout, "");
+    assert!(stderr.contains("<stdin>:1:"));
+    let written = std::fs::read_to_string(&output).unwrap();
+    assert_eq!(written, format!("==> <stdin> <==\n\n==> {} <==\nfunction f(n : 4) : 4\n    local x : 4\n    t0 = n + 1\n    x = t0\n    return x\nend\n",
+                                input.to_str().unwrap()));
+
+    // neither does a file that can't be read, bad usage is the lowest exit code
+    let missing = dir.join("missing.ss");
+    let (code, stdout, stderr) = rulox(&[missing.to_str().unwrap(), "-", input.to_str().unwrap()],
+                                       "function g(n : integer) : integer { var x : integer; x = true; }");
+    assert_eq!(code, 1);
+    assert!(stderr.starts_with(&format!("error: could not read `{}`: ", missing.to_str().unwrap())));
+    assert!(stderr.contains("<stdin>:1:"));
+    assert!(stdout.starts_with("==> <stdin> <==\n\n==> "));
+    assert!(stdout.ends_with("    return x\nend\n"));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_trace() {
+    let (code, stdout, stderr) = rulox(&["--trace", "json", "-"], PROGRAM);
+    assert_eq!(code, 0);
+    // the trace does not get in the way of the output
+    assert!(stdout.starts_with("function f(n : 4) : 4\n"));
+    assert!(stderr.lines().all(|line| line.starts_with("{\"state\":") && !line.contains("semantic_stack")));
+    assert!(stderr.ends_with("\"action\":\"accept\"}\n"));
+
+    let (code, _, stderr) = rulox(&["--trace=text", "--trace-stack", "-"], "function f(n : integer) : integer { var x : integer; x = ; }");
+    assert_eq!(code, 3);
+    assert!(stderr.starts_with("state 0, FUNCTION at 1:1: shift to state "));
+    assert!(stderr.contains("\n    semantic stack: ["));
+    assert!(stderr.contains("at 1:58: syntax error\n"));
+    assert!(stderr.contains("error[E0001]"));
+}
+
+#[test]
+fn test_run() {
+    let (code, stdout, stderr) = rulox(&["--run", "f(41)", "-"], PROGRAM);
+    assert_eq!((code, stdout.as_str(), stderr.as_str()), (0, "42\n", ""));
+
+    let (code, stdout, stderr) = rulox(&["--run=f(1)", "-"], "function f(n : integer) : integer { var x : integer; return n / (n - 1); }");
+    assert_eq!(code, 5);
+    assert_eq!(stdout, "");
+    assert!(stderr.starts_with("error[E0301]: division by zero\n --> <stdin>:1:61\n"));
+
+    let (code, _, stderr) = rulox(&["--run", "f(true)", "-"], PROGRAM);
+    assert_eq!(code, 1);
+    assert_eq!(stderr, "error: <stdin>: argument 1 of `f` must be integer but `true` was given\n");
+}
+
+#[test]
+fn test_usage_errors() {
+    assert_eq!(rulox(&[], "").0, 1);
+    assert_eq!(rulox(&["--emit", "machine", "-"], "").0, 1);
+    assert_eq!(rulox(&["--frobnicate", "-"], "").0, 1);
+    assert_eq!(rulox(&["--trace", "xml", "-"], "").0, 1);
+    assert_eq!(rulox(&["--trace-stack", "-"], "").0, 1);
+    assert_eq!(rulox(&["--emit", "ir", "--run", "f(1)", "-"], "").0, 1);
+    assert_eq!(rulox(&["/nonexistent/main.ss"], "").0, 1);
+
+    let (code, stdout, _) = rulox(&["--help"], "");
+    assert_eq!(code, 0);
+    assert!(stdout.starts_with("usage: rulox"));
+}
+
+#[test]
+fn test_bytecode() {
+    let dir = std::env::temp_dir().join(format!("rulox-driver-bytecode-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let ssbc = dir.join("main.ssbc");
+    let ssbc = ssbc.to_str().unwrap();
+
+    let (code, stdout, stderr) = rulox(&["--emit", "ssbc", "-o", ssbc, "-"], PROGRAM);
+    assert_eq!((code, stdout.as_str(), stderr.as_str()), (0, "", ""));
+    let (code, stdout, _) = rulox(&["--run", "f(41)", ssbc], "");
+    assert_eq!((code, stdout.as_str()), (0, "42\n"));
+    let (code, stdout, _) = rulox(&["--emit=bytecode", ssbc], "");
+    assert_eq!(code, 0);
+    assert_eq!(stdout, rulox(&["--emit=bytecode", "-"], PROGRAM).1);
+    assert!(stdout.contains("function f(n : %0) : %0\n    local x : %0\n    0000  load n\n"));
+
+    let (code, _, stderr) = rulox(&["--emit", "ir", ssbc], "");
+    assert_eq!(code, 1);
+    assert!(stderr.starts_with(&format!("error: {}: the file holds bytecode", ssbc)));
+    // runtime errors point into the source kept in the file
+    rulox(&["--emit", "ssbc", "-o", ssbc, "-"], "function f(n : integer) : integer { var x : integer; return n / (n - 1); }");
+    let (code, _, stderr) = rulox(&["--run", "f(1)", ssbc], "");
+    assert_eq!(code, 5);
+    assert!(stderr.starts_with(&format!("error[E0301]: division by zero\n --> {}:1:61\n", ssbc)));
+
+    std::fs::write(ssbc, b"SSBC\x02\x00").unwrap();
+    let (code, _, stderr) = rulox(&["--run", "f(1)", ssbc], "");
+    assert_eq!(code, 1);
+    assert_eq!(stderr, format!("error: could not load `{}`: unsupported bytecode version 2, expected 1\n", ssbc));
+
+    // a binary file can't go to the standard output or hold several programs
+    assert_eq!(rulox(&["--emit", "ssbc", "-"], PROGRAM).0, 1);
+    assert_eq!(rulox(&["--emit", "ssbc", "-o", ssbc, "-", "-"], PROGRAM).0, 1);
+
+    // a file that was tampered with stops with a runtime error instead of crashing
+    let mut module = rulox::compile(&rulox::check(&rulox::parse(PROGRAM).unwrap()));
+    module.functions[0].code.insert(0, rulox::bytecode::Instruction::Add);
+    std::fs::write(ssbc, module.to_ssbc()).unwrap();
+    let (code, _, stderr) = rulox(&["--run", "f(1)", ssbc], "");
+    assert_eq!(code, 5);
+    assert!(stderr.starts_with("error[E0303]: invalid bytecode: the operand stack is empty\n"));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_emit_c() {
+    let (code, stdout, stderr) = rulox(&["--emit", "c", "-"], PROGRAM);
+    assert_eq!((code, stderr.as_str()), (0, ""));
+    assert!(stdout.starts_with("/*\n * Runtime of the C programs generated from Simplescript"));
+    assert!(stdout.ends_with("int32_t f(int32_t);\n\nint32_t f(int32_t n) {\n    int32_t x = 0;\n    x = ss_add(n, 1);\n    return x;\n}\n"));
+
+    let (code, stdout, _) = rulox(&["--emit=c", "--run", "f(41)", "-"], PROGRAM);
+    assert_eq!(code, 0);
+    assert!(stdout.ends_with("int main(void) {\n    ss_print_integer(f(41));\n    putchar('\\n');\n    return 0;\n}\n"));
+
+    let (code, _, stderr) = rulox(&["--emit", "c", "--run", "f(true)", "-"], PROGRAM);
+    assert_eq!(code, 1);
+    assert_eq!(stderr, "error: <stdin>: argument 1 of `f` must be integer but `true` was given\n");
+}
+
+#[test]
+fn test_emit_llvm() {
+    let (code, stdout, stderr) = rulox(&["--emit", "llvm", "-"], PROGRAM);
+    assert_eq!((code, stderr.as_str()), (0, ""));
+    assert!(stdout.starts_with("; Runtime of the LLVM modules generated from Simplescript"));
+    assert!(stdout.ends_with("\
+define i32 @f(i32 %n) {
+entry:
+  %n.addr = alloca i32
+  %x = alloca i32
+  store i32 %n, ptr %n.addr
+  store i32 0, ptr %x
+  %0 = load i32, ptr %n.addr
+  %1 = add i32 %0, 1
+  store i32 %1, ptr %x
+  %2 = load i32, ptr %x
+  ret i32 %2
+}
+"));
+
+    let (code, stdout, _) = rulox(&["--emit=llvm", "--run", "f(41)", "-"], PROGRAM);
+    assert_eq!(code, 0);
+    assert!(stdout.ends_with("  %0 = call i32 @f(i32 41)\n  call void @ss.print.integer(i32 %0)\n  %1 = call i32 @putchar(i32 10)\n  ret i32 0\n}\n"));
+    assert_eq!(rulox(&["--emit", "bytecode", "--run", "f(41)", "-"], PROGRAM).0, 1);
+}
+
+#[test]
+fn test_emit_wat() {
+    let (code, stdout, stderr) = rulox(&["--emit", "wat", "-"], PROGRAM);
+    assert_eq!((code, stderr.as_str()), (0, ""));
+    assert!(stdout.starts_with("(module\n  ;; Runtime of the WebAssembly modules generated from Simplescript"));
+    assert!(stdout.ends_with("\
+  (func $f (export \"f\") (param $n i32) (result i32)
+    (local $x i32)
+    i32.const 0
+    local.set $x
+    local.get $n
+    i32.const 1
+    i32.add
+    local.set $x
+    local.get $x
+    return
+  )
+)
+"));
+    // the module has no `main` to make the call
+    assert_eq!(rulox(&["--emit", "wat", "--run", "f(41)", "-"], PROGRAM).0, 1);
+}
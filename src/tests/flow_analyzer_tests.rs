@@ -0,0 +1,132 @@
+use crate::syntatic_analyzer::StateMachine;
+use crate::syntatic_analyzer::diagnostics::{ErrorCode, Subject};
+
+// Source text where each function that can fall off its end does so
+fn fall_offs(code: &str) -> Vec<&str> {
+    let mut parser = StateMachine::new(String::from(code));
+    match parser.parse() {
+        Ok(_) => vec![],
+        Err(errors) => errors.iter()
+            .inspect(|d| assert_eq!(d.code, ErrorCode::MissingReturn, "{:?}", d))
+            .map(|d| &code[d.span.range()])
+            .collect(),
+    }
+}
+
+#[test]
+fn test_every_path_returns() {
+    let code = "
+        function f(n : integer) : integer {
+            var x : integer;
+            if (n > 0) return 1;
+            else {
+                var y : integer;
+                if (n < 0) return -1; else return 0;
+            }
+        }
+
+        function g(n : integer) : integer {
+            var x : integer;
+            while (true) {
+                var y : integer;
+                while (n > 0) break;
+                if (n == 3) return n;
+                n = n + 1;
+            }
+        }
+
+        function h(n : integer) : integer {
+            var x : integer;
+            do {
+                var y : integer;
+                x = n;
+            } while (true);
+        }
+
+        function k(n : integer) : integer {
+            var x : integer;
+            return n;
+            x = 1;
+        }
+    ";
+
+    assert_eq!(fall_offs(code), Vec::<&str>::new());
+}
+
+#[test]
+fn test_paths_falling_off() {
+    let code = "
+        function f(n : integer) : integer {
+            var x : integer;
+            if (n > 0) return 1;
+        }
+
+        function g(n : integer) : integer {
+            var x : integer;
+            if (n > 0) return 1; else { var y : integer; x = 0; }
+        }
+
+        function h(n : integer) : integer {
+            var x : integer;
+            while (true) {
+                var y : integer;
+                if (n == 3) break;
+            }
+        }
+
+        function k(n : integer) : integer {
+            var x : integer;
+            do {
+                var y : integer;
+                if (n == 3) continue;
+                return n;
+            } while (n > 0);
+        }
+    ";
+
+    assert_eq!(fall_offs(code), vec![
+        "if (n > 0) return 1;",
+        "x = 0;",
+        "while (true) {\n                var y : integer;\n                if (n == 3) break;\n            }",
+        "do {\n                var y : integer;\n                if (n == 3) continue;\n                return n;\n            } while (n > 0);",
+    ]);
+}
+
+#[test]
+fn test_missing_return_diagnostic() {
+    let code = "
+        function answer(n : integer) : boolean {
+            var x : integer;
+            x = 42;
+        }
+    ";
+
+    let mut parser = StateMachine::new(String::from(code));
+    let errors = parser.parse().unwrap_err();
+    assert_eq!(errors[0].message, "function `answer` can reach the end of its body without returning boolean");
+    assert_eq!(errors[0].subject, Some(Subject::Identifier(String::from("answer"))));
+    assert_eq!(&code[errors[0].labels[0].span.range()], "answer");
+}
+
+#[test]
+fn test_no_missing_return_after_a_syntax_error() {
+    let code = "
+        function f(n : integer) : integer {
+            var x : integer;
+            x = n;
+            return x
+        }
+
+        function g(n : integer) : integer {
+            var x : integer;
+            x = n;
+        }
+    ";
+
+    // the return of `f` was lost to the missing `;`, `g` is still checked
+    let mut parser = StateMachine::new(String::from(code));
+    let errors = parser.parse().unwrap_err();
+    let codes: Vec<ErrorCode> = errors.iter().map(|d| d.code).collect();
+    assert_eq!(codes, vec![ErrorCode::SyntaxError, ErrorCode::MissingReturn]);
+    assert_eq!(errors[1].subject, Some(Subject::Identifier(String::from("g"))));
+}
@@ -0,0 +1,268 @@
+use crate::syntatic_analyzer::StateMachine;
+use crate::intermediate_code::generate;
+
+fn dump(code: &str) -> String {
+    let mut parser = StateMachine::new(String::from(code));
+    generate(&parser.parse().unwrap()).to_string()
+}
+
+#[test]
+fn test_control_flow() {
+    let code = "function f(n : integer) : integer {
+    var i : integer;
+    i = 0;
+    while (i < n) {
+        var j : integer;
+        if (i == 3) break; else i = i + 1;
+        continue;
+    }
+    do i = i - 1; while (i > 0);
+    return i;
+}";
+
+    assert_eq!(dump(code), "\
+function f(n : 4) : 4
+    local i : 4
+    local j : 4
+    i = 0
+L0:
+    t0 = i < n
+    ifFalse t0 goto L1
+    t1 = i == 3
+    ifFalse t1 goto L2
+    goto L1
+    goto L3
+L2:
+    t2 = i + 1
+    i = t2
+L3:
+    goto L0
+    goto L0
+L1:
+L4:
+    t3 = i - 1
+    i = t3
+L5:
+    t4 = i > 0
+    if t4 goto L4
+L6:
+    return i
+end
+");
+}
+
+#[test]
+fn test_short_circuit() {
+    let code = "function f(a : boolean, b : boolean) : integer {
+    var c : boolean;
+    if (a && b || !a) c = a || b;
+    return 0;
+}";
+
+    assert_eq!(dump(code), "\
+function f(a : 1, b : 1) : 4
+    local c : 1
+    ifFalse a goto L2
+    if b goto L1
+L2:
+    if a goto L0
+L1:
+    if a goto L5
+    ifFalse b goto L3
+L5:
+    t0 = true
+    goto L4
+L3:
+    t0 = false
+L4:
+    c = t0
+L0:
+    return 0
+end
+");
+}
+
+#[test]
+fn test_aggregates() {
+    let code = "type Arr = array[10] of integer
+type Point = struct { tag : char; x, y : integer }
+type Points = array[3] of Point
+function f(ps : Points, v : Arr, s : string) : integer {
+    var i : integer;
+    var c : char;
+    ps[i].y = v[2];
+    v[i] = ps[1].x;
+    c = s[i];
+    return i;
+}";
+
+    assert_eq!(dump(code), "\
+function f(ps : 27, v : 40, s : 8) : 4
+    local i : 4
+    local c : 1
+    t0 = i * 9
+    t1 = t0 + 5
+    t2 = v[8]
+    ps[t1] = t2
+    t3 = i * 4
+    t4 = ps[10]
+    v[t3] = t4
+    t5 = s[i]
+    c = t5
+    return i
+end
+");
+}
+
+#[test]
+fn test_calls_and_shadowing() {
+    let code = "function g(a : integer, s : string) : integer {
+    var r : integer;
+    r = a;
+    return r;
+}
+function f(x : integer) : integer {
+    var x2 : integer;
+    {
+        var x2 : string;
+        x2 = \"a\" + \"b\";
+        x = g(g(x, x2) * 2, x2);
+    }
+    x2 = x++ + --x;
+    return x2;
+}";
+
+    assert_eq!(dump(code), "\
+function g(a : 4, s : 8) : 4
+    local r : 4
+    r = a
+    return r
+end
+
+function f(x : 4) : 4
+    local x2 : 4
+    local x2.1 : 8
+    t0 = \"a\" ++ \"b\"
+    x2.1 = t0
+    param x
+    param x2.1
+    t1 = call g, 2
+    t2 = t1 * 2
+    param t2
+    param x2.1
+    t3 = call g, 2
+    x = t3
+    t4 = x
+    t5 = t4 + 1
+    x = t5
+    t6 = x
+    t7 = t6 - 1
+    x = t7
+    t8 = t4 + t7
+    x2 = t8
+    return x2
+end
+");
+}
+
+#[test]
+fn test_nested_loop_targets() {
+    let code = "function f(n : integer) : integer {
+    var i : integer;
+    while (n > 0) {
+        var j : integer;
+        do {
+            var k : integer;
+            if (n == 2) break;
+            n = n - 1;
+        } while (n > 5);
+        if (n == 1) continue;
+        break;
+    }
+    return n;
+}";
+
+    // the inner `break` leaves the `do` loop, the `continue` and `break` after it the `while` loop
+    assert_eq!(dump(code), "\
+function f(n : 4) : 4
+    local i : 4
+    local j : 4
+    local k : 4
+L0:
+    t0 = n > 0
+    ifFalse t0 goto L1
+L2:
+    t1 = n == 2
+    ifFalse t1 goto L5
+    goto L4
+L5:
+    t2 = n - 1
+    n = t2
+L3:
+    t3 = n > 5
+    if t3 goto L2
+L4:
+    t4 = n == 1
+    ifFalse t4 goto L6
+    goto L0
+L6:
+    goto L1
+    goto L0
+L1:
+    return n
+end
+");
+}
+
+#[test]
+fn test_reals() {
+    let code = "function f(x : real, n : integer) : real {
+    var r : real;
+    r = x * 1.5 + real(n);
+    n = integer(-r);
+    r = real(r);
+    return r;
+}";
+
+    // converting a real to real lowers to nothing
+    assert_eq!(dump(code), "\
+function f(x : 8, n : 4) : 8
+    local r : 8
+    t0 = x * 1.5
+    t1 = (real) n
+    t2 = t0 + t1
+    r = t2
+    t3 = -r
+    t4 = (integer) t3
+    n = t4
+    r = r
+    return r
+end
+");
+}
+
+#[test]
+fn test_offsets_out_of_range() {
+    let code = "type A = array[4] of integer
+type M = array[2] of A
+function f(m : M) : integer {
+    var a : A;
+    a[1000000000] = 1;
+    a[1] = m[1][2147483647];
+    return 0;
+}";
+
+    // offsets that don't fit an integer are computed when the program runs instead of folded
+    assert_eq!(dump(code), "\
+function f(m : 32) : 4
+    local a : 16
+    t0 = 1000000000 * 4
+    a[t0] = 1
+    t1 = 2147483647 * 4
+    t2 = 16 + t1
+    t3 = m[t2]
+    a[4] = t3
+    return 0
+end
+");
+}
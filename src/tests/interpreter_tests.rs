@@ -0,0 +1,211 @@
+use super::common::checked;
+use crate::{parse_call, run, ErrorCode, Value, MAX_CALL_DEPTH};
+
+// Runs `call` on the program, the value it returns or the runtime error rendered
+fn call(code: &str, call: &str) -> Result<Value, String> {
+    let program = checked(code);
+    let (function, args) = parse_call(&program, call).unwrap();
+    run(&program, &function, args).map_err(|errors| errors.render("main.ss", code))
+}
+
+#[test]
+fn test_recursion_and_loops() {
+    let code = "function fact(n : integer) : integer {
+    var r : integer;
+    if (n <= 1) return 1;
+    return n * fact(n - 1);
+}
+
+function sum(n : integer) : integer {
+    var i, s, t : integer;
+    i = 0;
+    while (true) {
+        var skip : boolean;
+        t = ++i;
+        skip = i == 2;
+        if (skip) continue;
+        if (i > n) break;
+        s = s + i;
+    }
+    do { var u : integer; u = i--; } while (i > 0);
+    return s * 1000 + i;
+}";
+    assert_eq!(call(code, "fact(10)"), Ok(Value::Integer(3628800)));
+    assert_eq!(call(code, "sum(4)"), Ok(Value::Integer(8000)));
+    // the body of a do-while runs once before the condition is tested
+    assert_eq!(call(code, "sum(-1)"), Ok(Value::Integer(0)));
+}
+
+#[test]
+fn test_arrays_and_structs() {
+    let code = "type Row = array[3] of integer
+type Grid = array[2] of Row
+type Point = struct { x, y : integer; name : string }
+
+function f(k : integer) : integer {
+    var g : Grid;
+    var r : Row;
+    var p : Point;
+    var i, j : integer;
+    i = 0;
+    while (i < 2) {
+        var t : integer;
+        j = 0;
+        while (j < 3) { var u : integer; g[i][j] = i * 3 + j; j = j + 1; }
+        i = i + 1;
+    }
+    r = g[1];
+    r[0] = 100;
+    p.x = g[1][0];
+    p.y = r[0];
+    return p.x * 1000 + p.y + g[k][2];
+}
+
+function point(x : integer) : Point {
+    var p : Point;
+    p.x = x;
+    p.name = \"p\" + \"1\";
+    return p;
+}";
+    // arrays are copied on assignment, changing `r` leaves `g` as it was
+    assert_eq!(call(code, "f(0)"), Ok(Value::Integer(3102)));
+    assert_eq!(call(code, "point(7)").unwrap().to_string(), "{ x = 7, y = 0, name = \"p1\" }");
+}
+
+#[test]
+fn test_scalars() {
+    let code = "function f(x : real, c : char, s : string, b : boolean) : string {
+    var n : integer;
+    n = integer(x * 2.0);
+    if (c < 'm' && !b || s == \"\") return \"first\";
+    if (real(n) > x) return s + \"!\";
+    return \"last\";
+}
+
+function wrap(n : integer) : integer {
+    var x : integer;
+    return n * 2;
+}";
+    assert_eq!(call(code, "f(1.5, 'a', \"x\", false)"), Ok(Value::String(String::from("first"))));
+    assert_eq!(call(code, "f(1.5, 'z', \"x\", false)"), Ok(Value::String(String::from("x!"))));
+    assert_eq!(call(code, "f(-1.5, 'z', \"x\", true)"), Ok(Value::String(String::from("last"))));
+    // integers wrap around
+    assert_eq!(call(code, "wrap(2147483647)"), Ok(Value::Integer(-2)));
+}
+
+#[test]
+fn test_runtime_errors() {
+    let code = "type A = array[4] of integer
+
+function get(a : A, i : integer) : integer {
+    var x : integer;
+    return a[i];
+}
+
+function f(n : integer) : integer {
+    var a : A;
+    if (n == 0) return 1 / n;
+    return get(a, n);
+}
+
+function down(n : integer) : integer {
+    var x : integer;
+    return down(n + 1);
+}";
+    assert_eq!(call(code, "f(0)").unwrap_err(), "\
+error[E0301]: division by zero
+  --> main.ss:10:24
+   |
+10 |     if (n == 0) return 1 / n;
+   |                        ^^^^^
+
+");
+    assert_eq!(call(code, "f(3)"), Ok(Value::Integer(0)));
+    assert_eq!(call(code, "f(4)").unwrap_err(), "\
+error[E0300]: index 4 is out of bounds for an array of 4 elements
+  --> main.ss:5:14
+   |
+ 5 |     return a[i];
+   |              ^
+...
+11 |     return get(a, n);
+   |            --------- in this call to `get`
+
+");
+
+    let program = checked(code);
+    let errors = run(&program, "f", vec![Value::Integer(-1)]).unwrap_err();
+    assert_eq!(errors[0].code, ErrorCode::IndexOutOfBounds);
+    let errors = run(&program, "down", vec![Value::Integer(0)]).unwrap_err();
+    assert_eq!(errors[0].code, ErrorCode::StackOverflow);
+    assert!(errors[0].labels.is_empty());
+}
+
+#[test]
+fn test_string_indexing() {
+    let code = "function swap(s : string, i : integer) : string {
+    var c : char;
+    c = s[i];
+    s[i] = s[0];
+    s[0] = c;
+    return s;
+}
+
+function at(s : string, i : integer) : char {
+    var x : integer;
+    return s[i];
+}";
+    assert_eq!(call(code, "swap(\"abc\", 2)"), Ok(Value::String("cba".to_string())));
+    assert_eq!(call(code, "swap(\"\u{e9}t\u{e9}!\", 3)"), Ok(Value::String("!t\u{e9}\u{e9}".to_string())));
+    assert_eq!(call(code, "at(\"d\u{e9}j\u{e0}\", 3)"), Ok(Value::Char('\u{e0}')));
+    assert_eq!(call(code, "at(\"d\u{e9}j\u{e0}\", 4)").unwrap_err(), "\
+error[E0300]: index 4 is out of bounds for a string of 4 characters
+  --> main.ss:11:14
+   |
+11 |     return s[i];
+   |              ^
+
+");
+    assert_eq!(call(code, "swap(\"\", -1)").unwrap_err().lines().next(),
+               Some("error[E0300]: index -1 is out of bounds for a string of 0 characters"));
+}
+
+#[test]
+fn test_deeply_nested_calls() {
+    // the recursive call sits in a loop, an if, a block and many parentheses
+    let code = "function nested(n : integer) : integer {
+    var x : integer;
+    if (n == 0) return 0;
+    while (true) {
+        var y : integer;
+        if (n > 0) {
+            var z : integer;
+            do {
+                var w : integer;
+                if (n != 0) return 1 + PARENTHESES;
+            } while (false);
+        }
+    }
+}".replace("PARENTHESES", &format!("{}nested(n - 1){}", "(0 + ".repeat(200), ")".repeat(200)));
+    let program = checked(&code);
+    let depth = MAX_CALL_DEPTH as i32;
+    assert_eq!(run(&program, "nested", vec![Value::Integer(depth)]), Ok(Value::Integer(depth)));
+    let errors = run(&program, "nested", vec![Value::Integer(2 * depth)]).unwrap_err();
+    assert_eq!(errors[0].code, ErrorCode::StackOverflow);
+}
+
+#[test]
+fn test_parse_call() {
+    let program = checked("function f(n : integer, x : real, c : char, b : boolean) : integer { var y : integer; return n; }");
+    assert_eq!(parse_call(&program, "f(-2, -0.5, 'c', true)"),
+               Ok((String::from("f"), vec![Value::Integer(-2), Value::Real(-0.5), Value::Char('c'), Value::Boolean(true)])));
+    let error = |text: &str| parse_call(&program, text).unwrap_err();
+    assert_eq!(error("g(1)"), "the program has no function `g`");
+    assert_eq!(error("f"), "expected `(` after `f`");
+    assert_eq!(error("f()"), "`f` takes 4 arguments but 0 were given");
+    assert_eq!(error("f(1, 2.0, 'c',)"), "argument 4 of `f` is not a literal");
+    assert_eq!(error("f(1 2"), "expected `,` or `)` after argument 1 of `f`");
+    assert_eq!(error("f(1, 2.0, 'c', true) + 1"), "unexpected text after the call to `f`");
+    assert_eq!(error("f(1, 2, 'c', true)"), "argument 2 of `f` must be real but `2` was given");
+    assert_eq!(error("(1)"), "`(1)` is not a call, expected `function(arguments)`");
+}
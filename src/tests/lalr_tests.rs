@@ -0,0 +1,173 @@
+use crate::syntatic_analyzer::lalr::{build, Grammar, Symbol};
+use crate::syntatic_analyzer::constants;
+
+// `+` has no associativity and an identifier can be reduced by two rules
+const AMBIGUOUS: &str = "
+%token PLUS       \"`+`\"
+%token NUMERAL(_) \"number\"
+%token ID(_)      \"identifier\"
+%token DOLLAR     \"end of file\"
+%eof DOLLAR
+
+E_PLUS: E -> E 'PLUS' E
+E_NUM:  E -> 'NUMERAL'
+E_ID:   E -> ID
+ID_1:   ID -> 'ID'    // both rules of ID are the same
+ID_2:   ID -> 'ID'
+";
+
+#[test]
+fn test_simplescript_grammar() {
+    let grammar = Grammar::parse(include_str!("../syntatic_analyzer/simplescript.grammar")).unwrap();
+    let tables = build(&grammar);
+    assert_eq!(tables.check(&grammar), Ok(()));
+    assert_eq!(tables.action.len(), constants::ACTIONTABLE.len());
+    assert_eq!(tables.accept_state, constants::ACCEPT_STATE);
+
+    // the only conflict is the dangling else
+    assert_eq!(tables.conflicts.len(), 1);
+    let report = tables.conflicts[0].report(&grammar);
+    assert!(report.starts_with(&format!("shift/reduce conflict in state {} on `else`:\n", tables.conflicts[0].state)));
+    assert!(report.ends_with("\
+    S -> 'IF' 'LEFTPARENTHESIS' E 'RIGHTPARENTHESIS' MT S .                reduce by S_IF
+    S -> 'IF' 'LEFTPARENTHESIS' E 'RIGHTPARENTHESIS' MT S . 'ELSE' ME S    shift
+  solved by shifting
+"));
+}
+
+#[test]
+fn test_tables() {
+    let grammar = Grammar::parse(AMBIGUOUS).unwrap();
+    assert_eq!(grammar.nonterminals, vec!["E", "ID"]);
+    assert_eq!(grammar.rules[0].right, vec![Symbol::Nonterminal(0), Symbol::Terminal(0), Symbol::Nonterminal(0)]);
+    assert_eq!(grammar.rule(0), "E -> E 'PLUS' E");
+
+    let tables = build(&grammar);
+    // E -> E . 'PLUS' E in the accepting state, the start symbol was recognized
+    let accept = &tables.action[tables.accept_state];
+    assert!(accept[grammar.column(Symbol::Terminal(0))] > 0);
+    assert_eq!(accept[grammar.column(Symbol::Terminal(3))], 0);
+    // a number is reduced by E_NUM, the rule 1, before either `+` or the end of file
+    let number = usize::try_from(tables.action[0][grammar.column(Symbol::Terminal(1))]).unwrap();
+    assert_eq!(tables.action[number][2..], [-2, 0, 0, -2]);
+}
+
+#[test]
+fn test_conflict_reports() {
+    let grammar = Grammar::parse(AMBIGUOUS).unwrap();
+    let tables = build(&grammar);
+    let report = tables.check(&grammar).unwrap_err();
+    let state = |shift: bool| tables.conflicts.iter().find(|conflict| conflict.shift == shift).unwrap().state;
+    assert_eq!(report, format!("\
+1 shift/reduce conflicts (expected 0) and 2 reduce/reduce conflicts
+
+reduce/reduce conflict in state {rr} on `+`:
+    ID -> 'ID' .    reduce by ID_1
+    ID -> 'ID' .    reduce by ID_2
+  solved by reducing by ID_1, the first of the rules
+
+reduce/reduce conflict in state {rr} on end of file:
+    ID -> 'ID' .    reduce by ID_1
+    ID -> 'ID' .    reduce by ID_2
+  solved by reducing by ID_1, the first of the rules
+
+shift/reduce conflict in state {sr} on `+`:
+    E -> E 'PLUS' E .    reduce by E_PLUS
+    E -> E . 'PLUS' E    shift
+  solved by shifting
+
+", rr = state(false), sr = state(true)));
+
+    // expecting the shift/reduce conflict is not enough, reduce/reduce ones are always errors
+    let grammar = Grammar::parse(&format!("%expect 1\n{}", AMBIGUOUS)).unwrap();
+    assert!(build(&grammar).check(&grammar).is_err());
+    let grammar = Grammar::parse(&format!("%expect 1\n{}", AMBIGUOUS.replace("ID_2:   ID -> 'ID'", ""))).unwrap();
+    assert_eq!(build(&grammar).check(&grammar), Ok(()));
+}
+
+#[test]
+fn test_grammar_errors() {
+    let error = |text: &str| Grammar::parse(text).unwrap_err();
+    assert_eq!(error("%token A \"a\"\n%eof A\nS: S -> 'B'"), "line 3: terminal `B` is not declared");
+    assert_eq!(error("%token A \"a\"\n%eof A\nS: S -> T 'A'"), "line 3: `T` is not the left side of any rule");
+    assert_eq!(error("%token A \"a\"\n%eof A\nS: S -> 'A'\nS: S ->"), "line 4: rule `S` is declared twice");
+    assert_eq!(error("%token A a\n"), "line 1: the display name of `A` must be quoted");
+    assert_eq!(error("%token A \"a\"\nS -> 'A'"), "line 2: expected `NAME: LEFT -> RIGHT`");
+    assert_eq!(error("%token A \"a\"\nS: S -> 'A'"), "the grammar needs an `%eof` terminal");
+    assert_eq!(error("%left A"), "line 1: unknown declaration `%left A`");
+}
+
+#[test]
+fn test_compressed_table() {
+    for (state, row) in constants::ACTIONTABLE.iter().enumerate() {
+        for (column, entry) in row.iter().enumerate() {
+            assert_eq!(constants::action(state, column), *entry);
+        }
+        assert_eq!(constants::action(state, usize::MAX), 0);
+    }
+
+    let grammar = Grammar::parse(AMBIGUOUS).unwrap();
+    let tables = build(&grammar);
+    let compressed = tables.compress();
+    for (state, row) in tables.action.iter().enumerate() {
+        for (column, entry) in row.iter().enumerate() {
+            assert_eq!(compressed.get(state, column), *entry);
+        }
+    }
+    // the rows fill each other's gaps
+    assert!(compressed.check.len() < tables.action.len() * tables.action[0].len());
+}
+
+fn dense_size() -> usize {
+    constants::STATES * constants::COLUMNS * std::mem::size_of::<i32>()
+}
+
+fn compressed_size() -> usize {
+    std::mem::size_of_val(constants::BASE) + std::mem::size_of_val(constants::ENTRIES)
+}
+
+#[test]
+fn test_compressed_table_size() {
+    assert!(compressed_size() * 4 < dense_size(), "{} bytes compressed against {} dense", compressed_size(), dense_size());
+}
+
+/*
+ * Lookup throughput of the dense and the compressed tables, run with
+ * cargo test --release bench_table_lookup -- --ignored --nocapture
+ */
+#[test]
+#[ignore]
+fn bench_table_lookup() {
+    use std::hint::black_box;
+    use std::time::Instant;
+
+    const LOOKUPS: usize = 50_000_000;
+    // the same pseudo random walk over the table for both of them
+    let lookups = || {
+        let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+        (0..LOOKUPS).map(move |_| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            let state = (seed >> 33) as usize % constants::STATES;
+            let column = (seed >> 17) as usize % constants::COLUMNS;
+            (state, column)
+        })
+    };
+
+    let start = Instant::now();
+    let mut dense = 0i64;
+    for (state, column) in lookups() {
+        dense += i64::from(black_box(constants::ACTIONTABLE)[state][column]);
+    }
+    let dense_time = start.elapsed();
+
+    let start = Instant::now();
+    let mut compressed = 0i64;
+    for (state, column) in lookups() {
+        compressed += i64::from(constants::action(black_box(state), column));
+    }
+    let compressed_time = start.elapsed();
+
+    assert_eq!(dense, compressed);
+    println!("dense:      {:>6} bytes, {:.2} ns/lookup", dense_size(), dense_time.as_nanos() as f64 / LOOKUPS as f64);
+    println!("compressed: {:>6} bytes, {:.2} ns/lookup", compressed_size(), compressed_time.as_nanos() as f64 / LOOKUPS as f64);
+}
@@ -0,0 +1,81 @@
+use crate::{check, compile, lex, lower, parse, parse_traced, run, run_bytecode, ErrorCode, Phase, Token, Trace, TraceFormat, Value};
+
+const PROGRAM: &str = "function f(n : integer) : integer {
+    var x : integer;
+    x = n + 1;
+    return x;
+}";
+
+#[test]
+fn test_lex() {
+    let tokens = lex("var x ? 1");
+    let tokens: Vec<&Token> = tokens.iter().map(|spanned| &spanned.token).collect();
+    assert_eq!(tokens.len(), 5);
+    assert_eq!(*tokens[0], Token::VAR);
+    assert!(matches!(tokens[2], Token::ERROR(_)));
+    assert_eq!(*tokens[4], Token::DOLLAR);
+    assert_eq!(lex("").len(), 1);
+}
+
+#[test]
+fn test_stages() {
+    let ast = parse(PROGRAM).unwrap();
+    assert_eq!(ast.program().declarations.len(), 1);
+    let typed = check(&ast);
+    assert_eq!(typed.program(), ast.program());
+    let module = lower(&typed);
+    assert_eq!(module.functions.len(), 1);
+    assert_eq!(module.functions[0].name, "f");
+}
+
+#[test]
+fn test_lexical_errors_stop_parsing() {
+    let errors = parse("function f(n : integer) : integer { var x : integer; x = 1 # 2; return x; }").unwrap_err();
+    assert_eq!(errors.phase(), Phase::Lexical);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].code, ErrorCode::UnexpectedCharacter);
+}
+
+#[test]
+fn test_syntax_errors() {
+    let errors = parse("function f(n : integer) : integer { var x : integer; x = ; return x; }").unwrap_err();
+    assert_eq!(errors.phase(), Phase::Syntactic);
+    assert!(errors.render("f.ss", "").starts_with("error[E0001]"));
+}
+
+#[test]
+fn test_semantic_errors_are_reported_by_parse() {
+    let errors = parse("function f(n : integer) : integer { var x : integer; x = y; return x; }").unwrap_err();
+    assert_eq!(errors.phase(), Phase::Semantic);
+    let codes: Vec<ErrorCode> = errors.into_iter().map(|diagnostic| diagnostic.code).collect();
+    assert_eq!(codes, vec![ErrorCode::IdentifierNotDeclared]);
+}
+
+#[test]
+fn test_parse_traced() {
+    let (parsed, trace) = parse_traced(PROGRAM, Trace::new(TraceFormat::Text, false));
+    assert!(parsed.is_ok());
+    assert!(trace.output().ends_with(": accept\n"));
+    // lexical errors stop before the parser takes a step
+    let (parsed, trace) = parse_traced("#", Trace::new(TraceFormat::Text, false));
+    assert!(parsed.is_err());
+    assert_eq!(trace.output(), "");
+}
+
+#[test]
+fn test_invalid_calls() {
+    let program = check(&parse(PROGRAM).unwrap());
+    let module = compile(&program);
+    let calls = [
+        ("g", vec![], "the program has no function `g`"),
+        ("f", vec![], "`f` takes 1 argument but 0 were given"),
+        ("f", vec![Value::Boolean(true)], "argument 1 of `f` must be integer but `true` was given"),
+    ];
+    for (function, args, message) in calls {
+        for errors in [run(&program, function, args.clone()).unwrap_err(), run_bytecode(&module, function, args).unwrap_err()] {
+            assert_eq!(errors.len(), 1);
+            assert_eq!(errors[0].code, ErrorCode::InvalidCall);
+            assert_eq!(errors[0].message, message);
+        }
+    }
+}
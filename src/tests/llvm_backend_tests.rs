@@ -0,0 +1,252 @@
+use std::path::Path;
+use std::process::Command;
+
+use super::common::{same_as_the_interpreter, CALLS, PROGRAMS, STRINGS, STRING_CALLS};
+use crate::backend::llvm::RUNTIME;
+use crate::{emit_llvm, TypedAst, Value};
+
+// The generated module without the runtime it starts with
+fn generated(code: &str, call: Option<&str>) -> String {
+    super::common::generated(code, call, emit_llvm, RUNTIME)
+}
+
+const PROGRAM: &str = "type Row = array[3] of integer
+type Point = struct { x : real; row : Row }
+
+function count(p : Point) : integer {
+    var i, n : integer;
+    while (true) {
+        var v : integer;
+        if (i >= 3) break;
+        v = p.row[i++];
+        if (v == 0 || p.x < 0.5) continue;
+        n = n + v / 2;
+    }
+    do {
+        var i : integer;
+        i = n;
+        n = n - 1;
+    } while (n > 10 && !(p.x == 100000000000000000000.0));
+    return n + integer(-p.x);
+}
+
+function main(s : string) : boolean {
+    var p : Point;
+    p.x = 2.5;
+    p.row[1] = 30;
+    return count(p) <= 13 && s + \"!\" != \"a\\\"b\\n\";
+}";
+
+#[test]
+fn test_generated_module() {
+    assert_eq!(generated(PROGRAM, None), "
+%Point = type { double, [3 x i32] }
+
+@.str.0 = private unnamed_addr constant [1 x i8] c\"!\"
+@.str.1 = private unnamed_addr constant [4 x i8] c\"a\\22b\\0A\"
+
+define i32 @count(%Point %p) {
+entry:
+  %p.addr = alloca %Point
+  %i = alloca i32
+  %n = alloca i32
+  %v = alloca i32
+  %i.1 = alloca i32
+  store %Point %p, ptr %p.addr
+  store i32 0, ptr %i
+  store i32 0, ptr %n
+  br label %while.cond
+
+while.cond:
+  br i1 true, label %while.body, label %while.end
+
+while.body:
+  store i32 0, ptr %v
+  %0 = load i32, ptr %i
+  %1 = icmp sge i32 %0, 3
+  br i1 %1, label %if.then1, label %if.end1
+
+if.then1:
+  br label %while.end
+
+if.end1:
+  %2 = load i32, ptr %i
+  %3 = add i32 %2, 1
+  store i32 %3, ptr %i
+  %4 = call i32 @ss.index(i32 %2, i32 3, i32 9, i32 19)
+  %5 = getelementptr inbounds %Point, ptr %p.addr, i32 0, i32 1, i32 %4
+  %6 = load i32, ptr %5
+  store i32 %6, ptr %v
+  %7 = load i32, ptr %v
+  %8 = icmp eq i32 %7, 0
+  br i1 %8, label %or.end3, label %or.rhs3
+
+or.rhs3:
+  %9 = getelementptr inbounds %Point, ptr %p.addr, i32 0, i32 0
+  %10 = load double, ptr %9
+  %11 = fcmp olt double %10, 0.5
+  br label %or.end3
+
+or.end3:
+  %12 = phi i1 [ true, %if.end1 ], [ %11, %or.rhs3 ]
+  br i1 %12, label %if.then2, label %if.end2
+
+if.then2:
+  br label %while.cond
+
+if.end2:
+  %13 = load i32, ptr %n
+  %14 = load i32, ptr %v
+  %15 = call i32 @ss.div(i32 %14, i32 2, i32 11, i32 17)
+  %16 = add i32 %13, %15
+  store i32 %16, ptr %n
+  br label %while.cond
+
+while.end:
+  br label %do.body4
+
+do.body4:
+  store i32 0, ptr %i.1
+  %17 = load i32, ptr %n
+  store i32 %17, ptr %i.1
+  %18 = load i32, ptr %n
+  %19 = sub i32 %18, 1
+  store i32 %19, ptr %n
+  br label %do.cond4
+
+do.cond4:
+  %20 = load i32, ptr %n
+  %21 = icmp sgt i32 %20, 10
+  br i1 %21, label %and.rhs5, label %and.end5
+
+and.rhs5:
+  %22 = getelementptr inbounds %Point, ptr %p.addr, i32 0, i32 0
+  %23 = load double, ptr %22
+  %24 = fcmp oeq double %23, 1.0e20
+  %25 = xor i1 %24, true
+  br label %and.end5
+
+and.end5:
+  %26 = phi i1 [ false, %do.cond4 ], [ %25, %and.rhs5 ]
+  br i1 %26, label %do.body4, label %do.end4
+
+do.end4:
+  %27 = load i32, ptr %n
+  %28 = getelementptr inbounds %Point, ptr %p.addr, i32 0, i32 0
+  %29 = load double, ptr %28
+  %30 = fneg double %29
+  %31 = call i32 @llvm.fptosi.sat.i32.f64(double %30)
+  %32 = add i32 %27, %31
+  ret i32 %32
+}
+
+define i1 @main.(%ss.string %s) {
+entry:
+  %s.addr = alloca %ss.string
+  %p = alloca %Point
+  store %ss.string %s, ptr %s.addr
+  store %Point zeroinitializer, ptr %p
+  %0 = getelementptr inbounds %Point, ptr %p, i32 0, i32 0
+  store double 2.5, ptr %0
+  %1 = call i32 @ss.index(i32 1, i32 3, i32 24, i32 11)
+  %2 = getelementptr inbounds %Point, ptr %p, i32 0, i32 1, i32 %1
+  store i32 30, ptr %2
+  %3 = load %Point, ptr %p
+  %4 = call i32 @count(%Point %3)
+  %5 = icmp sle i32 %4, 13
+  br i1 %5, label %and.rhs, label %and.end
+
+and.rhs:
+  %6 = load %ss.string, ptr %s.addr
+  %7 = call %ss.string @ss.concat(%ss.string %6, %ss.string { i32 1, ptr @.str.0 })
+  %8 = call i32 @ss.compare.string(%ss.string %7, %ss.string { i32 4, ptr @.str.1 })
+  %9 = icmp ne i32 %8, 0
+  br label %and.end
+
+and.end:
+  %10 = phi i1 [ false, %entry ], [ %9, %and.rhs ]
+  ret i1 %10
+}
+");
+}
+
+#[test]
+fn test_string_indexing() {
+    // chars are whole Unicode characters, the runtime finds them in the UTF-8 of the strings
+    let code = "function f(s : string, i : integer) : char {
+    var c : char;
+    c = s[i];
+    s[i] = '\u{e9}';
+    if (c < 'a') return s[i + 1];
+    return c;
+}";
+    assert_eq!(generated(code, None), "
+define i32 @f(%ss.string %s, i32 %i) {
+entry:
+  %s.addr = alloca %ss.string
+  %i.addr = alloca i32
+  %c = alloca i32
+  store %ss.string %s, ptr %s.addr
+  store i32 %i, ptr %i.addr
+  store i32 0, ptr %c
+  %0 = load i32, ptr %i.addr
+  %1 = load %ss.string, ptr %s.addr
+  %2 = call i32 @ss.char_at(%ss.string %1, i32 %0, i32 3, i32 11)
+  store i32 %2, ptr %c
+  %3 = load i32, ptr %i.addr
+  %4 = load %ss.string, ptr %s.addr
+  %5 = call %ss.string @ss.with_char(%ss.string %4, i32 %3, i32 233, i32 4, i32 7)
+  store %ss.string %5, ptr %s.addr
+  %6 = load i32, ptr %c
+  %7 = icmp ult i32 %6, 97
+  br i1 %7, label %if.then, label %if.end
+
+if.then:
+  %8 = load i32, ptr %i.addr
+  %9 = add i32 %8, 1
+  %10 = load %ss.string, ptr %s.addr
+  %11 = call i32 @ss.char_at(%ss.string %10, i32 %9, i32 5, i32 27)
+  ret i32 %11
+
+if.end:
+  %12 = load i32, ptr %c
+  ret i32 %12
+}
+");
+}
+
+#[test]
+fn test_entry() {
+    let module = generated(PROGRAM, Some("main(\"x\")"));
+    assert!(module.ends_with("
+define i32 @main() {
+entry:
+  %0 = call i1 @main.(%ss.string { i32 1, ptr @.str.2 })
+  call void @ss.print.boolean(i1 %0)
+  %1 = call i32 @putchar(i32 10)
+  ret i32 0
+}
+"));
+    assert!(module.contains("@.str.2 = private unnamed_addr constant [1 x i8] c\"x\"\n"));
+}
+
+/*
+ * Runs the modules with `lli`, checking that they print what the interpreter returns.
+ * Needs lli, run with
+ * cargo test llvm_backend -- --ignored
+ */
+#[test]
+#[ignore]
+fn test_same_results_as_the_interpreter() {
+    let run_llvm = |dir: &Path, program: &TypedAst, function: &str, args: &[Value]| {
+        std::fs::write(dir.join("main.ll"), emit_llvm(program, Some((function, args)))).unwrap();
+        // LLVM 14 needs to be told about opaque pointers, later versions have nothing else
+        let output = Command::new("lli").args(["-opaque-pointers", "main.ll"]).current_dir(dir).output().unwrap();
+        if !String::from_utf8_lossy(&output.stderr).contains("opaque-pointers") {
+            return output;
+        }
+        Command::new("lli").arg("main.ll").current_dir(dir).output().unwrap()
+    };
+    same_as_the_interpreter("llvm", PROGRAMS, &CALLS, |value| value.to_string(), run_llvm);
+    same_as_the_interpreter("llvm", STRINGS, &STRING_CALLS, |value| value.to_string(), run_llvm);
+}
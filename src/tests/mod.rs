@@ -1,2 +1,14 @@
+pub mod common;
 pub mod token_stream_tests;
 pub mod syntatic_analyzer_tests;
+pub mod report_tests;
+pub mod ast_tests;
+pub mod intermediate_code_tests;
+pub mod flow_analyzer_tests;
+pub mod lalr_tests;
+pub mod lib_tests;
+pub mod interpreter_tests;
+pub mod bytecode_tests;
+pub mod c_backend_tests;
+pub mod llvm_backend_tests;
+pub mod wat_backend_tests;
@@ -0,0 +1,111 @@
+use crate::syntatic_analyzer::StateMachine;
+use crate::syntatic_analyzer::report::render;
+
+fn render_first(code: &str) -> String {
+    let mut parser = StateMachine::new(String::from(code));
+    let errors = parser.parse().unwrap_err();
+    render(&errors[0], "main.ss", code)
+}
+
+#[test]
+fn test_render_redeclaration() {
+    let code = "function main(n : integer) : integer {
+    var s : string;
+    var n : integer;
+    s = \"a\";
+}";
+
+    assert_eq!(render_first(code), "\
+error[E0100]: identifier `n` is already declared in this scope
+ --> main.ss:3:9
+  |
+1 | function main(n : integer) : integer {
+  |               - previously declared here
+2 |     var s : string;
+3 |     var n : integer;
+  |         ^
+");
+}
+
+#[test]
+fn test_render_param_mismatch() {
+    let code = "function f(a : integer, b : char) : integer {
+    var x : integer;
+    x = 1; return x;
+}
+
+
+
+function main(n : integer) : integer {
+    var x : integer;
+    x = f(n, true);
+    return x;
+}";
+
+    assert_eq!(render_first(code), "\
+error[E0210]: argument 2 of `f` expects char but found boolean
+  --> main.ss:10:14
+   |
+ 1 | function f(a : integer, b : char) : integer {
+   |                         - parameter declared as char here
+...
+10 |     x = f(n, true);
+   |              ^^^^
+");
+}
+
+#[test]
+fn test_render_multiline_span() {
+    let code = "function main(n : integer) : integer {
+    var s : string;
+    s = n +
+        n;
+    return n;
+}";
+
+    assert_eq!(render_first(code), "\
+error[E0201]: assignment expects string but found integer
+ --> main.ss:3:9
+  |
+3 |     s = n +
+  |         ^^^
+  |     - left value has type string
+4 |         n;
+  |         ^
+");
+}
+
+#[test]
+fn test_render_syntax_error_at_end_of_file() {
+    let code = "function main(n : integer) : integer {\n";
+
+    assert_eq!(render_first(code), "\
+error[E0001]: expected `var` but found end of file
+ --> main.ss:2:1
+  |
+2 |
+  | ^
+");
+}
+
+#[test]
+fn test_render_missing_return() {
+    let code = "function sign(n : integer) : integer {
+    var s : integer;
+    if (n > 0)
+        return 1;
+    else
+        s = 0;
+}";
+
+    assert_eq!(render_first(code), "\
+error[E0215]: function `sign` can reach the end of its body without returning integer
+ --> main.ss:6:9
+  |
+1 | function sign(n : integer) : integer {
+  |          ---- `sign` declared to return integer here
+...
+6 |         s = 0;
+  |         ^^^^^^
+");
+}
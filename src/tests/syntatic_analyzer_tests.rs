@@ -1,4 +1,14 @@
 use crate::syntatic_analyzer::*;
+use crate::syntatic_analyzer::diagnostics::{ErrorCode, Subject};
+use crate::syntatic_analyzer::nonterminals::Type;
+use crate::syntatic_analyzer::scope_analyzer::ScopeAnalyzer;
+use crate::syntatic_analyzer::span::Span;
+use crate::syntatic_analyzer::trace::{Trace, TraceFormat};
+
+fn error_codes(code: &str) -> Vec<ErrorCode> {
+    let mut parser = StateMachine::new(String::from(code));
+    parser.parse().unwrap_err().iter().map(|d| d.code).collect()
+}
 
 #[test]
 fn test_syntax_and_semantics(){
@@ -20,6 +30,7 @@ fn test_syntax_and_semantics(){
           var pessoa : Pessoa;
           if (n == pessoa.id)
             p = funcaoRecursiva(n-1);
+          return p;
         }
 
         function addPessoa(posicao : integer, pessoa : Pessoa,arr : pessoaArray) : integer {
@@ -39,13 +50,14 @@ fn test_syntax_and_semantics(){
             var tmp : integer;
             tmp = funcaoRecursiva(tmp-1);
           }
+          return a;
         }
 
         
     ");
     
     let mut parser = StateMachine::new(code);
-    assert_eq!(parser.parse(), true);
+    assert!(parser.parse().is_ok());
 }
 
 #[test]
@@ -55,51 +67,54 @@ fn test_syntax_and_semantics_2() {
           var ret : integer;
           if (n >= 2)
             ret = fibonacci(n-1) + fibonacci(n-2);
+          return ret;
         } 
     ");
 
     let mut parser = StateMachine::new(code);
-    assert_eq!(parser.parse(), true);
+    assert!(parser.parse().is_ok());
 }
 
 #[test]
-#[should_panic]
 fn test_syntax_and_semantics_3() {
     let code = String::from("
         function fibonacci(n : integer) : integer {
           var ret : integer;
           if (n >= 2)
             ret = fibonacci(n-1) + fibonacci(n-2);
+          return ret;
         } 
 
         function main(x : integer) : boolean {
             var res : integer;
             res = fibonacci();
+            return true;
         }
     ");
 
     let mut parser = StateMachine::new(code);
-    assert_eq!(parser.parse(), true);
+    assert_eq!(parser.parse().unwrap_err()[0].code, ErrorCode::SyntaxError);
 }
 
 #[test]
-#[should_panic]
 fn test_syntax_and_semantics_4() {
     let code = String::from("
         function fibonacci(n : integer) : integer {
           var ret : integer;
           if (n >= 2)
             ret = fibonacci(n-1) + fibonacci(n-2);
+          return ret;
         } 
 
         function main(x : integer) : boolean {
             var res : boolean;
             res = fibonacci(5);
+            return res;
         }
     ");
 
     let mut parser = StateMachine::new(code);
-    assert_eq!(parser.parse(), true);
+    assert_eq!(parser.parse().unwrap_err()[0].code, ErrorCode::AssignmentTypeMismatch);
 }
 
 #[test]
@@ -130,11 +145,12 @@ fn test_syntax_and_semantics_5() {
 
             if (rafael == matheus) 
                 resultado = 0;
+            return resultado;
         }
     ");
 
     let mut parser = StateMachine::new(code);
-    assert_eq!(parser.parse(), true);
+    assert!(parser.parse().is_ok());
 }
 
 #[test]
@@ -151,15 +167,15 @@ fn test_syntax_and_semantics_6() {
                     break;
                 }
             }
+            return cnt == 10;
         }
     ");
 
     let mut parser = StateMachine::new(code);
-    assert_eq!(parser.parse(), true);
+    assert!(parser.parse().is_ok());
 }
 
 #[test]
-#[should_panic]
 fn test_syntax_and_semantics_7() {
     let code = String::from("
         type casa = struct {
@@ -177,11 +193,560 @@ fn test_syntax_and_semantics_7() {
             var ok : boolean;
 
             if (minha_casa == meu_carro) ok = false; 
+            return 0;
         }
     ");
 
     let mut parser = StateMachine::new(code);
-    assert_eq!(parser.parse(), true);
+    assert_eq!(parser.parse().unwrap_err()[0].code, ErrorCode::OperandTypeMismatch);
+}
+
+#[test]
+fn test_identifier_redeclaration() {
+    let code = "
+        function main(n : integer) : integer {
+            var n : integer;
+            var x, x : boolean;
+            x = true;
+            return n;
+        }
+    ";
+
+    assert_eq!(error_codes(code), vec![ErrorCode::IdentifierRedeclaration, ErrorCode::IdentifierRedeclaration]);
+}
+
+#[test]
+fn test_undeclared_identifier_does_not_cascade() {
+    let code = "
+        function main(n : integer) : integer {
+            var x : integer;
+            x = y + 1;
+            x = y * 2;
+            return x;
+        }
+    ";
+
+    let mut parser = StateMachine::new(String::from(code));
+    let errors = parser.parse().unwrap_err();
+    assert_eq!(errors.len(), 2);
+    assert!(errors.iter().all(|d| d.code == ErrorCode::IdentifierNotDeclared));
+    assert_eq!(errors[0].subject, Some(Subject::Identifier(String::from("y"))));
+}
+
+#[test]
+fn test_errors_are_collected() {
+    let code = "
+        function f(a : integer, b : char) : integer {
+            var x : integer;
+            x = 1;
+            return x;
+        }
+
+        function main(n : integer) : integer {
+            var c : boolean;
+            if (n) c = true;
+            c = f(true, 'a');
+            c = f(1, 'a', 2);
+            c = f(1, 'a');
+            return n;
+        }
+    ";
+
+    assert_eq!(error_codes(code), vec![ErrorCode::ConditionNotBoolean,
+                                       ErrorCode::ArgumentTypeMismatch,
+                                       ErrorCode::AssignmentTypeMismatch,
+                                       ErrorCode::TooManyArguments,
+                                       ErrorCode::AssignmentTypeMismatch,
+                                       ErrorCode::AssignmentTypeMismatch]);
+}
+
+#[test]
+fn test_mismatch_subject() {
+    let code = "
+        function main(n : integer) : integer {
+            var s : string;
+            s = n;
+            return n;
+        }
+    ";
+
+    let mut parser = StateMachine::new(String::from(code));
+    let errors = parser.parse().unwrap_err();
+    assert_eq!(errors[0].code.as_str(), "E0201");
+    assert_eq!(errors[0].subject, Some(Subject::Mismatch { expected: Type::String_, found: Type::Int_ }));
+}
+
+#[test]
+fn test_diagnostic_spans() {
+    let code = String::from("function main(n : integer) : integer {
+    var s : string;
+    var n : integer;
+    s = n + 1;
+    return n;
+}");
+
+    let mut parser = StateMachine::new(code.clone());
+    let errors = parser.parse().unwrap_err();
+    assert_eq!(errors[0].code, ErrorCode::IdentifierRedeclaration);
+    assert_eq!((errors[0].span.start.line, errors[0].span.start.column), (3, 9));
+    assert_eq!(&code[errors[0].span.range()], "n");
+
+    assert_eq!(errors[1].code, ErrorCode::AssignmentTypeMismatch);
+    assert_eq!(&code[errors[1].span.range()], "n + 1");
+}
+
+#[test]
+fn test_syntax_error_span() {
+    let code = String::from("function main(n : integer) : integer {
+    var x : integer
+    x = 1;
+    return x;
+}");
+
+    let mut parser = StateMachine::new(code.clone());
+    let errors = parser.parse().unwrap_err();
+    assert_eq!(errors[0].code, ErrorCode::SyntaxError);
+    assert_eq!((errors[0].span.start.line, errors[0].span.start.column), (3, 5));
+}
+
+#[test]
+fn test_recovers_from_many_syntax_errors() {
+    let code = String::from("function main(n : integer) : integer {
+    var x : integer;
+    x = 1 +;
+    x = 2;
+    x = (3;
+}
+
+function other(n : integer) : integer {
+    var y : integer
+    y = 1; return y;
+}
+
+type t = array[ of integer
+
+function last(n : integer) : integer {
+    var z : integer;
+    z = n; return z;
+}");
+
+    let mut parser = StateMachine::new(code);
+    let errors = parser.parse().unwrap_err();
+    let lines: Vec<u32> = errors.iter().map(|d| d.span.start.line).collect();
+    assert!(errors.iter().all(|d| d.code == ErrorCode::SyntaxError));
+    assert_eq!(lines, vec![3, 5, 10, 13]);
 }
 
+#[test]
+fn test_recovers_at_the_statement_after_an_unclosed_parenthesis() {
+    let code = String::from("function main(n : integer) : integer {
+    var x : integer;
+    x = (3;
+    x = ;
+    return x;
+}");
+
+    let mut parser = StateMachine::new(code);
+    let errors = parser.parse().unwrap_err();
+    let positions: Vec<(u32, u32)> = errors.iter().map(|d| (d.span.start.line, d.span.start.column)).collect();
+    assert!(errors.iter().all(|d| d.code == ErrorCode::SyntaxError));
+    assert_eq!(positions, vec![(3, 11), (4, 9)]);
+}
 
+#[test]
+fn test_field_with_a_broken_type() {
+    let code = "
+        type P = struct { x : integer; z : ; }
+
+        function f(n : integer) : integer {
+            var p : P;
+            p.z = 1;
+            p.y = 1;
+            return p.x;
+        }
+    ";
+
+    // `z` is a field of `P` even though its type is lost, only `y` is missing
+    let mut parser = StateMachine::new(String::from(code));
+    let errors = parser.parse().unwrap_err();
+    let codes: Vec<ErrorCode> = errors.iter().map(|error| error.code).collect();
+    assert_eq!(codes, vec![ErrorCode::SyntaxError, ErrorCode::StructFieldNotDeclared]);
+    assert_eq!(&code[errors[1].span.range()], "y");
+}
+
+#[test]
+fn test_semantic_analysis_continues_after_syntax_error() {
+    let code = "
+        function main(n : integer) : integer {
+            var x : integer;
+            x = = 1;
+            x = y;
+            return x;
+        }
+
+        function other(n : integer) : integer {
+            var z : boolean;
+            z = n;
+            return n;
+        }
+    ";
+
+    assert_eq!(error_codes(code), vec![ErrorCode::SyntaxError,
+                                       ErrorCode::IdentifierNotDeclared,
+                                       ErrorCode::AssignmentTypeMismatch]);
+}
+
+#[test]
+fn test_recovery_closes_scopes() {
+    let code = "
+        function main(n : integer) : integer {
+            var x : integer;
+            if (n == 1) {
+                var inner : integer;
+                inner = ;
+            }
+            inner = 2;
+            return x;
+        }
+    ";
+
+    assert_eq!(error_codes(code), vec![ErrorCode::SyntaxError, ErrorCode::IdentifierNotDeclared]);
+}
+
+#[test]
+fn test_unexpected_end_of_file() {
+    let code = "
+        function main(n : integer) : integer {
+            var x : integer;
+            x = 1;
+    ";
+
+    assert_eq!(error_codes(code), vec![ErrorCode::SyntaxError]);
+}
+
+#[test]
+fn test_unknown_characters_are_skipped() {
+    let code = "
+        function main(n : integer) : integer {
+            var x : integer;
+            x = 1 # 2;
+            x = @;
+            x = true;
+            return x;
+        }
+    ";
+
+    // the characters are reported and dropped, what is left around them is still parsed
+    assert_eq!(error_codes(code), vec![ErrorCode::UnexpectedCharacter, ErrorCode::SyntaxError,
+                                       ErrorCode::UnexpectedCharacter, ErrorCode::SyntaxError,
+                                       ErrorCode::AssignmentTypeMismatch]);
+}
+
+#[test]
+fn test_expected_tokens_message() {
+    let code = "
+        function main(n : integer) : integer {
+            var x : integer
+            var y : integer;
+            x = 1;
+        }
+    ";
+
+    let mut parser = StateMachine::new(String::from(code));
+    let errors = parser.parse().unwrap_err();
+    assert_eq!(errors[0].message, "expected `;` but found `var`");
+    assert_eq!(errors[0].subject, Some(Subject::UnexpectedToken {
+        expected: vec![String::from("`;`")],
+        found: String::from("`var`"),
+    }));
+}
+
+#[test]
+fn test_expected_one_of_message() {
+    let code = "
+        function main(n : integer) : integer {
+            var x : integer;
+            x = 1 x;
+        }
+    ";
+
+    let mut parser = StateMachine::new(String::from(code));
+    let errors = parser.parse().unwrap_err();
+    assert_eq!(errors[0].message, "expected one of `;`, `&&`, `||`, `<`, `>`, `<=`, `>=`, `!=`, `==`, `+`, `-`, `*` or `/` but found `x`");
+}
+
+#[test]
+fn test_return() {
+    let code = "
+        function f(n : integer) : boolean {
+            var x : integer;
+            while (n > 0) {
+                var done : boolean;
+                if (n == 1) return true;
+                n = n - 1;
+            }
+            return n == 0;
+        }
+
+        function g(s : string) : string {
+            var b : boolean;
+            b = f(2);
+            return s;
+        }
+    ";
+
+    let mut parser = StateMachine::new(String::from(code));
+    assert!(parser.parse().is_ok());
+}
+
+#[test]
+fn test_return_type_mismatch() {
+    let code = "
+        function f(n : integer) : boolean {
+            var x : integer;
+            return true;
+        }
+
+        function g(s : string) : integer {
+            var b : boolean;
+            return s;
+        }
+    ";
+
+    let mut parser = StateMachine::new(String::from(code));
+    let errors = parser.parse().unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].code, ErrorCode::ReturnTypeMismatch);
+    assert_eq!(errors[0].message, "`return` in `g` expects integer but found string");
+    assert_eq!(&code[errors[0].span.range()], "s");
+    assert_eq!(errors[0].labels[0].message, "return type integer declared here");
+    assert_eq!(&code[errors[0].labels[0].span.range()], "integer");
+}
+
+#[test]
+fn test_no_enclosing_function_after_its_body() {
+    let mut scope_analyzer = ScopeAnalyzer::new();
+    scope_analyzer.define(String::from("f"), Span::default());
+    scope_analyzer.new_block();
+    scope_analyzer.func_type_decl("f", Type::Int_, Span::default(), vec![]);
+    assert_eq!(scope_analyzer.enclosing_function().map(|function| function.name.as_str()), Some("f"));
+
+    scope_analyzer.end_block();
+    assert!(scope_analyzer.enclosing_function().is_none());
+}
+
+#[test]
+fn test_break_and_continue_outside_loop() {
+    let code = "
+        function f(n : integer) : integer {
+            var x : integer;
+            while (n > 0) {
+                var y : integer;
+                if (n == 1) break;
+                do { var z : integer; n = n - 1; continue; } while (n > 5);
+            }
+            if (n == 0) continue;
+            break;
+            return n;
+        }
+    ";
+
+    let mut parser = StateMachine::new(String::from(code));
+    let errors = parser.parse().unwrap_err();
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].code, ErrorCode::ContinueOutsideLoop);
+    assert_eq!(errors[0].message, "`continue` outside of a loop");
+    assert_eq!(&code[errors[0].span.range()], "continue;");
+    assert_eq!(errors[1].code, ErrorCode::BreakOutsideLoop);
+    assert_eq!(errors[1].message, "`break` outside of a loop");
+    assert_eq!(&code[errors[1].span.range()], "break;");
+}
+
+#[test]
+fn test_loop_nesting_after_recovery() {
+    // the loop whose body failed to parse is left, so the break after it has no target
+    let code = "
+        function f(n : integer) : integer {
+            var x : integer;
+            while (n > 0) { var y : integer; n = ; }
+            break;
+            return n;
+        }
+    ";
+    assert_eq!(error_codes(code), vec![ErrorCode::SyntaxError, ErrorCode::BreakOutsideLoop]);
+}
+
+#[test]
+fn test_real() {
+    let code = "
+        type Point = struct { x, y : real }
+        type Samples = array[4] of real
+
+        function norm(p : Point) : real {
+            var n : real;
+            n = p.x * p.x + p.y * p.y;
+            if (n >= 1.0 && -n != p.x / 2.0) n = n - 0.5;
+            return n;
+        }
+
+        function round(x : real) : integer {
+            var s : Samples;
+            var i : integer;
+            i = integer(x + 0.5);
+            s[i] = real(i) * 2.25;
+            return integer(s[i]) + i;
+        }
+    ";
+
+    let mut parser = StateMachine::new(String::from(code));
+    assert!(parser.parse().is_ok());
+}
+
+#[test]
+fn test_real_type_errors() {
+    let code = "
+        type Samples = array[2.5] of real
+
+        function f(x : real) : real {
+            var i : integer;
+            x = x + 1;
+            i = x;
+            x = real(true);
+            i = x++;
+            return x;
+        }
+    ";
+
+    let mut parser = StateMachine::new(String::from(code));
+    let errors = parser.parse().unwrap_err();
+    let codes: Vec<ErrorCode> = errors.iter().map(|error| error.code).collect();
+    // no implicit conversion between integer and real, in either direction
+    assert_eq!(codes, vec![ErrorCode::ArraySizeNotInteger, ErrorCode::OperandTypeMismatch,
+                           ErrorCode::AssignmentTypeMismatch, ErrorCode::InvalidOperandType,
+                           ErrorCode::InvalidOperandType]);
+    assert_eq!(errors[0].message, "array `Samples` must have an integer size, found 2.5");
+    assert_eq!(&code[errors[0].span.range()], "2.5");
+    assert_eq!(errors[1].message, "`+` right operand expects real but found integer");
+    assert_eq!(errors[3].message, "`real` conversion expects integer or real but found boolean");
+    assert_eq!(errors[4].message, "postfix `++` expects integer but found real");
+}
+
+#[test]
+fn test_literal_out_of_range() {
+    let code = "
+        type Big = array[99999999999] of integer
+
+        function f(x : integer) : integer {
+            var y : real;
+            x = 2147483647 + 0x7FFF_FFFF;
+            x = 0b1_0000_0000_0000_0000_0000_0000_0000_0000;
+            y = 1.0 + 1NUMERAL.0;
+            return x;
+        }
+    ".replace("NUMERAL", &"0".repeat(400));
+
+    let mut parser = StateMachine::new(code.clone());
+    let errors = parser.parse().unwrap_err();
+    let codes: Vec<ErrorCode> = errors.iter().map(|error| error.code).collect();
+    // the literal is reported once, it does not make the array size or the assignments wrong too
+    assert_eq!(codes, vec![ErrorCode::LiteralOutOfRange, ErrorCode::LiteralOutOfRange, ErrorCode::LiteralOutOfRange]);
+    assert_eq!(errors[0].message, "integer literal `99999999999` is out of range");
+    assert_eq!(&code[errors[0].span.range()], "99999999999");
+    assert_eq!(&code[errors[1].span.range()], "0b1_0000_0000_0000_0000_0000_0000_0000_0000");
+    assert!(errors[2].message.starts_with("real literal `1000"));
+}
+
+#[test]
+fn test_empty_array() {
+    let code = "
+        type Empty = array[0] of integer
+        type Hex = array[0x0] of boolean
+        type One = array[1] of integer
+    ";
+
+    let mut parser = StateMachine::new(String::from(code));
+    let errors = parser.parse().unwrap_err();
+    let codes: Vec<ErrorCode> = errors.iter().map(|error| error.code).collect();
+    assert_eq!(codes, vec![ErrorCode::ArraySizeNotPositive, ErrorCode::ArraySizeNotPositive]);
+    assert_eq!(errors[0].message, "array `Empty` must have a positive size, found 0");
+    assert_eq!(&code[errors[0].span.range()], "0");
+    assert_eq!(&code[errors[1].span.range()], "0x0");
+}
+
+// Trace of parsing `code`, with the state numbers left out as they change with the grammar
+fn trace(code: &str, format: TraceFormat, semantic_stack: bool) -> Vec<String> {
+    let mut parser = StateMachine::new(String::from(code));
+    parser.set_trace(Trace::new(format, semantic_stack));
+    let _ = parser.parse();
+    parser.take_trace().unwrap().output().lines()
+        .map(|line| {
+            let mut pieces = line.split("state");
+            let mut line = pieces.next().unwrap().to_string();
+            for piece in pieces {
+                line.push_str("state");
+                line.push_str(piece.trim_start_matches(|ch: char| ch == '"' || ch == ':' || ch == ' ' || ch.is_ascii_digit()));
+            }
+            line
+        })
+        .collect()
+}
+
+#[test]
+fn test_trace() {
+    assert_eq!(trace("type Id = integer", TraceFormat::Text, false), vec![
+        "state, TYPE at 1:1: shift to state",
+        "state, ID(\"Id\") at 1:6: shift to state",
+        "state, EQUAL at 1:9: reduce by IDD (1 symbol), goto state",
+        "state, EQUAL at 1:9: shift to state",
+        "state, INTEGER at 1:11: shift to state",
+        "state, DOLLAR at 1:18: reduce by T_INT (1 symbol), goto state",
+        "state, DOLLAR at 1:18: reduce by DT_ALIAS (4 symbols), goto state",
+        "state, DOLLAR at 1:18: reduce by DE_DT (1 symbol), goto state",
+        "state, DOLLAR at 1:18: reduce by LDE_DE (1 symbol), goto state",
+        "state, DOLLAR at 1:18: reduce by P (1 symbol), goto state",
+        "state, DOLLAR at 1:18: accept",
+    ]);
+
+    let lines = trace("type Id = integer", TraceFormat::Text, true);
+    // every step is followed by the stack it left
+    assert_eq!(lines[4], "state, EQUAL at 1:9: reduce by IDD (1 symbol), goto state");
+    assert_eq!(lines[5], "    semantic stack: [IDD(Object(\"Id\", no_kind_def))]");
+}
+
+#[test]
+fn test_trace_json_lines() {
+    let code = "function f(n : integer) : integer { var x : integer; x = ; return n; }";
+    let lines = trace(code, TraceFormat::JsonLines, true);
+    assert!(lines.iter().all(|line| line.starts_with("{\"state") && line.ends_with("]}")));
+    assert!(lines[1].starts_with("{\"state,\"lookahead\":\"ID(\\\"f\\\")\",\"line\":1,\"column\":10,\"action\":\"shift\",\"to\":"));
+    assert!(lines[1].ends_with(",\"semantic_stack\":[]}"));
+
+    // the error, the `;` recovery resynchronizes on and the end of the input
+    let steps: Vec<&String> = lines.iter().filter(|line| !line.contains("\"shift\"") && !line.contains("\"reduce\"")).collect();
+    assert_eq!(steps.len(), 3);
+    assert!(steps[0].contains("\"lookahead\":\"SEMICOLON\",\"line\":1,\"column\":58,\"action\":\"error\",\"semantic_stack\":["));
+    assert!(steps[1].contains("\"action\":\"error_statement\",\"goto\":"));
+    assert!(steps[2].contains("\"lookahead\":\"DOLLAR\",\"line\":1,\"column\":71,\"action\":\"accept\""));
+}
+
+#[test]
+fn test_types_too_large() {
+    let code = "type A = array[4] of integer
+type Big = array[1073741824] of integer
+type Fits = array[536870911] of integer
+type Pair = struct { a, b : Fits }
+type Deep = array[1000000000] of A
+function f(x : integer) : integer {
+    var b : Big;
+    return x;
+}";
+
+    let mut parser = StateMachine::new(String::from(code));
+    let errors = parser.parse().unwrap_err();
+    let codes: Vec<ErrorCode> = errors.iter().map(|error| error.code).collect();
+    assert_eq!(codes, vec![ErrorCode::TypeTooLarge, ErrorCode::TypeTooLarge, ErrorCode::TypeTooLarge]);
+    assert_eq!(errors[0].message, "the values of type `Big` take more than 2147483647 bytes");
+    assert_eq!(&code[errors[0].span.range()], "Big");
+    assert_eq!(&code[errors[1].span.range()], "Pair");
+    assert_eq!(&code[errors[2].span.range()], "Deep");
+}
@@ -1,4 +1,6 @@
-use crate::syntatic_analyzer::terminals::Token;
+use std::borrow::Cow;
+
+use crate::syntatic_analyzer::terminals::{Token, LexError, integer_value, real_value};
 use crate::syntatic_analyzer::token_stream::TokenStream;
 
 #[test]
@@ -297,6 +299,44 @@ fn test_of(){
     assert_eq!(t_stream.next(), Some(Token::DOLLAR));
 }
 
+#[test]
+fn test_return(){
+    let program = String::from("return returned");
+    let mut t_stream = TokenStream::new(program.chars());
+    assert_eq!(t_stream.next(), Some(Token::RETURN));
+    assert_eq!(t_stream.next(), Some(Token::ID("returned")));
+    assert_eq!(t_stream.next(), Some(Token::DOLLAR));
+}
+
+#[test]
+fn test_real(){
+    let program = String::from("real 2.5 realm");
+    let mut t_stream = TokenStream::new(program.chars());
+    assert_eq!(t_stream.next(), Some(Token::REAL));
+    assert_eq!(t_stream.next(), Some(Token::NUMERAL("2.5")));
+    assert_eq!(t_stream.next(), Some(Token::ID("realm")));
+    assert_eq!(t_stream.next(), Some(Token::DOLLAR));
+}
+
+#[test]
+fn test_radix_and_separators(){
+    let program = String::from("0xFF 0b1010 0o17 1_000_000 0x7fff_ffff 2.718_5 0 007");
+    let t_stream = TokenStream::new(program.chars());
+    let numerals: Vec<Token> = t_stream.take(8).collect();
+    assert_eq!(numerals, vec![Token::NUMERAL("0xFF"), Token::NUMERAL("0b1010"), Token::NUMERAL("0o17"),
+                              Token::NUMERAL("1_000_000"), Token::NUMERAL("0x7fff_ffff"), Token::NUMERAL("2.718_5"),
+                              Token::NUMERAL("0"), Token::NUMERAL("007")]);
+
+    assert_eq!(integer_value("0xFF"), Some(255));
+    assert_eq!(integer_value("0b1010"), Some(10));
+    assert_eq!(integer_value("0o17"), Some(15));
+    assert_eq!(integer_value("1_000_000"), Some(1000000));
+    assert_eq!(integer_value("0x7fff_ffff"), Some(i32::MAX));
+    assert_eq!(integer_value("0x8000_0000"), None);
+    assert_eq!(integer_value("99999999999"), None);
+    assert_eq!(real_value("2.718_5"), 2.7185);
+}
+
 #[test]
 fn test_string(){
     let program = String::from("string");
@@ -373,7 +413,7 @@ fn test_character(){
 fn test_stringval(){
     let program = String::from(r#""Hello   World""#);
     let mut t_stream = TokenStream::new(program.chars());
-    assert_eq!(t_stream.next(), Some(Token::STRINGVAL("Hello   World"))); 
+    assert_eq!(t_stream.next(), Some(Token::STRINGVAL("Hello   World".into()))); 
     assert_eq!(t_stream.next(), Some(Token::DOLLAR));
 }
 
@@ -389,7 +429,8 @@ fn test_numeral(){
 fn test_unknown_from_and(){
     let program = String::from("&a");
     let mut t_stream = TokenStream::new(program.chars());
-    assert_eq!(t_stream.next(), Some(Token::UNKNOWN("&")));
+    assert_eq!(t_stream.next(), Some(Token::ERROR(LexError::UnexpectedChar('&'))));
+    assert_eq!(t_stream.next(), Some(Token::ID("a")));
     assert_eq!(t_stream.next(), Some(Token::DOLLAR));
 }
 
@@ -397,30 +438,75 @@ fn test_unknown_from_and(){
 fn test_unknown_from_or(){
     let program = String::from("|a");
     let mut t_stream = TokenStream::new(program.chars());
-    assert_eq!(t_stream.next(), Some(Token::UNKNOWN("|")));
+    assert_eq!(t_stream.next(), Some(Token::ERROR(LexError::UnexpectedChar('|'))));
+    assert_eq!(t_stream.next(), Some(Token::ID("a")));
     assert_eq!(t_stream.next(), Some(Token::DOLLAR));
 }
 
-/*
 #[test]
 fn test_unknown_from_end(){
-    let program = String::from(r#""sdfdsfdsf"#);
+    let program = String::from("x \"sdf\ndsfdsf");
     let mut t_stream = TokenStream::new(program.chars());
-    assert_eq!(t_stream.next(), Some(Token::UNKNOWN));
+    assert_eq!(t_stream.next(), Some(Token::ID("x")));
+    let error = t_stream.next_spanned();
+    assert_eq!(error.token, Token::ERROR(LexError::UnterminatedString));
+    assert_eq!(error.span.range(), 2..3);
     assert_eq!(t_stream.next(), Some(Token::DOLLAR));
 }
-*/
 
-/*
 #[test]
 fn test_unknown_from_character(){
-    let program = String::from("'a");
+    let program = String::from("'a\nb '' '");
+    let mut t_stream = TokenStream::new(program.chars());
+    let tokens: Vec<_> = std::iter::from_fn(|| Some(t_stream.next_spanned()))
+        .take_while(|spanned| spanned.token != Token::DOLLAR)
+        .map(|spanned| (spanned.token, &program[spanned.span.range()]))
+        .collect();
+    assert_eq!(tokens, vec![
+        (Token::ERROR(LexError::UnterminatedChar), "'a"),
+        (Token::ID("b"), "b"),
+        (Token::ERROR(LexError::EmptyChar), "''"),
+        (Token::ERROR(LexError::UnterminatedChar), "'"),
+    ]);
+}
+
+#[test]
+fn test_unexpected_character(){
+    let program = String::from("a # ç b");
     let mut t_stream = TokenStream::new(program.chars());
-    assert_eq!(t_stream.next(), Some(Token::UNKNOWN));
+    assert_eq!(t_stream.next(), Some(Token::ID("a")));
+    let hash = t_stream.next_spanned();
+    assert_eq!(hash.token, Token::ERROR(LexError::UnexpectedChar('#')));
+    assert_eq!(hash.span.range(), 2..3);
+    let c = t_stream.next_spanned();
+    assert_eq!(c.token, Token::ERROR(LexError::UnexpectedChar('ç')));
+    assert_eq!(&program[c.span.range()], "ç");
+    assert_eq!(t_stream.next(), Some(Token::ID("b")));
+}
+
+#[test]
+fn test_dollar_is_not_the_end(){
+    let program = String::from("a $ b");
+    let mut t_stream = TokenStream::new(program.chars());
+    assert_eq!(t_stream.next(), Some(Token::ID("a")));
+    let dollar = t_stream.next_spanned();
+    assert_eq!(dollar.token, Token::ERROR(LexError::UnexpectedChar('$')));
+    assert_eq!(dollar.span.range(), 2..3);
+    assert_eq!(t_stream.next(), Some(Token::ID("b")));
     assert_eq!(t_stream.next(), Some(Token::DOLLAR));
 }
-*/
 
+#[test]
+fn test_malformed_number(){
+    let program = String::from("12ab 3. 4__0 4_ 5.6x 0x 0b102 0o_7 1._5 7");
+    let mut t_stream = TokenStream::new(program.chars());
+    for text in ["12ab", "3.", "4__0", "4_", "5.6x", "0x", "0b102", "0o_7", "1._5"] {
+        let error = t_stream.next_spanned();
+        assert_eq!(error.token, Token::ERROR(LexError::MalformedNumber));
+        assert_eq!(&program[error.span.range()], text);
+    }
+    assert_eq!(t_stream.next(), Some(Token::NUMERAL("7")));
+}
 
 #[test]
 fn test_mix_and_next_line() {
@@ -473,3 +559,132 @@ fn test_some_random() {
     assert_eq!(t_stream.next(), Some(Token::RIGHTBRACE));
     assert_eq!(t_stream.next(), Some(Token::DOLLAR));
 }
+
+#[test]
+fn test_spans() {
+    let program = String::from("var x\n  : integer;");
+    let mut t_stream = TokenStream::new(program.chars());
+
+    let var = t_stream.next_spanned();
+    assert_eq!(var.token, Token::VAR);
+    assert_eq!(var.span.range(), 0..3);
+    assert_eq!((var.span.start.line, var.span.start.column), (1, 1));
+    assert_eq!((var.span.end.line, var.span.end.column), (1, 4));
+
+    let x = t_stream.next_spanned();
+    assert_eq!(x.token, Token::ID("x"));
+    assert_eq!(&program[x.span.range()], "x");
+
+    let colon = t_stream.next_spanned();
+    assert_eq!(colon.span.range(), 8..9);
+    assert_eq!((colon.span.start.line, colon.span.start.column), (2, 3));
+
+    let integer = t_stream.next_spanned();
+    assert_eq!(&program[integer.span.range()], "integer");
+    assert_eq!(t_stream.next_spanned().span.range(), 17..18);
+
+    let dollar = t_stream.next_spanned();
+    assert_eq!(dollar.token, Token::DOLLAR);
+    assert!(dollar.span.is_empty());
+}
+
+#[test]
+fn test_multiline_stringval_span() {
+    let program = String::from("x = \"first\nsecond\"; y");
+    let mut t_stream = TokenStream::new(program.chars());
+    t_stream.next_spanned();
+    t_stream.next_spanned();
+
+    let string = t_stream.next_spanned();
+    assert_eq!(string.token, Token::STRINGVAL("first\nsecond".into()));
+    assert_eq!(&program[string.span.range()], "\"first\nsecond\"");
+    assert_eq!((string.span.start.line, string.span.start.column), (1, 5));
+    assert_eq!((string.span.end.line, string.span.end.column), (2, 8));
+
+    assert_eq!(t_stream.next_spanned().token, Token::SEMICOLON);
+    let y = t_stream.next_spanned();
+    assert_eq!(y.token, Token::ID("y"));
+    assert_eq!((y.span.start.line, y.span.start.column), (2, 10));
+}
+
+#[test]
+fn test_non_ascii_columns() {
+    let program = String::from("\"çã\" a");
+    let mut t_stream = TokenStream::new(program.chars());
+    t_stream.next_spanned();
+
+    let a = t_stream.next_spanned();
+    assert_eq!(a.span.range(), 7..8);
+    assert_eq!(a.span.start.column, 6);
+}
+
+#[test]
+fn test_comments() {
+    let program = String::from("a // b / c\n/* d\n/* nested */ e */ f / g/**/h");
+    let mut t_stream = TokenStream::new(program.chars());
+    assert_eq!(t_stream.next(), Some(Token::ID("a")));
+
+    let f = t_stream.next_spanned();
+    assert_eq!(f.token, Token::ID("f"));
+    assert_eq!((f.span.start.line, f.span.start.column), (3, 19));
+
+    assert_eq!(t_stream.next(), Some(Token::DIVIDE));
+    assert_eq!(t_stream.next(), Some(Token::ID("g")));
+    assert_eq!(t_stream.next(), Some(Token::ID("h")));
+    assert_eq!(t_stream.next(), Some(Token::DOLLAR));
+}
+
+#[test]
+fn test_line_comment_at_end() {
+    let program = String::from("a //");
+    let mut t_stream = TokenStream::new(program.chars());
+    assert_eq!(t_stream.next(), Some(Token::ID("a")));
+    assert_eq!(t_stream.next(), Some(Token::DOLLAR));
+}
+
+#[test]
+fn test_unterminated_comment() {
+    let program = String::from("a\n /* b /* c */ d");
+    let mut t_stream = TokenStream::new(program.chars());
+    assert_eq!(t_stream.next(), Some(Token::ID("a")));
+
+    let error = t_stream.next_spanned();
+    assert_eq!(error.token, Token::ERROR(LexError::UnterminatedComment));
+    assert_eq!(&program[error.span.range()], "/*");
+    assert_eq!((error.span.start.line, error.span.start.column), (2, 2));
+    assert_eq!(t_stream.next(), Some(Token::DOLLAR));
+}
+
+#[test]
+fn test_escapes() {
+    let program = String::from(r#""a\"b\\c\n\td\0" '\n' '\'' '\\' '"' "\u{48}\u{e7}\u{1F600}" "plain""#);
+    let mut t_stream = TokenStream::new(program.chars());
+    assert_eq!(t_stream.next(), Some(Token::STRINGVAL("a\"b\\c\n\td\0".into())));
+    assert_eq!(t_stream.next(), Some(Token::CHARACTER('\n')));
+    assert_eq!(t_stream.next(), Some(Token::CHARACTER('\'')));
+    assert_eq!(t_stream.next(), Some(Token::CHARACTER('\\')));
+    assert_eq!(t_stream.next(), Some(Token::CHARACTER('"')));
+    assert_eq!(t_stream.next(), Some(Token::STRINGVAL("Hç😀".into())));
+    assert_eq!(t_stream.next(), Some(Token::STRINGVAL(Cow::Borrowed("plain"))));
+    assert_eq!(t_stream.next(), Some(Token::DOLLAR));
+}
+
+#[test]
+fn test_invalid_escapes() {
+    let program = String::from(r#""ab\q" x '\u{110000}' "\u{1234567}" "\u{41" "\u{}" y"#);
+    let mut t_stream = TokenStream::new(program.chars());
+
+    // the whole literal is skipped, the error points at the escape
+    let error = t_stream.next_spanned();
+    assert_eq!(error.token, Token::ERROR(LexError::InvalidEscape));
+    assert_eq!(&program[error.span.range()], r"\q");
+    assert_eq!((error.span.start.line, error.span.start.column), (1, 4));
+    assert_eq!(t_stream.next(), Some(Token::ID("x")));
+
+    for escape in [r"\u{110000}", r"\u{1234567}", r"\u{41", r"\u{}"] {
+        let error = t_stream.next_spanned();
+        assert_eq!(error.token, Token::ERROR(LexError::InvalidEscape));
+        assert_eq!(&program[error.span.range()], escape);
+    }
+    assert_eq!(t_stream.next(), Some(Token::ID("y")));
+}
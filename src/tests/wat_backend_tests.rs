@@ -0,0 +1,457 @@
+use std::path::Path;
+use std::process::Command;
+
+use super::common::{checked, same_as_the_interpreter, CALLS, PROGRAMS, STRINGS, STRING_CALLS};
+use crate::backend::wat::RUNTIME;
+use crate::interpreter::Value;
+use crate::{emit_wat, run, ErrorCode, TypedAst};
+
+// The generated module without the runtime it starts with
+fn generated(code: &str) -> String {
+    let wat = emit_wat(&checked(code)).unwrap();
+    wat.strip_prefix("(module\n").and_then(|wat| wat.strip_prefix(RUNTIME)).expect("the runtime comes first").to_string()
+}
+
+const PROGRAM: &str = "type Row = array[2] of integer
+type Pair = struct { c : char; row : Row }
+
+function sum(p : Pair) : integer {
+    var i, s : integer;
+    while (true) {
+        var v : integer;
+        if (i >= 2) break;
+        v = p.row[i++];
+        if (v == 0 || p.c > 'x') continue;
+        s = s + 10 / v;
+    }
+    do { var k : real; k = 0.5; s = s - integer(k * 4.0); } while (s > 100);
+    return -s;
+}
+
+function main(s : string) : boolean {
+    var p : Pair;
+    var i : integer;
+    p.row[i] = sum(p);
+    return p != p && s + \"!\" < \"a\\\"b\\n\";
+}";
+
+#[test]
+fn test_generated_module() {
+    assert_eq!(generated(PROGRAM), "\n  (memory (export \"memory\") 17)
+  (global $ss.sp (mut i32) (i32.const 1048608))
+  (global $ss.heap (mut i32) (i32.const 1048608))
+  (global $ss.limit i32 (i32.const 24))
+
+  (data (i32.const 8) \"\\01\\00\\00\\00!\")
+  (data (i32.const 16) \"\\04\\00\\00\\00a\\22b\\0a\")
+
+  (func $ss.compare.Row (param $a i32) (param $b i32) (result i32)
+    (local $i i32)
+    (local $order i32)
+    block $done
+      loop $next
+        local.get $i
+        i32.const 2
+        i32.ge_u
+        br_if $done
+        local.get $a
+        local.get $i
+        i32.const 4
+        i32.mul
+        i32.add
+        i32.load
+        local.get $b
+        local.get $i
+        i32.const 4
+        i32.mul
+        i32.add
+        i32.load
+        call $ss.compare.integer
+        local.tee $order
+        if
+          local.get $order
+          return
+        end
+        local.get $i
+        i32.const 1
+        i32.add
+        local.set $i
+        br $next
+      end
+    end
+    i32.const 0
+  )
+
+  (func $ss.compare.Pair (param $a i32) (param $b i32) (result i32)
+    (local $order i32)
+    local.get $a
+    i32.load
+    local.get $b
+    i32.load
+    call $ss.compare.char
+    local.tee $order
+    if
+      local.get $order
+      return
+    end
+    local.get $a
+    i32.const 4
+    i32.add
+    local.get $b
+    i32.const 4
+    i32.add
+    call $ss.compare.Row
+  )
+
+  (func $sum (export \"sum\") (param $p i32) (result i32)
+    (local $i i32)
+    (local $s i32)
+    (local $v i32)
+    (local $k f64)
+    (local $ss.top i32)
+    (local $ss.fp i32)
+    global.get $ss.sp
+    local.tee $ss.top
+    global.get $ss.limit
+    i32.sub
+    i32.const 16
+    i32.lt_u
+    if
+      unreachable
+    end
+    local.get $ss.top
+    i32.const 16
+    i32.sub
+    local.tee $ss.fp
+    global.set $ss.sp
+    local.get $ss.fp
+    local.get $p
+    i32.const 12
+    memory.copy
+    local.get $ss.fp
+    local.set $p
+    i32.const 0
+    local.set $i
+    i32.const 0
+    local.set $s
+    block $break.0
+      loop $continue.0
+        i32.const 1
+        i32.eqz
+        br_if $break.0
+        i32.const 0
+        local.set $v
+        local.get $i
+        i32.const 2
+        i32.ge_s
+        if
+          br $break.0
+        end
+        local.get $p
+        local.get $i
+        local.get $i
+        i32.const 1
+        i32.add
+        local.set $i
+        i32.const 2
+        i32.const 9
+        i32.const 19
+        call $ss.index
+        i32.const 4
+        i32.mul
+        i32.add
+        i32.load offset=4
+        local.set $v
+        local.get $v
+        i32.const 0
+        i32.eq
+        if (result i32)
+          i32.const 1
+        else
+          local.get $p
+          i32.load
+          i32.const 120
+          i32.gt_u
+        end
+        if
+          br $continue.0
+        end
+        local.get $s
+        i32.const 10
+        local.get $v
+        i32.const 11
+        i32.const 17
+        call $ss.div
+        i32.add
+        local.set $s
+        br $continue.0
+      end
+    end
+    block $break.1
+      loop $do.1
+        block $continue.1
+          f64.const 0
+          local.set $k
+          f64.const 0.5
+          local.set $k
+          local.get $s
+          local.get $k
+          f64.const 4.0
+          f64.mul
+          i32.trunc_sat_f64_s
+          i32.sub
+          local.set $s
+        end
+        local.get $s
+        i32.const 100
+        i32.gt_s
+        br_if $do.1
+      end
+    end
+    i32.const 0
+    local.get $s
+    i32.sub
+    local.get $ss.top
+    global.set $ss.sp
+    return
+  )
+
+  (func $main (export \"main\") (param $s i32) (result i32)
+    (local $p i32)
+    (local $i i32)
+    (local $ss.t0 i32)
+    (local $ss.t1 i32)
+    (local $ss.top i32)
+    (local $ss.fp i32)
+    global.get $ss.sp
+    local.tee $ss.top
+    global.get $ss.limit
+    i32.sub
+    i32.const 16
+    i32.lt_u
+    if
+      unreachable
+    end
+    local.get $ss.top
+    i32.const 16
+    i32.sub
+    local.tee $ss.fp
+    global.set $ss.sp
+    local.get $ss.fp
+    local.tee $p
+    i32.const 0
+    i32.const 12
+    memory.fill
+    i32.const 0
+    local.set $i
+    local.get $i
+    local.set $ss.t0
+    local.get $p
+    call $sum
+    local.set $ss.t1
+    local.get $p
+    local.get $ss.t0
+    i32.const 2
+    i32.const 20
+    i32.const 11
+    call $ss.index
+    i32.const 4
+    i32.mul
+    i32.add
+    local.get $ss.t1
+    i32.store offset=4
+    local.get $p
+    local.get $p
+    call $ss.compare.Pair
+    i32.const 0
+    i32.ne
+    if (result i32)
+      local.get $s
+      i32.const 8
+      call $ss.concat
+      i32.const 16
+      call $ss.compare.string
+      i32.const 0
+      i32.lt_s
+    else
+      i32.const 0
+    end
+    local.get $ss.top
+    global.set $ss.sp
+    return
+  )
+)
+");
+}
+
+#[test]
+fn test_returned_struct() {
+    let wat = generated("type P = struct { x : integer; y : real }
+
+function make(n : integer) : P {
+    var p : P;
+    p.y = real(n);
+    return p;
+}
+
+function get(n : integer) : real {
+    var q : P;
+    q = make(n);
+    return q.y;
+}");
+    // the struct is copied out of the frame of `make` as soon as it returns, then into `q`
+    assert!(wat.contains("    local.get $q\n    local.get $ss.fp\n    i32.const 16\n    i32.add\n    local.get $n\n    call $make\n    \
+                          i32.const 16\n    memory.copy\n    local.get $ss.fp\n    i32.const 16\n    i32.add\n    i32.const 16\n    memory.copy\n"));
+    assert!(wat.ends_with("    local.get $q\n    f64.load offset=8\n    local.get $ss.top\n    global.set $ss.sp\n    return\n  )\n)\n"));
+}
+
+#[test]
+fn test_string_indexing() {
+    // chars are whole Unicode characters, the runtime finds them in the UTF-8 of the strings
+    let code = "function f(s : string, i : integer) : char {
+    var c : char;
+    c = s[i];
+    s[i] = '\u{e9}';
+    if (c < 'a') return s[i + 1];
+    return c;
+}";
+    assert!(generated(code).ends_with("  (func $f (export \"f\") (param $s i32) (param $i i32) (result i32)
+    (local $c i32)
+    i32.const 0
+    local.set $c
+    local.get $s
+    local.get $i
+    i32.const 3
+    i32.const 11
+    call $ss.char_at
+    local.set $c
+    local.get $s
+    local.get $i
+    i32.const 233
+    i32.const 4
+    i32.const 7
+    call $ss.with_char
+    local.set $s
+    local.get $c
+    i32.const 97
+    i32.lt_u
+    if
+      local.get $s
+      local.get $i
+      i32.const 1
+      i32.add
+      i32.const 5
+      i32.const 27
+      call $ss.char_at
+      return
+    end
+    local.get $c
+    return
+  )
+)
+"));
+}
+
+#[test]
+fn test_too_large_for_memory() {
+    // the semantic analysis lets these through, but a char takes 4 bytes in memory
+    let code = "type Text = array[1073741824] of char
+type Big = array[500000000] of integer
+
+function f(n : integer) : integer {
+    var t : Text;
+    return n;
+}";
+    assert_eq!(emit_wat(&checked(code)).unwrap_err().render("main.ss", code), "\
+error[E0105]: the values of type `Text` take more than 4294967295 bytes of memory
+ --> main.ss:1:6
+  |
+1 | type Text = array[1073741824] of char
+  |      ^^^^
+
+");
+
+    // two of them fit, their function traps when the stack is too small for them
+    let code = "type Big = array[500000000] of integer
+
+function two(n : integer) : integer {
+    var a, b : Big;
+    return n;
+}
+
+function three(n : integer) : integer {
+    var a, b, c : Big;
+    return n;
+}";
+    let errors = emit_wat(&checked(code)).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].code, ErrorCode::TypeTooLarge);
+    assert_eq!(errors[0].message, "the arrays and structs of `three` take more than 4294967295 bytes of memory");
+}
+
+// Reports the runtime errors like the programs of the C backend, prints what the call returns
+const HOST: &str = r#"const fs = require("fs");
+const [file, name, kind, ...args] = process.argv.slice(2);
+class Failure extends Error {}
+function fail(code, message, line, column) {
+  process.stderr.write(`error[${code}]: ${message} at ${line}:${column}\n`);
+  throw new Failure();
+}
+const imports = { ss: {
+  division_by_zero: (line, column) => fail("E0301", "division by zero", line, column),
+  out_of_bounds: (index, length, line, column) =>
+    fail("E0300", `index ${index} is out of bounds for an array of ${length} elements`, line, column),
+  string_out_of_bounds: (index, length, line, column) =>
+    fail("E0300", `index ${index} is out of bounds for a string of ${length} characters`, line, column),
+} };
+WebAssembly.instantiate(fs.readFileSync(file), imports).then(({ instance }) => {
+  try {
+    const result = instance.exports[name](...args.map(Number));
+    if (kind === "string") {
+      const memory = instance.exports.memory.buffer;
+      const length = new DataView(memory).getUint32(result, true);
+      process.stdout.write(Buffer.from(memory, result + 4, length).toString() + "\n");
+    } else {
+      process.stdout.write(`${result}\n`);
+    }
+  } catch (error) {
+    if (!(error instanceof Failure)) throw error;
+    process.exitCode = 5;
+  }
+});
+"#;
+
+fn host_value(value: &Value) -> String {
+    match value {
+        Value::Integer(n) => n.to_string(),
+        Value::Real(x) => format!("{:?}", x),
+        Value::Char(ch) => u32::from(*ch).to_string(),
+        Value::Boolean(b) => u8::from(*b).to_string(),
+        Value::String(s) => s.clone(),
+        Value::Array(_) | Value::Struct(_) => unreachable!("the host only reads scalars and strings"),
+    }
+}
+
+/*
+ * Assembles the module with `wat2wasm` and makes the calls with `node`, checking that they do
+ * what the interpreter does. Needs wat2wasm and node, run with
+ * cargo test wat_backend -- --ignored
+ */
+#[test]
+#[ignore]
+fn test_same_results_as_the_interpreter() {
+    let run_wat = |dir: &Path, program: &TypedAst, function: &str, args: &[Value]| {
+        std::fs::write(dir.join("main.wat"), emit_wat(program).unwrap()).unwrap();
+        std::fs::write(dir.join("host.js"), HOST).unwrap();
+        let status = Command::new("wat2wasm").args(["main.wat", "-o", "main.wasm"]).current_dir(dir).status().unwrap();
+        assert!(status.success());
+        let kind = if matches!(run(program, function, args.to_vec()), Ok(Value::String(_))) { "string" } else { "number" };
+        Command::new("node").args(["host.js", "main.wasm", function, kind])
+            .args(args.iter().map(host_value))
+            .current_dir(dir)
+            .output()
+            .unwrap()
+    };
+    same_as_the_interpreter("wat", PROGRAMS, &CALLS, host_value, run_wat);
+    same_as_the_interpreter("wat", STRINGS, &STRING_CALLS, host_value, run_wat);
+}